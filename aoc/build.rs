@@ -0,0 +1,68 @@
+//! Compute a version for each day's solvers: a hash of every source file they are built from.
+//! The answer cache uses it to tell when a cached answer may be out of date.
+
+use std::{env, fs, path::{Path, PathBuf}};
+use sha2::{Digest, Sha256};
+
+/// Add every file under `path` to `files`, recursing into directories.
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) {
+    if path.is_dir() {
+        for entry in fs::read_dir(path).unwrap() {
+            collect_files(&entry.unwrap().path(), files);
+        }
+    } else if path.is_file() {
+        files.push(path.to_path_buf());
+    }
+}
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let root = manifest_dir.parent().unwrap();
+
+    // Sources which every day's solvers depend on.
+    let shared = [
+        root.join("aoc_common").join("src"),
+        root.join("aoc_common").join("Cargo.toml"),
+        root.join("aoc_days").join("src"),
+        root.join("aoc_days").join("Cargo.toml"),
+        root.join("Cargo.lock"),
+    ];
+    for path in &shared {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+    let mut days = fs::read_dir(root)
+        .unwrap()
+        .filter_map(|entry| {
+            let name = entry.unwrap().file_name().into_string().ok()?;
+            name.strip_prefix("day")?.parse::<u32>().ok()
+        })
+        .collect::<Vec<u32>>();
+    days.sort();
+
+    let mut versions = String::from("/// The version of each day's solvers, by day number, as a hex SHA-256 of their sources.\n");
+    versions.push_str(&format!("pub const SOLVER_VERSIONS: [(u32, &str); {}] = [\n", days.len()));
+    for day in days {
+        let day_dir = root.join(format!("day{}", day));
+        let inputs = [day_dir.join("src"), day_dir.join("Cargo.toml")];
+        let mut files = Vec::new();
+        for path in &inputs {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+        for path in inputs.iter().chain(shared.iter()) {
+            collect_files(path, &mut files);
+        }
+        files.sort();
+
+        let mut hasher = Sha256::new();
+        for file in files {
+            hasher.update(file.strip_prefix(root).unwrap().to_string_lossy().as_bytes());
+            hasher.update(fs::read(&file).unwrap());
+        }
+        let hash = hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect::<String>();
+        versions.push_str(&format!("    ({}, \"{}\"),\n", day, hash));
+    }
+    versions.push_str("];\n");
+
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("solver_versions.rs");
+    fs::write(out_path, versions).unwrap();
+}
@@ -0,0 +1,190 @@
+//! Benchmarks of each solver on its real puzzle input, with a history of past runs
+//! which can be compared to find performance regressions.
+
+use std::{fs, path::{Path, PathBuf}, time::{Duration, SystemTime, UNIX_EPOCH}};
+use aoc_common::{PartResult, Solver};
+use serde::{Deserialize, Serialize};
+use crate::report::format_duration;
+
+/// Timings of one part of one day's puzzle.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub day: u32,
+    pub part: usize,
+    pub samples: usize,
+    pub median_seconds: f64,
+    pub min_seconds: f64,
+}
+
+/// One run of the benchmarks, as saved in the history file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BenchRun {
+    /// When the run finished, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// An optional name for the run, e.g. the change being measured.
+    pub label: Option<String>,
+    pub results: Vec<BenchResult>,
+}
+
+impl BenchRun {
+    pub fn new(label: Option<String>, results: Vec<BenchResult>) -> BenchRun {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+        BenchRun { timestamp, label, results }
+    }
+}
+
+/// Path to the history of benchmark runs, `bench_history.json` at the root of the repository.
+/// Timings depend on the machine, so the history is not checked in.
+pub fn default_history_path() -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "..", "bench_history.json"].iter().collect()
+}
+
+/// Load the history of benchmark runs, oldest first. The history is empty if the file doesn't exist yet.
+pub fn load_history(path: &Path) -> Result<Vec<BenchRun>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let text = fs::read_to_string(path)
+        .map_err(|error| format!("could not read benchmark history {}: {}", path.display(), error))?;
+    serde_json::from_str(&text)
+        .map_err(|error| format!("invalid benchmark history {}: {}", path.display(), error))
+}
+
+pub fn save_history(path: &Path, history: &[BenchRun]) -> Result<(), String> {
+    let mut text = serde_json::to_string_pretty(history).unwrap();
+    text.push('\n');
+    fs::write(path, text).map_err(|error| format!("could not write benchmark history {}: {}", path.display(), error))
+}
+
+/// Time `samples` runs of `solver` on `text`, after one warm-up run.
+/// Fails if the solver gives no answer.
+pub fn bench_part(day: u32, part: usize, solver: Solver, text: &str, samples: usize) -> Result<BenchResult, String> {
+    PartResult::run(day, part, solver, text).answer?;
+    let mut times = (0..samples.max(1))
+        .map(|_| PartResult::run(day, part, solver, text).elapsed)
+        .collect::<Vec<Duration>>();
+    times.sort();
+    Ok(BenchResult {
+        day,
+        part,
+        samples: times.len(),
+        median_seconds: times[times.len() / 2].as_secs_f64(),
+        min_seconds: times[0].as_secs_f64(),
+    })
+}
+
+/// The median times of one part in two benchmark runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub day: u32,
+    pub part: usize,
+    pub baseline_seconds: f64,
+    pub current_seconds: f64,
+}
+
+impl Comparison {
+    /// How much slower the current run is than the baseline, in percent. Negative if it is faster.
+    pub fn change_percent(&self) -> f64 {
+        (self.current_seconds - self.baseline_seconds) / self.baseline_seconds * 100.0
+    }
+
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.change_percent() > threshold_percent
+    }
+}
+
+/// Compare the parts which were benchmarked in both runs.
+pub fn compare(baseline: &BenchRun, current: &BenchRun) -> Vec<Comparison> {
+    current
+        .results
+        .iter()
+        .filter_map(|result| {
+            let base = baseline.results.iter().find(|base| base.day == result.day && base.part == result.part)?;
+            Some(Comparison {
+                day: result.day,
+                part: result.part,
+                baseline_seconds: base.median_seconds,
+                current_seconds: result.median_seconds,
+            })
+        })
+        .collect()
+}
+
+/// Render the comparisons as a table, marking each part which is slower by more than `threshold_percent`.
+pub fn format_comparison(comparisons: &[Comparison], threshold_percent: f64) -> String {
+    let mut table = format!("{:>3}  {:>4}  {:>10}  {:>10}  {:>8}\n", "Day", "Part", "Baseline", "Current", "Change");
+    table.push_str(&format!("{}\n", "-".repeat(3 + 2 + 4 + 2 + 10 + 2 + 10 + 2 + 8)));
+    for comparison in comparisons {
+        table.push_str(&format!("{:>3}  {:>4}  {:>10}  {:>10}  {:>+7.1}%{}\n",
+            comparison.day,
+            comparison.part,
+            format_duration(Duration::from_secs_f64(comparison.baseline_seconds)),
+            format_duration(Duration::from_secs_f64(comparison.current_seconds)),
+            comparison.change_percent(),
+            if comparison.is_regression(threshold_percent) { "  REGRESSION" } else { "" }));
+    }
+    table
+}
+
+/// List the runs in the history, numbered from 1, oldest first.
+pub fn format_history(history: &[BenchRun]) -> String {
+    history
+        .iter()
+        .enumerate()
+        .map(|(i, run)| format!("{:>3}: {} parts, timestamp {}{}\n", i + 1, run.results.len(), run.timestamp,
+            run.label.as_ref().map_or(String::new(), |label| format!(", {}", label))))
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Solution;
+    use super::*;
+
+    fn result(day: u32, part: usize, median_seconds: f64) -> BenchResult {
+        BenchResult { day, part, samples: 5, median_seconds, min_seconds: median_seconds }
+    }
+
+    #[test]
+    fn test_bench_part() {
+        let result = bench_part(3, 1, |text| Ok(Solution::new(text.len())), "abc", 4).unwrap();
+        assert_eq!((result.day, result.part, result.samples), (3, 1, 4));
+        assert!(result.min_seconds <= result.median_seconds);
+        let error = bench_part(3, 1, |text| Err(aoc_common::ParseError::missing_section(text, "x")), "abc", 4);
+        assert!(error.is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = BenchRun { timestamp: 1, label: None, results: vec![result(8, 2, 0.010), result(15, 2, 4.0)] };
+        let current = BenchRun {
+            timestamp: 2,
+            label: Some("faster day 15".to_string()),
+            results: vec![result(8, 2, 0.012), result(15, 2, 1.0), result(17, 2, 0.3)],
+        };
+        let comparisons = compare(&baseline, &current);
+        assert_eq!(comparisons.len(), 2);
+        assert!((comparisons[0].change_percent() - 20.0).abs() < 1e-9);
+        assert!(comparisons[0].is_regression(10.0));
+        assert!(!comparisons[0].is_regression(25.0));
+        assert!((comparisons[1].change_percent() + 75.0).abs() < 1e-9);
+        assert!(!comparisons[1].is_regression(10.0));
+
+        let table = format_comparison(&comparisons, 10.0);
+        let lines = table.lines().collect::<Vec<&str>>();
+        assert_eq!(lines[2], "  8     2     10.0 ms     12.0 ms    +20.0%  REGRESSION");
+        assert_eq!(lines[3], " 15     2      4.00 s      1.00 s    -75.0%");
+    }
+
+    #[test]
+    fn test_history_round_trip() {
+        let path = std::env::temp_dir().join(format!("aoc_bench_history_{}.json", std::process::id()));
+        assert_eq!(load_history(&path), Ok(Vec::new()));
+        let history = vec![BenchRun { timestamp: 1, label: Some("baseline".to_string()), results: vec![result(1, 1, 0.5)] }];
+        save_history(&path, &history).unwrap();
+        assert_eq!(load_history(&path), Ok(history.clone()));
+        assert_eq!(format_history(&history), "  1: 1 parts, timestamp 1, baseline\n");
+        fs::remove_file(path).unwrap();
+    }
+}
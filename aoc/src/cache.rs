@@ -0,0 +1,213 @@
+//! A cache of answers on disk, so that expensive parts don't need solving again for the same input.
+//!
+//! Each answer is stored with a hash of the input it was solved from, and the version of the day's
+//! solvers: a hash of their sources, computed when `aoc` is built. An answer is only used again
+//! for the same input and the same solver version.
+
+use std::{collections::BTreeMap, fs, path::{Path, PathBuf}, time::Instant};
+use aoc_common::{PartResult, Solution};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+include!(concat!(env!("OUT_DIR"), "/solver_versions.rs"));
+
+/// The version of day `day`'s solvers, which changes whenever their sources change.
+pub fn solver_version(day: u32) -> &'static str {
+    SOLVER_VERSIONS.iter().find(|&&(number, _)| number == day).map_or("", |&(_, version)| version)
+}
+
+/// The SHA-256 of `text`, in hex.
+pub fn sha256_hex(text: &str) -> String {
+    Sha256::digest(text.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// One cached answer, as saved in the cache file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CachedAnswer {
+    pub day: u32,
+    pub part: usize,
+    pub input_sha256: String,
+    pub solver_version: String,
+    pub answer: String,
+    pub intermediates: BTreeMap<String, String>,
+    /// How long the solver took when it found this answer.
+    pub elapsed_seconds: f64,
+}
+
+/// The cached answers, and the file they are kept in.
+#[derive(Debug)]
+pub struct Cache {
+    path: PathBuf,
+    entries: Vec<CachedAnswer>,
+}
+
+/// Path to the answer cache, `answer_cache.json` at the root of the repository.
+/// The cache is only useful on one machine, so it is not checked in.
+pub fn default_cache_path() -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "..", "answer_cache.json"].iter().collect()
+}
+
+impl Cache {
+    /// Load the cache from `path`. The cache is empty if the file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Cache, String> {
+        let mut cache = Cache { path: path.to_path_buf(), entries: Vec::new() };
+        if path.exists() {
+            let text = fs::read_to_string(path)
+                .map_err(|error| format!("could not read answer cache {}: {}", path.display(), error))?;
+            cache.entries = serde_json::from_str(&text)
+                .map_err(|error| format!("invalid answer cache {}: {}", path.display(), error))?;
+        }
+        Ok(cache)
+    }
+
+    /// Load the cache from `path`, or start an empty one there if the file can't be read, because
+    /// a broken cache shouldn't stop the solvers from running.
+    pub fn load_or_empty(path: &Path) -> Cache {
+        Cache::load(path).unwrap_or_else(|error| {
+            tracing::warn!(%error, "ignoring the answer cache");
+            Cache { path: path.to_path_buf(), entries: Vec::new() }
+        })
+    }
+
+    /// Save the cache to its file. The file is replaced in one step, so that a run which is
+    /// interrupted, or another `aoc` saving at the same time, can't leave it half written.
+    pub fn save(&self) -> Result<(), String> {
+        let mut text = serde_json::to_string_pretty(&self.entries).unwrap();
+        text.push('\n');
+        let temporary_path = self.path.with_extension(format!("json.{}", std::process::id()));
+        fs::write(&temporary_path, text)
+            .and_then(|_| fs::rename(&temporary_path, &self.path))
+            .map_err(|error| format!("could not write answer cache {}: {}", self.path.display(), error))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Look up the answer to part `part` of day `day` for the input `text`.
+    pub fn get(&self, day: u32, part: usize, text: &str) -> Option<PartResult> {
+        let start = Instant::now();
+        let input_sha256 = sha256_hex(text);
+        let version = solver_version(day);
+        let entry = self.entries.iter().find(|entry| {
+            entry.day == day && entry.part == part && entry.input_sha256 == input_sha256 && entry.solver_version == version
+        })?;
+        let solution = Solution {
+            answer: entry.answer.clone(),
+            intermediates: entry.intermediates.iter().map(|(name, value)| (name.clone().into(), value.clone())).collect(),
+        };
+        Some(PartResult { day, part, answer: Ok(solution), elapsed: start.elapsed() })
+    }
+
+    /// Add the answer in `result`, solved from the input `text`, replacing any earlier answer for
+    /// the same day, part and input. Results without an answer are not cached.
+    pub fn insert(&mut self, result: &PartResult, text: &str) {
+        let solution = match &result.answer {
+            Ok(solution) => solution,
+            Err(_) => return,
+        };
+        let input_sha256 = sha256_hex(text);
+        self.entries.retain(|entry| {
+            !(entry.day == result.day && entry.part == result.part && entry.input_sha256 == input_sha256)
+        });
+        self.entries.push(CachedAnswer {
+            day: result.day,
+            part: result.part,
+            input_sha256,
+            solver_version: solver_version(result.day).to_string(),
+            answer: solution.answer.clone(),
+            intermediates: solution.intermediates.iter().map(|(name, value)| (name.to_string(), value.clone())).collect(),
+            elapsed_seconds: result.elapsed.as_secs_f64(),
+        });
+    }
+
+    /// Remove the cached answers for day `day`, or for every day if `day` is `None`.
+    /// Returns the number of answers removed.
+    pub fn clear(&mut self, day: Option<u32>) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|entry| day.is_some_and(|day| entry.day != day));
+        before - self.entries.len()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::*;
+
+    fn result(day: u32, part: usize, answer: Result<Solution, String>) -> PartResult {
+        PartResult { day, part, answer, elapsed: Duration::from_millis(1500) }
+    }
+
+    fn empty_cache(name: &str) -> Cache {
+        let path = std::env::temp_dir().join(format!("aoc_cache_test_{}_{}.json", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        Cache::load(&path).unwrap()
+    }
+
+    #[test]
+    fn test_solver_versions() {
+        assert_eq!(SOLVER_VERSIONS.len(), aoc_days::DAYS.len());
+        assert_eq!(solver_version(7).len(), 64);
+        assert_ne!(solver_version(7), solver_version(8));
+    }
+
+    #[test]
+    fn test_get_and_insert() {
+        let mut cache = empty_cache("get");
+        assert_eq!(cache.get(13, 1, "939\n7,13"), None);
+        cache.insert(&result(13, 1, Ok(Solution::new(295).with("bus_id", 59))), "939\n7,13");
+        cache.insert(&result(13, 2, Err("no input".to_string())), "939\n7,13");
+        assert_eq!(cache.len(), 1);
+
+        let cached = cache.get(13, 1, "939\n7,13").unwrap();
+        assert_eq!(cached.answer, Ok(Solution::new(295).with("bus_id", 59)));
+        assert!(cached.elapsed < Duration::from_millis(1500));
+        // A different input, part or day is a different answer.
+        assert_eq!(cache.get(13, 1, "939\n7,14"), None);
+        assert_eq!(cache.get(13, 2, "939\n7,13"), None);
+        assert_eq!(cache.get(12, 1, "939\n7,13"), None);
+
+        // A newer answer for the same input replaces the old one.
+        cache.insert(&result(13, 1, Ok(Solution::new(296))), "939\n7,13");
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(13, 1, "939\n7,13").unwrap().answer, Ok(Solution::new(296)));
+    }
+
+    #[test]
+    fn test_other_solver_version() {
+        let mut cache = empty_cache("version");
+        cache.insert(&result(13, 1, Ok(Solution::new(295))), "939\n7,13");
+        cache.entries[0].solver_version = "0".repeat(64);
+        assert_eq!(cache.get(13, 1, "939\n7,13"), None);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let mut cache = empty_cache("save");
+        cache.insert(&result(15, 2, Ok(Solution::new(175594))), "0,3,6");
+        cache.save().unwrap();
+        let loaded = Cache::load(&cache.path).unwrap();
+        assert_eq!(loaded.entries, cache.entries);
+        fs::remove_file(&cache.path).unwrap();
+
+        fs::write(&cache.path, "not json").unwrap();
+        assert!(Cache::load(&cache.path).is_err());
+        assert_eq!(Cache::load_or_empty(&cache.path).len(), 0);
+        fs::remove_file(&cache.path).unwrap();
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut cache = empty_cache("clear");
+        for day in [13, 15] {
+            cache.insert(&result(day, 1, Ok(Solution::new(1))), "input");
+            cache.insert(&result(day, 2, Ok(Solution::new(2))), "input");
+        }
+        assert_eq!(cache.clear(Some(13)), 2);
+        assert_eq!(cache.get(15, 1, "input").map(|result| result.answer), Some(Ok(Solution::new(1))));
+        assert_eq!(cache.clear(None), 2);
+        assert_eq!(cache.len(), 0);
+    }
+}
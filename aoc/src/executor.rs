@@ -0,0 +1,344 @@
+//! Run many solvers at once on a pool of threads.
+//!
+//! Each solver runs on its own thread, and at most `jobs` of a `Pool`'s threads run at the same time.
+//! A panic in one solver is caught and reported as that part's error, and a solver which runs
+//! for longer than the timeout is reported as timed out, so one broken day doesn't stop the rest.
+//! A thread which timed out still counts against its pool's `jobs` until its solver returns, even
+//! in later calls to `Pool::run`, so a solver stuck in a loop can't make more than `jobs` threads busy.
+
+use std::{any::Any, collections::HashMap, fmt, panic::{self, AssertUnwindSafe}, sync::{mpsc, Arc, Condvar, Mutex},
+    thread, time::{Duration, Instant}};
+use aoc_common::{PartResult, Solver};
+use crate::report::format_duration;
+
+/// One part of one day's puzzle, ready to solve.
+pub struct Task {
+    pub day: u32,
+    pub part: usize,
+    pub solver: Solver,
+    pub text: Arc<str>,
+}
+
+/// How a task ended.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Status {
+    /// The solver returned, with an answer or a parse error.
+    Finished,
+    /// The answer was found in the cache, so the solver didn't run.
+    Cached,
+    Panicked,
+    /// The solver was still running at the timeout. Its thread is left to finish on its own,
+    /// because Rust has no way to stop a thread, and its answer is ignored. The thread counts
+    /// against `jobs` until it finishes.
+    TimedOut,
+    /// The solver never ran, e.g. because the part has no input or no thread could be started.
+    NotRun,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Finished => write!(f, "finished"),
+            Status::Cached => write!(f, "cached"),
+            Status::Panicked => write!(f, "panicked"),
+            Status::TimedOut => write!(f, "timed out"),
+            Status::NotRun => write!(f, "not run"),
+        }
+    }
+}
+
+/// The result of one task, and how it ended.
+/// Panics and timeouts are described by the result's error.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaskReport {
+    pub result: PartResult,
+    pub status: Status,
+}
+
+impl TaskReport {
+    /// The report for a part which could not be run at all, e.g. because its input is missing.
+    pub fn not_run(result: PartResult) -> TaskReport {
+        TaskReport { result, status: Status::NotRun }
+    }
+}
+
+/// How many tasks to run at once, and for how long.
+#[derive(Clone, Copy, Debug)]
+pub struct Options {
+    /// The most tasks to run at the same time. At least 1.
+    pub jobs: usize,
+    /// How long each task may run for, or `None` to wait for as long as it takes.
+    pub timeout: Option<Duration>,
+}
+
+/// The number of threads to run tasks on when none is given: one per CPU.
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map(|jobs| jobs.get()).unwrap_or(1)
+}
+
+/// The number of a pool's solver threads which are running, including those which timed out,
+/// and a signal for when one of them finishes.
+type BusyThreads = (Mutex<usize>, Condvar);
+
+/// A place among a pool's busy threads, which a solver thread holds until it finishes.
+struct ThreadSlot {
+    busy: Arc<BusyThreads>,
+}
+
+impl ThreadSlot {
+    /// Take a place if fewer than `jobs` of the pool's solver threads are running, waiting up to
+    /// `wait` for one of them to finish if not, or for as long as it takes if `wait` is `None`.
+    fn take(busy: &Arc<BusyThreads>, jobs: usize, wait: Option<Duration>) -> Option<ThreadSlot> {
+        let (count, finished) = &**busy;
+        let count = count.lock().unwrap();
+        let mut count = match wait {
+            None => finished.wait_while(count, |count| *count >= jobs).unwrap(),
+            Some(wait) => finished.wait_timeout_while(count, wait, |count| *count >= jobs).unwrap().0,
+        };
+        if *count >= jobs {
+            return None;
+        }
+        *count += 1;
+        Some(ThreadSlot { busy: Arc::clone(busy) })
+    }
+}
+
+impl Drop for ThreadSlot {
+    fn drop(&mut self) {
+        let (count, finished) = &*self.busy;
+        *count.lock().unwrap() -= 1;
+        finished.notify_all();
+    }
+}
+
+/// Threads to run tasks on, with the same `Options` for every call to `run`. Each pool counts only
+/// its own threads, so a solver which timed out in one pool doesn't hold up another.
+pub struct Pool {
+    options: Options,
+    busy: Arc<BusyThreads>,
+}
+
+impl Pool {
+    pub fn new(options: Options) -> Pool {
+        Pool { options, busy: Arc::new((Mutex::new(0), Condvar::new())) }
+    }
+
+    pub fn options(&self) -> Options {
+        self.options
+    }
+
+    /// Run every task, and return their reports in the same order as `tasks`.
+    pub fn run(&self, tasks: Vec<Task>) -> Vec<TaskReport> {
+        run(tasks, self.options, &self.busy)
+    }
+}
+
+/// Run every task on threads counted in `busy`, and return their reports in the same order as `tasks`.
+fn run(tasks: Vec<Task>, options: Options, busy: &Arc<BusyThreads>) -> Vec<TaskReport> {
+    let jobs = options.jobs.max(1);
+    let mut reports: Vec<Option<TaskReport>> = vec![None; tasks.len()];
+    let (sender, receiver) = mpsc::channel::<(usize, TaskReport)>();
+    let mut waiting = tasks.into_iter().enumerate().peekable();
+    // The day, part and start time of each running task, by its index in `tasks`.
+    let mut running = HashMap::<usize, (u32, usize, Instant)>::new();
+
+    loop {
+        while waiting.peek().is_some() {
+            // With none of these tasks running, all of the threads are busy with solvers which
+            // timed out, or with other calls' tasks, so wait for one of them to finish.
+            let wait = if running.is_empty() { options.timeout } else { Some(Duration::ZERO) };
+            let slot = match ThreadSlot::take(busy, jobs, wait) {
+                Some(slot) => slot,
+                None => break,
+            };
+            let (index, task) = waiting.next().unwrap();
+            let (day, part) = (task.day, task.part);
+            let sender = sender.clone();
+            let spawned = thread::Builder::new()
+                .name(format!("day{}-part{}", day, part))
+                .spawn(move || {
+                    let report = run_task(task);
+                    drop(slot);
+                    // The receiver is gone if this task timed out and every other task has finished.
+                    let _ = sender.send((index, report));
+                });
+            match spawned {
+                Ok(_) => {
+                    running.insert(index, (day, part, Instant::now()));
+                }
+                Err(error) => {
+                    let result = PartResult { day, part, answer: Err(format!("could not start a thread: {}", error)),
+                        elapsed: Duration::default() };
+                    reports[index] = Some(TaskReport::not_run(result));
+                }
+            }
+        }
+        if running.is_empty() {
+            // The threads stayed busy for a whole timeout, so give up on the tasks which are left.
+            for (index, task) in waiting {
+                let message = "not run: the threads stayed busy with solvers which timed out".to_string();
+                let result = PartResult { day: task.day, part: task.part, answer: Err(message), elapsed: Duration::default() };
+                reports[index] = Some(TaskReport::not_run(result));
+            }
+            break;
+        }
+
+        let received = match options.timeout {
+            None => receiver.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+            Some(timeout) => {
+                let first_deadline = running.values().map(|&(_, _, start)| start + timeout).min().unwrap();
+                receiver.recv_timeout(first_deadline.saturating_duration_since(Instant::now()))
+            }
+        };
+        match received {
+            Ok((index, report)) => {
+                // Tasks which already timed out are no longer running, and their late reports are ignored.
+                if running.remove(&index).is_some() {
+                    reports[index] = Some(report);
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                let timeout = options.timeout.unwrap();
+                let timed_out = running
+                    .iter()
+                    .filter(|(_, &(_, _, start))| start.elapsed() >= timeout)
+                    .map(|(&index, _)| index)
+                    .collect::<Vec<usize>>();
+                for index in timed_out {
+                    let (day, part, start) = running.remove(&index).unwrap();
+                    tracing::warn!(day, part, "solver timed out");
+                    let result = PartResult { day, part, answer: Err(format!("timed out after {}", format_duration(timeout))),
+                        elapsed: start.elapsed() };
+                    reports[index] = Some(TaskReport { result, status: Status::TimedOut });
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => unreachable!("this function holds a sender"),
+        }
+    }
+
+    reports.into_iter().map(|report| report.unwrap()).collect()
+}
+
+/// Run one task on this thread, catching any panic.
+fn run_task(task: Task) -> TaskReport {
+    let start = Instant::now();
+    let (day, part) = (task.day, task.part);
+    match panic::catch_unwind(AssertUnwindSafe(|| PartResult::run(day, part, task.solver, &task.text))) {
+        Ok(result) => TaskReport { result, status: Status::Finished },
+        Err(payload) => {
+            let result = PartResult { day, part, answer: Err(format!("panicked: {}", panic_message(&*payload))),
+                elapsed: start.elapsed() };
+            TaskReport { result, status: Status::Panicked }
+        }
+    }
+}
+
+/// The message which a panic was started with, if it has one.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "no message"
+    }
+}
+
+/// Summarize how the tasks ended, how long they took in total, and how long they took together
+/// on `jobs` threads, e.g. `36 parts: 30 finished, 5 cached, 1 panicked, 0 timed out, 0 not run
+/// in 1.20 s (4.56 s of solver time on 4 threads)`.
+pub fn format_summary(reports: &[TaskReport], wall_time: Duration, jobs: usize) -> String {
+    let count = |status: Status| reports.iter().filter(|report| report.status == status).count();
+    let solver_time: Duration = reports.iter().map(|report| report.result.elapsed).sum();
+    format!("{} parts: {} {}, {} {}, {} {}, {} {}, {} {} in {} ({} of solver time on {} {})\n",
+        reports.len(),
+        count(Status::Finished), Status::Finished,
+        count(Status::Cached), Status::Cached,
+        count(Status::Panicked), Status::Panicked,
+        count(Status::TimedOut), Status::TimedOut,
+        count(Status::NotRun), Status::NotRun,
+        format_duration(wall_time), format_duration(solver_time),
+        jobs, if jobs == 1 { "thread" } else { "threads" })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{ParseError, Solution};
+    use super::*;
+
+    fn task(part: usize, solver: Solver) -> Task {
+        Task { day: 1, part, solver, text: Arc::from("abc") }
+    }
+
+    fn length(text: &str) -> Result<Solution, ParseError> {
+        Ok(Solution::new(text.len()))
+    }
+
+    fn broken(_: &str) -> Result<Solution, ParseError> {
+        panic!("broken solver")
+    }
+
+    fn slow(_: &str) -> Result<Solution, ParseError> {
+        thread::sleep(Duration::from_secs(1));
+        Ok(Solution::new(0))
+    }
+
+    #[test]
+    fn test_run_in_order() {
+        let tasks = (1..=6).map(|part| task(part, length)).collect();
+        let reports = Pool::new(Options { jobs: 3, timeout: None }).run(tasks);
+        assert_eq!(reports.iter().map(|report| report.result.part).collect::<Vec<usize>>(), vec![1, 2, 3, 4, 5, 6]);
+        assert!(reports.iter().all(|report| report.status == Status::Finished));
+        assert_eq!(reports[0].result.answer, Ok(Solution::new(3)));
+    }
+
+    #[test]
+    fn test_panic_is_isolated() {
+        let reports = Pool::new(Options { jobs: 1, timeout: None }).run(vec![task(1, broken), task(2, length)]);
+        assert_eq!(reports[0].status, Status::Panicked);
+        assert_eq!(reports[0].result.answer, Err("panicked: broken solver".to_string()));
+        assert_eq!(reports[1].status, Status::Finished);
+        assert_eq!(reports[1].result.answer, Ok(Solution::new(3)));
+    }
+
+    #[test]
+    fn test_timeout() {
+        let start = Instant::now();
+        let options = Options { jobs: 2, timeout: Some(Duration::from_millis(100)) };
+        let reports = Pool::new(options).run(vec![task(1, slow), task(2, length)]);
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert_eq!(reports[0].status, Status::TimedOut);
+        assert_eq!(reports[0].result.answer, Err("timed out after 100.0 ms".to_string()));
+        assert_eq!(reports[1].status, Status::Finished);
+        assert_eq!(reports[1].result.answer, Ok(Solution::new(3)));
+    }
+
+    #[test]
+    fn test_timed_out_threads_count_against_jobs() {
+        let pool = Pool::new(Options { jobs: 1, timeout: Some(Duration::from_millis(100)) });
+        let reports = pool.run(vec![task(1, slow), task(2, length)]);
+        assert_eq!(reports[0].status, Status::TimedOut);
+        assert_eq!(reports[1].status, Status::NotRun);
+        assert_eq!(reports[1].result.answer, Err("not run: the threads stayed busy with solvers which timed out".to_string()));
+        // The solver which timed out still holds the pool's only thread in the next call, but
+        // another pool has threads of its own.
+        assert_eq!(pool.run(vec![task(1, length)])[0].status, Status::NotRun);
+        let reports = Pool::new(Options { jobs: 1, timeout: None }).run(vec![task(1, length)]);
+        assert_eq!(reports[0].result.answer, Ok(Solution::new(3)));
+        // Once the solver returns, its thread is free again.
+        thread::sleep(Duration::from_secs(1));
+        assert_eq!(pool.run(vec![task(1, length)])[0].result.answer, Ok(Solution::new(3)));
+    }
+
+    #[test]
+    fn test_format_summary() {
+        let mut reports = Pool::new(Options { jobs: 1, timeout: None }).run(vec![task(1, broken), task(2, length)]);
+        reports.push(TaskReport::not_run(PartResult { day: 1, part: 3, answer: Err("no input".to_string()),
+            elapsed: Duration::default() }));
+        let summary = format_summary(&reports, Duration::from_millis(1500), 1);
+        assert!(summary.starts_with("3 parts: 1 finished, 0 cached, 1 panicked, 0 timed out, 1 not run in 1.50 s ("),
+            "{}", summary);
+        assert!(summary.ends_with("of solver time on 1 thread)\n"), "{}", summary);
+    }
+}
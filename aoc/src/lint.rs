@@ -0,0 +1,320 @@
+//! Check puzzle input files against a description of each day's input format.
+//!
+//! The solvers' parsers accept some inputs which are not quite right, e.g. with CRLF line endings,
+//! and stop at the first problem. The linter is stricter, and reports every line which doesn't fit.
+
+use aoc_common::{ParseError, ParseErrorKind};
+use regex::Regex;
+
+/// The expected shape of a group of lines in a paragraph of an input.
+pub struct LineShape {
+    /// A regular expression which each whole line must match.
+    pub pattern: &'static str,
+    /// What a line should look like, for people to read, e.g. "an instruction like `acc +1`".
+    pub description: &'static str,
+    /// How many lines there are. Only the last group of a section can have more than one line.
+    pub count: Count,
+    /// A test for what `pattern` can't say, which lines that match it must pass too.
+    pub check: Option<LineCheck>,
+}
+
+/// A test of a line which a regular expression can't do, e.g. that a number is a multiple of 90.
+#[derive(Clone, Copy)]
+pub struct LineCheck {
+    pub test: fn(&str) -> bool,
+    /// What a line which fails the test should look like.
+    pub expected: &'static str,
+}
+
+impl LineShape {
+    /// This shape, where lines must pass `test` as well, or else look like `expected`.
+    fn with_check(self, test: fn(&str) -> bool, expected: &'static str) -> LineShape {
+        LineShape { check: Some(LineCheck { test, expected }), ..self }
+    }
+}
+
+/// How many lines a group has.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Count {
+    One,
+    AtLeastOne,
+}
+
+/// One paragraph of an input: groups of lines, in order. Sections are separated by single blank lines.
+pub struct Section {
+    pub lines: Vec<LineShape>,
+    /// The lines are rows of a grid, so they must all be as long as the first.
+    pub same_width: bool,
+}
+
+/// The format of one day's input: its sections, in order.
+pub struct Format {
+    pub sections: Vec<Section>,
+    /// The last section can be repeated any number of times, e.g. one paragraph per passport.
+    pub repeat_last: bool,
+}
+
+const fn one(pattern: &'static str, description: &'static str) -> LineShape {
+    LineShape { pattern, description, count: Count::One, check: None }
+}
+
+const fn many(pattern: &'static str, description: &'static str) -> LineShape {
+    LineShape { pattern, description, count: Count::AtLeastOne, check: None }
+}
+
+fn section(lines: Vec<LineShape>) -> Section {
+    Section { lines, same_width: false }
+}
+
+/// A format of sections, which can't be repeated.
+fn sections(sections: Vec<Section>) -> Format {
+    Format { sections, repeat_last: false }
+}
+
+/// A format of one section of grid rows.
+fn grid(line: LineShape) -> Format {
+    sections(vec![Section { lines: vec![line], same_width: true }])
+}
+
+/// A format of any number of paragraphs, each of lines with the same shape.
+fn paragraphs(line: LineShape) -> Format {
+    Format { sections: vec![section(vec![line])], repeat_last: true }
+}
+
+const NUMBER: &str = r"^\d+$";
+const NUMBER_DESCRIPTION: &str = "a number like `1721`";
+const TICKET: &str = r"^\d+(,\d+)*$";
+
+/// Whether a day 12 instruction's value is one which `day12::parse_nav_instructions` accepts.
+fn is_valid_nav_value(line: &str) -> bool {
+    let (action, value) = line.split_at(1);
+    match value.parse::<i32>() {
+        Ok(value) => !"LR".contains(action) || day12::is_valid_turn(value),
+        Err(_) => false,
+    }
+}
+
+/// The format of day `day`'s input, or `None` if there is no such day.
+pub fn format(day: u32) -> Option<Format> {
+    let lines = |pattern, description| sections(vec![section(vec![many(pattern, description)])]);
+    let format = match day {
+        1 | 9 | 10 => lines(NUMBER, NUMBER_DESCRIPTION),
+        2 => lines(r"^\d+-\d+ [a-z]: [a-z]+$", "a password policy and password like `1-3 a: abcde`"),
+        3 | 17 => grid(many(r"^[.#]+$", "a row of `.` and `#`")),
+        4 => paragraphs(many(r"^[a-z]{3}:\S+( [a-z]{3}:\S+)*$", "passport fields like `ecl:gry pid:860033327`")),
+        5 => lines(r"^[FB]{7}[LR]{3}$", "a seat code like `FBFBBFFRLR`"),
+        6 => paragraphs(many(r"^[a-z]+$", "one person's answers like `abc`")),
+        7 => lines(r"^[a-z]+ [a-z]+ bags contain (no other bags|\d+ [a-z]+ [a-z]+ bags?(, \d+ [a-z]+ [a-z]+ bags?)*)\.$",
+            "a rule like `light red bags contain 1 bright white bag, 2 muted yellow bags.`"),
+        8 => lines(r"^(acc|jmp|nop) [+-]\d+$", "an instruction like `acc +1`"),
+        11 => grid(many(r"^[.L#]+$", "a row of `.`, `L` and `#`")),
+        12 => sections(vec![section(vec![many(r"^[NSEWFLR]\d+$", "an instruction like `F10` or `R90`")
+            .with_check(is_valid_nav_value, "a turn of a multiple of 90 degrees, and a number which fits in 32 bits")])]),
+        13 => sections(vec![section(vec![
+            one(NUMBER, "a ready time like `939`"),
+            one(r"^(\d+|x)(,(\d+|x))*$", "bus IDs like `7,13,x,x,59`"),
+        ])]),
+        14 => lines(r"^(mask = [01X]{36}|mem\[\d+\] = \d+)$", "`mask = ` and 36 bits, or a write like `mem[8] = 11`"),
+        15 => sections(vec![section(vec![one(TICKET, "starting numbers like `0,3,6`")])]),
+        16 => sections(vec![
+            section(vec![many(r"^[a-z ]+: \d+-\d+( or \d+-\d+)*$", "a field like `class: 1-3 or 5-7`")]),
+            section(vec![one(r"^your ticket:$", "`your ticket:`"), one(TICKET, "ticket values like `7,1,14`")]),
+            section(vec![one(r"^nearby tickets:$", "`nearby tickets:`"), many(TICKET, "ticket values like `7,3,47`")]),
+        ]),
+        18 => lines(r"^\(*\d+\)*( [+*] \(*\d+\)*)*$", "an expression like `2 * 3 + (4 * 5)`"),
+        19 => sections(vec![
+            section(vec![many(r#"^\d+: ("."|\d+( \d+)*( \| \d+( \d+)*)*)$"#, "a rule like `1: 2 3 | 3 2` or `4: \"a\"`")]),
+            section(vec![many(r"^[a-z]+$", "a message like `ababbb`")]),
+        ]),
+        _ => return None,
+    };
+    Some(format)
+}
+
+/// An error at `column` (counting characters from 1) of line `line`.
+fn problem(line: usize, column: usize, text: &str, kind: ParseErrorKind) -> ParseError {
+    ParseError { line, column, text: text.escape_debug().to_string(), kind }
+}
+
+/// Check `text` against `format`, and return every problem found, in order.
+pub fn lint(text: &str, format: &Format) -> Vec<ParseError> {
+    if text.is_empty() {
+        return vec![problem(1, 1, "", ParseErrorKind::MissingSection("the puzzle input"))];
+    }
+    let mut problems = Vec::new();
+
+    // Check the line endings and trailing spaces, and split the lines into paragraphs of (line number, line).
+    let mut paragraphs = Vec::<Vec<(usize, &str)>>::new();
+    let mut after_blank = true;
+    let body = text.strip_suffix('\n').unwrap_or(text);
+    let mut line_count = 0;
+    for (i, raw_line) in body.split('\n').enumerate() {
+        let number = i + 1;
+        line_count = number;
+        let mut line = raw_line;
+        if let Some(stripped) = line.strip_suffix('\r') {
+            problems.push(problem(number, stripped.chars().count() + 1, "\r", ParseErrorKind::Expected("a LF line ending")));
+            line = stripped;
+        }
+        let trimmed = line.trim_end();
+        if trimmed.len() < line.len() {
+            problems.push(problem(number, trimmed.chars().count() + 1, &line[trimmed.len()..],
+                ParseErrorKind::Expected("no spaces at the end of the line")));
+            line = trimmed;
+        }
+        if line.is_empty() {
+            if after_blank {
+                problems.push(problem(number, 1, "\n", ParseErrorKind::Expected("only one blank line between paragraphs")));
+            }
+            after_blank = true;
+        } else {
+            if after_blank {
+                paragraphs.push(Vec::new());
+            }
+            paragraphs.last_mut().unwrap().push((number, line));
+            after_blank = false;
+        }
+    }
+    if after_blank && !paragraphs.is_empty() {
+        problems.push(problem(line_count, 1, "\n", ParseErrorKind::Expected("no blank line at the end of the input")));
+    }
+
+    for (i, paragraph) in paragraphs.iter().enumerate() {
+        let section_index = if i < format.sections.len() {
+            i
+        } else if format.repeat_last {
+            format.sections.len() - 1
+        } else {
+            let (number, line) = paragraph[0];
+            problems.push(problem(number, 1, line, ParseErrorKind::Expected("the end of the input")));
+            break;
+        };
+        lint_section(paragraph, &format.sections[section_index], &mut problems);
+    }
+    for section in format.sections.iter().skip(paragraphs.len()) {
+        problems.push(problem(line_count + 1, 1, "", ParseErrorKind::MissingSection(section.lines[0].description)));
+    }
+    problems.sort_by_key(|problem| problem.line);
+    problems
+}
+
+/// Check one paragraph, of (line number, line), against `section`.
+fn lint_section(paragraph: &[(usize, &str)], section: &Section, problems: &mut Vec<ParseError>) {
+    let width = paragraph[0].1.chars().count();
+    let mut lines = paragraph.iter();
+    for shape in &section.lines {
+        let regex = Regex::new(shape.pattern).unwrap();
+        let group = match shape.count {
+            Count::One => lines.next().into_iter().collect::<Vec<_>>(),
+            Count::AtLeastOne => lines.by_ref().collect(),
+        };
+        if group.is_empty() {
+            let after = paragraph.last().unwrap().0 + 1;
+            problems.push(problem(after, 1, "", ParseErrorKind::Expected(shape.description)));
+            return;
+        }
+        for &(number, line) in group {
+            if !regex.is_match(line) {
+                problems.push(problem(number, 1, line, ParseErrorKind::Expected(shape.description)));
+            } else if let Some(check) = shape.check.filter(|check| !(check.test)(line)) {
+                problems.push(problem(number, 1, line, ParseErrorKind::Expected(check.expected)));
+            } else if section.same_width && line.chars().count() != width {
+                problems.push(problem(number, 1, line, ParseErrorKind::Expected("a row as wide as the first row")));
+            }
+        }
+    }
+    for &(number, line) in lines {
+        problems.push(problem(number, 1, line, ParseErrorKind::Expected("a blank line")));
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use aoc_days::DAYS;
+    use super::*;
+
+    fn lint_day(day: u32, text: &str) -> Vec<String> {
+        lint(text, &format(day).unwrap()).iter().map(|problem| problem.to_string()).collect()
+    }
+
+    #[test]
+    fn test_every_day_has_a_format() {
+        for day in DAYS.iter() {
+            let format = format(day.number).unwrap();
+            for shape in format.sections.iter().flat_map(|section| &section.lines) {
+                Regex::new(shape.pattern).unwrap();
+            }
+        }
+        assert!(format(20).is_none());
+    }
+
+    #[test]
+    fn test_inputs_are_clean() {
+        for day in DAYS.iter() {
+            let text = fs::read_to_string(day.default_input_path()).unwrap();
+            assert_eq!(lint_day(day.number, &text), Vec::<String>::new(), "day {}", day.number);
+        }
+    }
+
+    #[test]
+    fn test_line_endings_and_spaces() {
+        assert_eq!(lint_day(1, "1721\r\n979 \n366\n"), vec![
+            "line 1, column 5: expected a LF line ending, found `\\r`",
+            "line 2, column 4: expected no spaces at the end of the line, found ` `",
+        ]);
+    }
+
+    #[test]
+    fn test_line_shapes() {
+        assert_eq!(lint_day(2, "1-3 a: abcde\n1-3 B: cdefg\n"), vec![
+            "line 2, column 1: expected a password policy and password like `1-3 a: abcde`, found `1-3 B: cdefg`",
+        ]);
+        let rules = "light red bags contain 10 bright white bags.\nbright white bags contain 1 shiny gold bag\n";
+        assert_eq!(lint_day(7, rules).len(), 1);
+        assert!(lint_day(7, rules)[0].starts_with("line 2, column 1: expected a rule like"));
+    }
+
+    #[test]
+    fn test_checks() {
+        assert_eq!(lint_day(12, "F10\nL450\nR45\nN99999999999\n"), vec![
+            "line 3, column 1: expected a turn of a multiple of 90 degrees, and a number which fits in 32 bits, found `R45`",
+            "line 4, column 1: expected a turn of a multiple of 90 degrees, and a number which fits in 32 bits, found `N99999999999`",
+        ]);
+        // The linter is only stricter than the parser about signs, which it doesn't allow.
+        for line in ["F10", "L450", "R0", "R45", "L100", "N99999999999", "X90", "L"] {
+            assert_eq!(lint_day(12, line).is_empty(), day12::parse_nav_instructions(line).is_ok(), "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_grid_width() {
+        assert_eq!(lint_day(3, "..#\n.#.#\n#..\n"), vec![
+            "line 2, column 1: expected a row as wide as the first row, found `.#.#`",
+        ]);
+    }
+
+    #[test]
+    fn test_sections() {
+        let notes = "class: 1-3 or 5-7\n\nyour ticket:\n7,1,14\n\nnearby tickets:\n7,3,47\n";
+        assert!(lint_day(16, notes).is_empty());
+        assert_eq!(lint_day(16, "class: 1-3 or 5-7\n\nyour tickets:\n7,1,14\n7,1,14\n"), vec![
+            "line 3, column 1: expected `your ticket:`, found `your tickets:`",
+            "line 5, column 1: expected a blank line, found `7,1,14`",
+            "line 6, column 1: input ended before `nearby tickets:`",
+        ]);
+        assert!(lint_day(13, "939\n7,13,x\n").is_empty());
+        assert_eq!(lint_day(13, "939\n\n7,13,x\n"), vec![
+            "line 2, column 1: expected bus IDs like `7,13,x,x,59`, found the end of the line",
+            "line 3, column 1: expected the end of the input, found `7,13,x`",
+        ]);
+        assert_eq!(lint_day(6, "abc\n\n\nab\n"), vec![
+            "line 3, column 1: expected only one blank line between paragraphs, found `\\n`",
+        ]);
+        assert_eq!(lint_day(1, "1721\n\n"), vec![
+            "line 2, column 1: expected no blank line at the end of the input, found `\\n`",
+        ]);
+        assert_eq!(lint_day(1, ""), vec!["line 1, column 1: input ended before the puzzle input"]);
+    }
+}
@@ -0,0 +1,569 @@
+//! `aoc`: one binary which runs any day's Advent of Code 2020 solvers.
+//!
+//! ```text
+//! aoc run 7 --part 2 --input path/to/input.txt
+//! aoc run all --format json
+//! aoc list
+//! aoc check all --jobs 4 --timeout 30
+//! aoc check 7 --record
+//! aoc run all --no-cache
+//! aoc cache clear 7
+//! aoc tui
+//! aoc bench run all --label baseline
+//! aoc bench compare
+//! aoc watch 7
+//! aoc lint 7 path/to/input.txt
+//! aoc run 11 --log-level info --log-day 11=trace --log-file day11.log
+//! ```
+
+use std::{fmt, path::PathBuf, process, str::FromStr, sync::Arc, time::{Duration, Instant}};
+use aoc_common::{format_json, parse_level, DayLevel, InputSource, LogSettings, OutputFormat, PartResult, UnwrapOrExit};
+use clap::{Parser, Subcommand};
+use tracing::level_filters::LevelFilter;
+
+mod bench;
+mod cache;
+mod executor;
+mod lint;
+mod regression;
+mod report;
+mod tui;
+mod visualize;
+mod watch;
+
+use cache::Cache;
+use aoc_days::{find_day, Day, DAYS};
+use executor::{Status, Task, TaskReport};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Run the Advent of Code 2020 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Most detailed level of the solvers' tracing output to write:
+    /// `off`, `error`, `warn`, `info`, `debug` or `trace`.
+    #[arg(long, global = true, default_value = "warn", value_parser = parse_level)]
+    log_level: LevelFilter,
+    /// Log level for one day, like `11=trace`, instead of `--log-level`. Can be repeated.
+    #[arg(long = "log-day", global = true, value_name = "DAY=LEVEL")]
+    log_days: Vec<DayLevel>,
+    /// Write the tracing output to this file instead of stderr.
+    #[arg(long, global = true)]
+    log_file: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solvers for one day, or for all days, and print a table of answers and timings.
+    Run {
+        /// Day number (1-19), or `all`.
+        day: DaySelection,
+        /// Only run this part of the puzzle (1 or 2).
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Puzzle input file, or `-` for stdin. Defaults to the day's `input.txt`.
+        /// Can only be used when running a single day.
+        #[arg(long)]
+        input: Option<String>,
+        /// Print a table for people to read (`text`), or the answers with their intermediate values as `json`.
+        #[arg(long, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+        #[command(flatten)]
+        executor: ExecutorArgs,
+    },
+    /// List the days and which parts have solvers.
+    List,
+    /// Check that each solver still gives the expected answer for the day's `input.txt`.
+    /// The expected answers are stored in `answers.json` at the root of the repository.
+    Check {
+        /// Day number (1-19), or `all`.
+        #[arg(default_value_t = DaySelection::All)]
+        day: DaySelection,
+        /// Record the current answers as the expected answers, after a deliberate change.
+        #[arg(long)]
+        record: bool,
+        #[command(flatten)]
+        executor: ExecutorArgs,
+    },
+    /// Re-run one day's tests and solvers whenever its input, examples or sources change,
+    /// and show how the answers changed since the previous run.
+    Watch {
+        /// Day number (1-19).
+        day: u32,
+        /// Only run this part of the puzzle (1 or 2).
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+    },
+    /// Check a puzzle input against the day's input format, and report every line which doesn't fit.
+    Lint {
+        /// Day number (1-19), or `all` to check every day's `input.txt`.
+        day: DaySelection,
+        /// Puzzle input file, or `-` for stdin. Defaults to the day's `input.txt`.
+        /// Can only be given when checking a single day.
+        file: Option<String>,
+    },
+    /// Benchmark the solvers on their real inputs, and compare the timings with earlier runs.
+    Bench {
+        #[command(subcommand)]
+        command: BenchCommand,
+    },
+    /// Browse the days, their answers and timings in a terminal dashboard, and step through
+    /// visualizations of days 8, 11, 12 and 17.
+    Tui {
+        #[command(flatten)]
+        executor: ExecutorArgs,
+    },
+    /// Manage the cache of answers in `answer_cache.json`, which `aoc run` and `aoc check` use
+    /// instead of solving a part again for the same input and solvers.
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+}
+
+/// How to run the solvers of several days and parts.
+#[derive(clap::Args)]
+struct ExecutorArgs {
+    /// Number of solvers to run at the same time. Defaults to one per CPU.
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    jobs: Option<u32>,
+    /// Give up on any solver which takes longer than this many seconds, and report it as timed out.
+    #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+    timeout: Option<Duration>,
+    /// Run every solver, instead of using answers from the cache, and don't add the answers to it.
+    #[arg(long)]
+    no_cache: bool,
+}
+
+impl ExecutorArgs {
+    fn options(&self) -> executor::Options {
+        executor::Options {
+            jobs: self.jobs.map_or_else(executor::default_jobs, |jobs| jobs as usize),
+            timeout: self.timeout,
+        }
+    }
+
+    /// The answer cache to use, or `None` with `--no-cache`.
+    fn cache(&self) -> Option<Cache> {
+        if self.no_cache {
+            None
+        } else {
+            Some(Cache::load_or_empty(&cache::default_cache_path()))
+        }
+    }
+}
+
+/// Save the answer cache, if there is one. A cache which can't be saved is only worth a warning,
+/// because the answers have been found anyway.
+fn save_cache(cache: Option<Cache>) {
+    if let Some(cache) = cache {
+        if let Err(error) = cache.save() {
+            tracing::warn!(%error, "could not save the answer cache");
+        }
+    }
+}
+
+fn parse_timeout(s: &str) -> Result<Duration, String> {
+    match s.parse::<f64>() {
+        Ok(seconds) if seconds > 0.0 && seconds.is_finite() => Ok(Duration::from_secs_f64(seconds)),
+        _ => Err(format!("expected a positive number of seconds, found `{}`", s)),
+    }
+}
+
+#[derive(Subcommand)]
+enum BenchCommand {
+    /// Time each part of one day, or of all days, and add the timings to `bench_history.json`.
+    Run {
+        /// Day number (1-19), or `all`.
+        #[arg(default_value_t = DaySelection::All)]
+        day: DaySelection,
+        /// Number of timed runs of each part, after one warm-up run.
+        #[arg(long, default_value_t = 5)]
+        samples: usize,
+        /// A name for this run, e.g. the change being measured.
+        #[arg(long)]
+        label: Option<String>,
+    },
+    /// List the saved benchmark runs.
+    History,
+    /// Compare the median times of two saved runs, and flag each part which got slower.
+    /// Fails if any part got slower by more than the threshold.
+    Compare {
+        /// Number of the baseline run, as listed by `aoc bench history`. Defaults to the second last run.
+        baseline: Option<usize>,
+        /// Number of the run to compare with the baseline. Defaults to the last run.
+        current: Option<usize>,
+        /// Slowdown, in percent, above which a part counts as a regression.
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheCommand {
+    /// Remove the cached answers of one day, or of all days.
+    Clear {
+        /// Day number (1-19), or `all`.
+        #[arg(default_value_t = DaySelection::All)]
+        day: DaySelection,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DaySelection {
+    All,
+    Day(u32),
+}
+
+impl FromStr for DaySelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<DaySelection, String> {
+        if s == "all" {
+            return Ok(DaySelection::All);
+        }
+        match s.parse::<u32>() {
+            Ok(number) if find_day(number).is_some() => Ok(DaySelection::Day(number)),
+            _ => Err(format!("expected a day from 1 to {} or `all`, found `{}`", DAYS.len(), s)),
+        }
+    }
+}
+
+impl fmt::Display for DaySelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DaySelection::All => write!(f, "all"),
+            DaySelection::Day(number) => write!(f, "{}", number),
+        }
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let mut log_settings = LogSettings { level: cli.log_level, day_levels: cli.log_days, file: cli.log_file };
+    if matches!(cli.command, Command::Tui { .. }) && log_settings.file.is_none() {
+        // Tracing output on stderr would be drawn over the dashboard.
+        log_settings = LogSettings { level: LevelFilter::OFF, day_levels: Vec::new(), file: None };
+    }
+    log_settings.init().unwrap_or_exit();
+    match cli.command {
+        Command::Run { day, part, input, format, executor } => {
+            if day == DaySelection::All && input.is_some() {
+                eprintln!("error: --input can only be used when running a single day");
+                process::exit(2);
+            }
+            let parts: Vec<usize> = match part {
+                Some(part) => vec![part as usize],
+                None => vec![1, 2],
+            };
+
+            let source = input.as_deref().map(InputSource::from_arg);
+            let pool = executor::Pool::new(executor.options());
+            let mut cache = executor.cache();
+            let start = Instant::now();
+            let reports = run_days(&select_days(day), &parts, source.as_ref(), &pool, cache.as_mut());
+            save_cache(cache);
+            let results = reports.iter().map(|report| report.result.clone()).collect::<Vec<PartResult>>();
+            match format {
+                OutputFormat::Text => {
+                    print!("{}", report::format_table(&results));
+                    print!("{}", executor::format_summary(&reports, start.elapsed(), pool.options().jobs));
+                }
+                OutputFormat::Json => print!("{}", format_json(&results)),
+            }
+            if results.iter().any(|result| result.answer.is_err()) {
+                process::exit(1);
+            }
+        }
+        Command::List => {
+            for day in DAYS.iter() {
+                let parts = (1..=2)
+                    .filter(|&part| day.part(part).is_some())
+                    .map(|part| part.to_string())
+                    .collect::<Vec<String>>();
+                println!("day {:>2}: part {}", day.number, parts.join(", "));
+            }
+        }
+        Command::Check { day, record, executor } => {
+            let manifest_path = regression::default_manifest_path();
+            let mut manifest = if manifest_path.exists() || !record {
+                regression::load_manifest(&manifest_path).unwrap_or_exit()
+            } else {
+                Vec::new()
+            };
+            let pool = executor::Pool::new(executor.options());
+            let mut cache = executor.cache();
+            let start = Instant::now();
+            let mut reports = run_days(&select_days(day), &[1, 2], None, &pool, cache.as_mut());
+            save_cache(cache);
+            // Parts without a solver have nothing to check.
+            reports.retain(|report| find_day(report.result.day).unwrap().part(report.result.part).is_some());
+            let results = reports.iter().map(|report| report.result.clone()).collect::<Vec<PartResult>>();
+
+            if record {
+                regression::record(&mut manifest, &results).unwrap_or_exit();
+                regression::save_manifest(&manifest_path, &manifest).unwrap_or_exit();
+                println!("Recorded {} answers in {}", results.len(), manifest_path.display());
+            } else {
+                let checks = results
+                    .iter()
+                    .map(|result| (result, regression::check(&manifest, result)))
+                    .collect::<Vec<_>>();
+                print!("{}", regression::format_report(&checks));
+                print!("{}", executor::format_summary(&reports, start.elapsed(), pool.options().jobs));
+                if !checks.iter().all(|(_, outcome)| outcome.is_pass()) {
+                    process::exit(1);
+                }
+            }
+        }
+        Command::Watch { day, part } => {
+            let day = find_day(day)
+                .ok_or_else(|| format!("expected a day from 1 to {}, found `{}`", DAYS.len(), day))
+                .unwrap_or_exit();
+            watch::watch(day, part).unwrap_or_exit();
+        }
+        Command::Lint { day, file } => {
+            if day == DaySelection::All && file.is_some() {
+                eprintln!("error: a file can only be given when checking a single day");
+                process::exit(2);
+            }
+            let mut problem_count = 0;
+            for day in select_days(day) {
+                let source = match &file {
+                    Some(arg) => InputSource::from_arg(arg),
+                    None => InputSource::File(day.default_input_path()),
+                };
+                let text = source.read_to_string().unwrap_or_exit();
+                let problems = lint::lint(&text, &lint::format(day.number).unwrap());
+                for problem in &problems {
+                    println!("{}: {}", source, problem);
+                }
+                problem_count += problems.len();
+            }
+            if problem_count > 0 {
+                println!("{} problems found", problem_count);
+                process::exit(1);
+            }
+            println!("No problems found");
+        }
+        Command::Bench { command } => run_bench_command(command),
+        Command::Tui { executor } => tui::run(executor.options(), executor.cache()).unwrap_or_exit(),
+        Command::Cache { command: CacheCommand::Clear { day } } => {
+            let mut cache = Cache::load_or_empty(&cache::default_cache_path());
+            let day = match day {
+                DaySelection::All => None,
+                DaySelection::Day(number) => Some(number),
+            };
+            let removed = cache.clear(day);
+            cache.save().unwrap_or_exit();
+            println!("Removed {} cached answers, {} left", removed, cache.len());
+        }
+    }
+}
+
+fn run_bench_command(command: BenchCommand) {
+    let history_path = bench::default_history_path();
+    let mut history = bench::load_history(&history_path).unwrap_or_exit();
+    match command {
+        BenchCommand::Run { day, samples, label } => {
+            let mut results = Vec::new();
+            for day in select_days(day) {
+                let text = InputSource::File(day.default_input_path()).read_to_string().unwrap_or_exit();
+                for part in 1..=2 {
+                    if let Some(solver) = day.part(part) {
+                        let result = bench::bench_part(day.number, part, solver, &text, samples).unwrap_or_exit();
+                        println!("day {:>2} part {}: median {}, min {}", day.number, part,
+                            report::format_duration(Duration::from_secs_f64(result.median_seconds)),
+                            report::format_duration(Duration::from_secs_f64(result.min_seconds)));
+                        results.push(result);
+                    }
+                }
+            }
+            history.push(bench::BenchRun::new(label, results));
+            bench::save_history(&history_path, &history).unwrap_or_exit();
+            println!("Saved as run {} in {}", history.len(), history_path.display());
+        }
+        BenchCommand::History => print!("{}", bench::format_history(&history)),
+        BenchCommand::Compare { baseline, current, threshold } => {
+            let current = current.unwrap_or(history.len());
+            let baseline = baseline.unwrap_or_else(|| current.saturating_sub(1));
+            let find_run = |number: usize| {
+                history.get(number.wrapping_sub(1)).ok_or_else(|| format!(
+                    "no benchmark run {}: the history has {} runs", number, history.len()))
+            };
+            let comparisons = bench::compare(find_run(baseline).unwrap_or_exit(), find_run(current).unwrap_or_exit());
+            print!("{}", bench::format_comparison(&comparisons, threshold));
+            if comparisons.iter().any(|comparison| comparison.is_regression(threshold)) {
+                process::exit(1);
+            }
+        }
+    }
+}
+
+fn select_days(selection: DaySelection) -> Vec<&'static Day> {
+    match selection {
+        DaySelection::All => DAYS.iter().collect(),
+        DaySelection::Day(number) => vec![find_day(number).unwrap()],
+    }
+}
+
+/// Run the requested parts of each day's puzzle on the `pool`, with the input from `source`,
+/// or from each day's `input.txt` if there is no `source`. Returns a report for each part, in order.
+/// With a `cache`, parts whose answer is in it aren't run again, and new answers are added to it.
+fn run_days(days: &[&Day], parts: &[usize], source: Option<&InputSource>, pool: &executor::Pool,
+    mut cache: Option<&mut Cache>) -> Vec<TaskReport> {
+    let planned = days
+        .iter()
+        .flat_map(|&day| {
+            let default_source;
+            let source = match source {
+                Some(source) => source,
+                None => {
+                    default_source = InputSource::File(day.default_input_path());
+                    &default_source
+                }
+            };
+            plan_day(day, parts, source)
+        })
+        .collect::<Vec<Result<Task, PartResult>>>();
+
+    let mut tasks = Vec::new();
+    let mut not_run = Vec::new();
+    for (index, planned) in planned.into_iter().enumerate() {
+        match planned {
+            Ok(task) => match cache.as_ref().and_then(|cache| cache.get(task.day, task.part, &task.text)) {
+                Some(result) => not_run.push((index, TaskReport { result, status: Status::Cached })),
+                None => tasks.push(task),
+            },
+            Err(result) => not_run.push((index, TaskReport::not_run(result))),
+        }
+    }
+    let texts = tasks.iter().map(|task| Arc::clone(&task.text)).collect::<Vec<Arc<str>>>();
+    let mut reports = pool.run(tasks);
+    if let Some(cache) = cache.as_mut() {
+        for (report, text) in reports.iter().zip(&texts) {
+            if report.status == Status::Finished {
+                cache.insert(&report.result, text);
+            }
+        }
+    }
+    for (index, report) in not_run {
+        reports.insert(index, report);
+    }
+    reports
+}
+
+/// Read one day's input, and make a task for each requested part. Parts without a solver are skipped,
+/// unless that day has no solver for any of the requested parts. Parts which can't be run, because
+/// there is no solver or no input, are given as their result instead.
+fn plan_day(day: &Day, parts: &[usize], source: &InputSource) -> Vec<Result<Task, PartResult>> {
+    let solvers = parts
+        .iter()
+        .filter_map(|&part| day.part(part).map(|solver| (part, solver)))
+        .collect::<Vec<_>>();
+    if solvers.is_empty() {
+        return parts
+            .iter()
+            .map(|&part| Err(PartResult {
+                day: day.number,
+                part,
+                answer: Err("no solver for this part".to_string()),
+                elapsed: Default::default(),
+            }))
+            .collect();
+    }
+
+    let text: Arc<str> = match source.read_to_string() {
+        Ok(text) => text.into(),
+        Err(error) => {
+            return solvers
+                .iter()
+                .map(|&(part, _)| Err(PartResult {
+                    day: day.number,
+                    part,
+                    answer: Err(error.to_string()),
+                    elapsed: Default::default(),
+                }))
+                .collect();
+        }
+    };
+
+    solvers
+        .into_iter()
+        .map(|(part, solver)| Ok(Task { day: day.number, part, solver, text: Arc::clone(&text) }))
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_day(day: &Day, parts: &[usize], source: &InputSource) -> Vec<PartResult> {
+        let pool = executor::Pool::new(executor::Options { jobs: 2, timeout: None });
+        run_days(&[day], parts, Some(source), &pool, None).into_iter().map(|report| report.result).collect()
+    }
+
+    #[test]
+    fn test_parse_day_selection() {
+        assert_eq!("all".parse::<DaySelection>(), Ok(DaySelection::All));
+        assert_eq!("7".parse::<DaySelection>(), Ok(DaySelection::Day(7)));
+        assert!("0".parse::<DaySelection>().is_err());
+        assert!("20".parse::<DaySelection>().is_err());
+        assert!("seven".parse::<DaySelection>().is_err());
+    }
+
+    #[test]
+    fn test_run_day_missing_part() {
+        let results = run_day(find_day(4).unwrap(), &[1], &InputSource::Stdin);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].answer, Err("no solver for this part".to_string()));
+    }
+
+    #[test]
+    fn test_run_day_missing_input() {
+        let source = InputSource::from_arg("./does_not_exist.txt");
+        let results = run_day(find_day(7).unwrap(), &[1, 2], &source);
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|result| result.answer.is_err()));
+    }
+
+    #[test]
+    fn test_run_day() {
+        let day = find_day(7).unwrap();
+        let source = InputSource::File(day.default_input_path().with_file_name("example_input.txt"));
+        let results = run_day(day, &[1, 2], &source);
+        let answers = results.iter().map(|result| result.answer.clone().unwrap().answer).collect::<Vec<String>>();
+        assert_eq!(answers, vec!["4", "32"]);
+    }
+
+    #[test]
+    fn test_run_days_with_cache() {
+        let day = find_day(7).unwrap();
+        let source = InputSource::File(day.default_input_path().with_file_name("example_input.txt"));
+        let pool = executor::Pool::new(executor::Options { jobs: 2, timeout: None });
+        let path = std::env::temp_dir().join(format!("aoc_run_days_cache_{}.json", process::id()));
+        let mut cache = Cache::load(&path).unwrap();
+
+        let first = run_days(&[day], &[1, 2], Some(&source), &pool, Some(&mut cache));
+        assert!(first.iter().all(|report| report.status == Status::Finished));
+        assert_eq!(cache.len(), 2);
+        let second = run_days(&[day], &[1, 2], Some(&source), &pool, Some(&mut cache));
+        assert!(second.iter().all(|report| report.status == Status::Cached));
+        let answers = |reports: &[TaskReport]| reports.iter().map(|report| report.result.answer.clone()).collect::<Vec<_>>();
+        assert_eq!(answers(&second), answers(&first));
+    }
+
+    #[test]
+    fn test_run_days_keeps_order() {
+        let pool = executor::Pool::new(executor::Options { jobs: 4, timeout: None });
+        let reports = run_days(&select_days(DaySelection::All), &[1], None, &pool, None);
+        assert_eq!(reports.len(), DAYS.len());
+        for (report, day) in reports.iter().zip(DAYS.iter()) {
+            assert_eq!((report.result.day, report.result.part), (day.number, 1));
+        }
+        assert_eq!(reports[3].result.answer, Err("no solver for this part".to_string()));
+        assert_eq!(reports[3].status, Status::NotRun);
+        assert!(reports[6].result.answer.is_ok());
+    }
+}
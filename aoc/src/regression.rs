@@ -0,0 +1,176 @@
+//! Regression checks of each solver's answer for its real puzzle input,
+//! against a manifest of the expected answers.
+
+use std::{fs, path::{Path, PathBuf}};
+use aoc_common::PartResult;
+use serde::{Deserialize, Serialize};
+
+/// The expected answer to one part of one day's puzzle, for that day's `input.txt`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExpectedAnswer {
+    pub day: u32,
+    pub part: usize,
+    pub answer: String,
+}
+
+/// Path to the manifest of expected answers, `answers.json` at the root of the repository.
+pub fn default_manifest_path() -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "..", "answers.json"].iter().collect()
+}
+
+pub fn load_manifest(path: &Path) -> Result<Vec<ExpectedAnswer>, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|error| format!("could not read answer manifest {}: {}", path.display(), error))?;
+    serde_json::from_str(&text)
+        .map_err(|error| format!("invalid answer manifest {}: {}", path.display(), error))
+}
+
+pub fn save_manifest(path: &Path, manifest: &[ExpectedAnswer]) -> Result<(), String> {
+    let mut text = serde_json::to_string_pretty(manifest).unwrap();
+    text.push('\n');
+    fs::write(path, text).map_err(|error| format!("could not write answer manifest {}: {}", path.display(), error))
+}
+
+/// How a solver's answer compares with the manifest.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome {
+    Pass,
+    Mismatch { expected: String, actual: String },
+    /// The manifest has no expected answer for this part. Holds the solver's answer.
+    NotRecorded(String),
+    /// The solver gave no answer. Holds the reason.
+    Failed(String),
+}
+
+impl Outcome {
+    pub fn is_pass(&self) -> bool {
+        *self == Outcome::Pass
+    }
+}
+
+/// Compare one solver's answer with the expected answer in the manifest.
+pub fn check(manifest: &[ExpectedAnswer], result: &PartResult) -> Outcome {
+    let actual = match &result.answer {
+        Ok(solution) => solution.answer.clone(),
+        Err(error) => return Outcome::Failed(error.clone()),
+    };
+    match manifest.iter().find(|expected| expected.day == result.day && expected.part == result.part) {
+        None => Outcome::NotRecorded(actual),
+        Some(expected) if expected.answer == actual => Outcome::Pass,
+        Some(expected) => Outcome::Mismatch { expected: expected.answer.clone(), actual },
+    }
+}
+
+/// Record the answers in `results` as the expected answers, replacing any which are already in the manifest.
+/// Nothing is recorded if any solver failed.
+pub fn record(manifest: &mut Vec<ExpectedAnswer>, results: &[PartResult]) -> Result<(), String> {
+    if let Some(failed) = results.iter().find(|result| result.answer.is_err()) {
+        return Err(format!("day {} part {} has no answer to record: {}",
+            failed.day, failed.part, failed.answer.as_ref().unwrap_err()));
+    }
+    for result in results {
+        manifest.retain(|expected| !(expected.day == result.day && expected.part == result.part));
+        manifest.push(ExpectedAnswer {
+            day: result.day,
+            part: result.part,
+            answer: result.answer.as_ref().unwrap().answer.clone(),
+        });
+    }
+    manifest.sort_by_key(|expected| (expected.day, expected.part));
+    Ok(())
+}
+
+/// Describe the outcome for each part, then how many parts passed.
+pub fn format_report(checks: &[(&PartResult, Outcome)]) -> String {
+    let mut report = String::new();
+    for (result, outcome) in checks {
+        let description = match outcome {
+            Outcome::Pass => "ok".to_string(),
+            Outcome::Mismatch { expected, actual } => format!("MISMATCH: expected {}, got {}", expected, actual),
+            Outcome::NotRecorded(actual) => format!("NOT RECORDED: got {}", actual),
+            Outcome::Failed(error) => format!("FAILED: {}", error),
+        };
+        report.push_str(&format!("day {:>2} part {}: {}\n", result.day, result.part, description));
+    }
+    let passed = checks.iter().filter(|(_, outcome)| outcome.is_pass()).count();
+    report.push_str(&format!("{} passed, {} failed\n", passed, checks.len() - passed));
+    report
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use aoc_common::Solution;
+    use aoc_days::DAYS;
+    use super::*;
+
+    fn result(day: u32, part: usize, answer: Result<&str, &str>) -> PartResult {
+        PartResult {
+            day,
+            part,
+            answer: answer.map(Solution::new).map_err(|error| error.to_string()),
+            elapsed: Duration::default(),
+        }
+    }
+
+    fn expected(day: u32, part: usize, answer: &str) -> ExpectedAnswer {
+        ExpectedAnswer { day, part, answer: answer.to_string() }
+    }
+
+    #[test]
+    fn test_check() {
+        let manifest = vec![expected(7, 1, "278"), expected(7, 2, "45157")];
+        assert_eq!(check(&manifest, &result(7, 1, Ok("278"))), Outcome::Pass);
+        assert_eq!(check(&manifest, &result(7, 2, Ok("45158"))),
+            Outcome::Mismatch { expected: "45157".to_string(), actual: "45158".to_string() });
+        assert_eq!(check(&manifest, &result(8, 1, Ok("2025"))), Outcome::NotRecorded("2025".to_string()));
+        assert_eq!(check(&manifest, &result(7, 1, Err("no input"))), Outcome::Failed("no input".to_string()));
+    }
+
+    #[test]
+    fn test_record() {
+        let mut manifest = vec![expected(7, 1, "278"), expected(8, 1, "2025")];
+        record(&mut manifest, &[result(7, 2, Ok("45157")), result(7, 1, Ok("279"))]).unwrap();
+        assert_eq!(manifest, vec![expected(7, 1, "279"), expected(7, 2, "45157"), expected(8, 1, "2025")]);
+
+        assert!(record(&mut manifest, &[result(7, 1, Ok("1")), result(7, 2, Err("no input"))]).is_err());
+        assert_eq!(manifest[0], expected(7, 1, "279"));
+    }
+
+    #[test]
+    fn test_format_report() {
+        let pass = result(7, 1, Ok("278"));
+        let mismatch = result(7, 2, Ok("1"));
+        let checks = vec![
+            (&pass, Outcome::Pass),
+            (&mismatch, Outcome::Mismatch { expected: "45157".to_string(), actual: "1".to_string() }),
+        ];
+        assert_eq!(format_report(&checks),
+            "day  7 part 1: ok\nday  7 part 2: MISMATCH: expected 45157, got 1\n1 passed, 1 failed\n");
+    }
+
+    #[test]
+    fn test_manifest_covers_every_solver() {
+        let manifest = load_manifest(&default_manifest_path()).unwrap();
+        for day in DAYS.iter() {
+            for part in 1..=2 {
+                let recorded = manifest.iter().any(|expected| expected.day == day.number && expected.part == part);
+                assert_eq!(recorded, day.part(part).is_some(), "day {} part {}", day.number, part);
+            }
+        }
+    }
+
+    /// Slow in debug builds. Run it with `cargo test --release -p aoc -- --ignored`, or use `aoc check`.
+    #[test]
+    #[ignore]
+    fn test_real_inputs_match_manifest() {
+        let manifest = load_manifest(&default_manifest_path()).unwrap();
+        let options = crate::executor::Options { jobs: crate::executor::default_jobs(), timeout: None };
+        let pool = crate::executor::Pool::new(options);
+        for report in crate::run_days(&DAYS.iter().collect::<Vec<_>>(), &[1, 2], None, &pool, None) {
+            let result = report.result;
+            assert_eq!(check(&manifest, &result), Outcome::Pass, "day {} part {}", result.day, result.part);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Summary table of answers and timings.
+
+use std::time::Duration;
+use aoc_common::PartResult;
+
+/// Format a duration with units that suit its size, e.g. `850 µs`, `12.3 ms` or `4.56 s`.
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    if micros < 1000 {
+        format!("{} µs", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.1} ms", micros as f64 / 1e3)
+    } else {
+        format!("{:.2} s", micros as f64 / 1e6)
+    }
+}
+
+/// Render the results as a table, with one row per day and part, and a total time row.
+pub fn format_table(results: &[PartResult]) -> String {
+    let answers = results
+        .iter()
+        .map(|result| match &result.answer {
+            Ok(solution) => solution.answer.clone(),
+            Err(error) => format!("error: {}", error),
+        })
+        .collect::<Vec<String>>();
+    let times = results
+        .iter()
+        .map(|result| format_duration(result.elapsed))
+        .collect::<Vec<String>>();
+    let total: Duration = results.iter().map(|result| result.elapsed).sum();
+    let total_time = format_duration(total);
+
+    let answer_width = answers.iter().map(|a| a.chars().count()).max().unwrap_or(0).max("Answer".len());
+    let time_width = times.iter().chain(Some(&total_time)).map(|t| t.chars().count()).max().unwrap().max("Time".len());
+
+    let mut table = String::new();
+    table.push_str(&format!("{:>3}  {:>4}  {:<aw$}  {:>tw$}\n", "Day", "Part", "Answer", "Time",
+        aw = answer_width, tw = time_width));
+    table.push_str(&format!("{}\n", "-".repeat(3 + 2 + 4 + 2 + answer_width + 2 + time_width)));
+    for ((result, answer), time) in results.iter().zip(answers.iter()).zip(times.iter()) {
+        table.push_str(&format!("{:>3}  {:>4}  {:<aw$}  {:>tw$}\n", result.day, result.part, answer, time,
+            aw = answer_width, tw = time_width));
+    }
+    table.push_str(&format!("{:>3}  {:>4}  {:<aw$}  {:>tw$}\n", "", "", "Total", total_time,
+        aw = answer_width, tw = time_width));
+    table
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Solution;
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_micros(850)), "850 µs");
+        assert_eq!(format_duration(Duration::from_micros(12_345)), "12.3 ms");
+        assert_eq!(format_duration(Duration::from_millis(4_560)), "4.56 s");
+    }
+
+    #[test]
+    fn test_format_table() {
+        let results = vec![
+            PartResult { day: 7, part: 1, answer: Ok(Solution::new(278)), elapsed: Duration::from_micros(120) },
+            PartResult { day: 7, part: 2, answer: Err("no input".to_string()), elapsed: Duration::from_micros(30) },
+        ];
+        let table = format_table(&results);
+        let lines = table.lines().collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "Day  Part  Answer             Time");
+        assert_eq!(lines[2], "  7     1  278              120 µs");
+        assert_eq!(lines[3], "  7     2  error: no input   30 µs");
+        assert_eq!(lines[4], "           Total            150 µs");
+    }
+}
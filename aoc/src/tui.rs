@@ -0,0 +1,366 @@
+//! `aoc tui`: a terminal dashboard which lists every day with its answers and timings, and opens
+//! step-by-step visualizations of the days which have one.
+//!
+//! The days are solved on a background thread, through the answer cache like `aoc run`, so the
+//! dashboard fills in as the answers arrive.
+
+use std::{sync::{mpsc, Arc, Mutex}, thread, time::{Duration, Instant}};
+use aoc_common::InputSource;
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Style, Stylize},
+    widgets::{Block, Paragraph, Row, Table, TableState},
+    DefaultTerminal, Frame,
+};
+use aoc_days::{Day, DAYS};
+use crate::{cache::Cache, executor::{self, Status, TaskReport}, report::format_duration, visualize};
+
+/// How long to wait for a key press before checking for new answers and animation steps.
+const POLL_TIME: Duration = Duration::from_millis(20);
+/// The time between steps of a visualization which is playing, at first.
+const DEFAULT_STEP_TIME: Duration = Duration::from_millis(200);
+
+/// A message from the thread which solves the days.
+enum Update {
+    /// The reports for the day at this index of `DAYS`.
+    Reports(usize, Vec<TaskReport>),
+    /// The answer cache could not be saved.
+    Error(String),
+}
+
+/// A visualization being shown, and how it is being played.
+struct Player {
+    day: u32,
+    part: usize,
+    visualization: Box<dyn visualize::Visualization>,
+    playing: bool,
+    step_time: Duration,
+    last_step: Instant,
+}
+
+/// The state of the dashboard.
+pub struct App {
+    /// The reports for each day, by index in `DAYS`, or `None` while it is being solved.
+    reports: Vec<Option<Vec<TaskReport>>>,
+    selected: usize,
+    player: Option<Player>,
+    /// A message for the status line, like an error opening a visualization.
+    message: String,
+    /// Indices of days to solve again, without the cache, which the event loop sends to the solving thread.
+    rerun: Vec<usize>,
+    quit: bool,
+}
+
+impl App {
+    pub fn new() -> App {
+        App { reports: vec![None; DAYS.len()], selected: 0, player: None, message: String::new(), rerun: Vec::new(),
+            quit: false }
+    }
+
+    fn selected_day(&self) -> &'static Day {
+        &DAYS[self.selected]
+    }
+
+    /// Handle one key press.
+    pub fn handle_key(&mut self, key: KeyCode) {
+        if self.player.is_some() {
+            self.handle_player_key(key);
+            return;
+        }
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1).min(DAYS.len() - 1),
+            KeyCode::Char('r') => {
+                self.reports[self.selected] = None;
+                self.rerun.push(self.selected);
+            }
+            KeyCode::Enter | KeyCode::Char('v') => self.open(self.selected_day().number, 1),
+            _ => {}
+        }
+    }
+
+    fn handle_player_key(&mut self, key: KeyCode) {
+        let player = self.player.as_mut().unwrap();
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.player = None,
+            KeyCode::Char(' ') => player.playing = !player.playing,
+            KeyCode::Right | KeyCode::Char('n') => {
+                player.playing = false;
+                player.visualization.step();
+            }
+            KeyCode::Char('+') => player.step_time = (player.step_time / 2).max(POLL_TIME),
+            KeyCode::Char('-') => player.step_time = (player.step_time * 2).min(Duration::from_secs(5)),
+            KeyCode::Char(c @ '1'..='2') => {
+                let day = player.day;
+                self.open(day, c.to_digit(10).unwrap() as usize);
+            }
+            _ => {}
+        }
+    }
+
+    /// Start the visualization of part `part` of day `day`, with the day's `input.txt`.
+    fn open(&mut self, day: u32, part: usize) {
+        let input = InputSource::File(DAYS.iter().find(|d| d.number == day).unwrap().default_input_path());
+        let text = match input.read_to_string() {
+            Ok(text) => text,
+            Err(error) => {
+                self.message = error.to_string();
+                return;
+            }
+        };
+        match visualize::visualization(day, part, &text) {
+            None => self.message = format!("day {} has no visualization", day),
+            Some(Err(error)) => self.message = format!("{}: {}", input, error),
+            Some(Ok(visualization)) => {
+                self.message.clear();
+                self.player = Some(Player { day, part, visualization, playing: false, step_time: DEFAULT_STEP_TIME,
+                    last_step: Instant::now() });
+            }
+        }
+    }
+
+    /// Step the visualization if it is playing and its next step is due, and stop it at the end.
+    fn tick(&mut self) {
+        if let Some(player) = self.player.as_mut() {
+            if player.playing && player.last_step.elapsed() >= player.step_time {
+                player.playing = player.visualization.step();
+                player.last_step = Instant::now();
+            }
+        }
+    }
+
+    fn update(&mut self, update: Update) {
+        match update {
+            Update::Reports(index, reports) => self.reports[index] = Some(reports),
+            Update::Error(error) => self.message = error,
+        }
+    }
+}
+
+/// The text for one part in the table: its answer, or what went wrong.
+/// `None` is a part which is still being solved.
+pub fn format_part(reports: Option<&[TaskReport]>, part: usize) -> String {
+    let reports = match reports {
+        Some(reports) => reports,
+        None => return "…".to_string(),
+    };
+    match reports.iter().find(|report| report.result.part == part) {
+        None => "-".to_string(),
+        Some(report) => match (&report.result.answer, report.status) {
+            (Ok(solution), _) => solution.answer.clone(),
+            (Err(_), Status::Panicked) => "panicked".to_string(),
+            (Err(_), Status::TimedOut) => "timed out".to_string(),
+            (Err(_), Status::NotRun) => "not run".to_string(),
+            (Err(_), _) => "error".to_string(),
+        },
+    }
+}
+
+/// The text for the time column: the day's total solver time, and whether the answers were cached.
+pub fn format_time(reports: Option<&[TaskReport]>) -> String {
+    let reports = match reports {
+        Some(reports) => reports,
+        None => return "solving".to_string(),
+    };
+    let elapsed = reports.iter().map(|report| report.result.elapsed).sum();
+    if reports.iter().all(|report| report.status == Status::Cached) {
+        format!("{} (cached)", format_duration(elapsed))
+    } else {
+        format_duration(elapsed)
+    }
+}
+
+/// The details of one day's reports: each part's answer with its intermediate values, or its error.
+pub fn format_details(reports: &[TaskReport]) -> String {
+    let mut text = String::new();
+    for report in reports {
+        let result = &report.result;
+        match &result.answer {
+            Ok(solution) => {
+                text.push_str(&format!("part {}: {} ({}, {})\n", result.part, solution.answer,
+                    format_duration(result.elapsed), report.status));
+                for (name, value) in &solution.intermediates {
+                    text.push_str(&format!("    {} = {}\n", name, value));
+                }
+            }
+            Err(error) => text.push_str(&format!("part {}: {} ({})\n", result.part, error, report.status)),
+        }
+    }
+    text
+}
+
+/// Run the dashboard until the user quits. Days are solved with the executor `options`, and through
+/// `cache` if there is one, which is saved after each day. Every run shares one pool, so a solver
+/// which timed out keeps one of its threads busy for the reruns too.
+pub fn run(options: executor::Options, cache: Option<Cache>) -> Result<(), String> {
+    let (update_sender, updates) = mpsc::channel::<Update>();
+    let (rerun_sender, reruns) = mpsc::channel::<usize>();
+    let cache = cache.map(|cache| Arc::new(Mutex::new(cache)));
+    // Solve every day in order, then any day the user asks for again.
+    thread::spawn(move || {
+        let pool = executor::Pool::new(options);
+        let solve = |index: usize, use_cache: bool| {
+            let cache = cache.as_ref().filter(|_| use_cache);
+            let mut cache = cache.map(|cache| cache.lock().unwrap());
+            let reports = crate::run_days(&[&DAYS[index]], &[1, 2], None, &pool, cache.as_deref_mut());
+            if let Some(Err(error)) = cache.map(|cache| cache.save()) {
+                let _ = update_sender.send(Update::Error(error));
+            }
+            update_sender.send(Update::Reports(index, reports)).is_ok()
+        };
+        for index in 0..DAYS.len() {
+            if !solve(index, true) {
+                return;
+            }
+        }
+        for index in reruns {
+            if !solve(index, false) {
+                return;
+            }
+        }
+    });
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &updates, &rerun_sender);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, updates: &mpsc::Receiver<Update>, reruns: &mpsc::Sender<usize>)
+        -> Result<(), String> {
+    let mut app = App::new();
+    let terminal_error = |error: std::io::Error| format!("terminal error: {}", error);
+    while !app.quit {
+        terminal.draw(|frame| draw(frame, &app)).map_err(terminal_error)?;
+        if event::poll(POLL_TIME).map_err(terminal_error)? {
+            if let Event::Key(key) = event::read().map_err(terminal_error)? {
+                if key.kind == KeyEventKind::Press {
+                    app.handle_key(key.code);
+                }
+            }
+        }
+        for index in app.rerun.drain(..) {
+            // The solving thread only stops when this loop has finished.
+            reruns.send(index).unwrap();
+        }
+        while let Ok(update) = updates.try_recv() {
+            app.update(update);
+        }
+        app.tick();
+    }
+    Ok(())
+}
+
+fn draw(frame: &mut Frame, app: &App) {
+    let [main, status, help] = Layout::vertical([Constraint::Min(3), Constraint::Length(1), Constraint::Length(1)])
+        .areas(frame.area());
+    frame.render_widget(Paragraph::new(app.message.as_str()).red(), status);
+
+    if let Some(player) = &app.player {
+        let [header, body] = Layout::vertical([Constraint::Length(1), Constraint::Min(1)]).areas(main);
+        frame.render_widget(Paragraph::new(player.visualization.status()).bold(), header);
+        let block = Block::bordered().title(player.visualization.title());
+        let inner = block.inner(body);
+        let drawing = player.visualization.render(inner.width as usize, inner.height as usize);
+        frame.render_widget(Paragraph::new(drawing).block(block), body);
+        let playing = if player.playing { "pause" } else { "play" };
+        frame.render_widget(Paragraph::new(format!(
+            "space: {}  →/n: step  +/-: faster/slower  1/2: part  esc: back   (part {})",
+            playing, player.part)).dim(), help);
+        return;
+    }
+
+    let [table_area, details_area] = Layout::vertical([Constraint::Min(3), Constraint::Length(8)]).areas(main);
+    let rows = DAYS.iter().zip(&app.reports).map(|(day, reports)| {
+        let reports = reports.as_deref();
+        let visualized = if visualize::VISUALIZED_DAYS.contains(&day.number) { "yes" } else { "" };
+        Row::new(vec![day.number.to_string(), format_part(reports, 1), format_part(reports, 2),
+            format_time(reports), visualized.to_string()])
+    });
+    let widths = [Constraint::Length(4), Constraint::Fill(1), Constraint::Fill(1), Constraint::Length(20),
+        Constraint::Length(6)];
+    let table = Table::new(rows, widths)
+        .header(Row::new(vec!["Day", "Part 1", "Part 2", "Time", "Visual"]).bold())
+        .row_highlight_style(Style::new().reversed())
+        .block(Block::bordered().title("Advent of Code 2020"));
+    let mut state = TableState::default().with_selected(Some(app.selected));
+    frame.render_stateful_widget(table, table_area, &mut state);
+
+    let day = app.selected_day();
+    let details = match &app.reports[app.selected] {
+        Some(reports) => format_details(reports),
+        None => "Solving...".to_string(),
+    };
+    frame.render_widget(Paragraph::new(details).block(Block::bordered().title(format!("Day {}", day.number))),
+        details_area);
+    frame.render_widget(
+        Paragraph::new("↑/↓: select  enter: visualize  r: solve again without the cache  q: quit").dim(), help);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{PartResult, Solution};
+    use super::*;
+
+    fn report(part: usize, answer: Result<Solution, String>, status: Status) -> TaskReport {
+        TaskReport { result: PartResult { day: 13, part, answer, elapsed: Duration::from_millis(2) }, status }
+    }
+
+    #[test]
+    fn test_format_part() {
+        let reports = [
+            report(1, Ok(Solution::new(295).with("bus_id", 59)), Status::Finished),
+            report(2, Err("panicked: oops".to_string()), Status::Panicked),
+        ];
+        assert_eq!(format_part(Some(&reports), 1), "295");
+        assert_eq!(format_part(Some(&reports), 2), "panicked");
+        assert_eq!(format_part(Some(&reports[..1]), 2), "-");
+        assert_eq!(format_part(None, 1), "…");
+        assert_eq!(format_time(Some(&reports)), "4.0 ms");
+        assert_eq!(format_time(None), "solving");
+        assert_eq!(format_details(&reports),
+            "part 1: 295 (2.0 ms, finished)\n    bus_id = 59\npart 2: panicked: oops (panicked)\n");
+    }
+
+    #[test]
+    fn test_format_time_cached() {
+        let reports = [report(1, Ok(Solution::new(1)), Status::Cached), report(2, Ok(Solution::new(2)), Status::Cached)];
+        assert_eq!(format_time(Some(&reports)), "4.0 ms (cached)");
+    }
+
+    #[test]
+    fn test_keys() {
+        let mut app = App::new();
+        app.handle_key(KeyCode::Up);
+        assert_eq!(app.selected, 0);
+        for _ in 0..30 {
+            app.handle_key(KeyCode::Down);
+        }
+        assert_eq!(app.selected, DAYS.len() - 1);
+
+        app.selected = 0;
+        app.handle_key(KeyCode::Enter);
+        assert!(app.player.is_none());
+        assert_eq!(app.message, "day 1 has no visualization");
+
+        app.selected = 10;
+        app.handle_key(KeyCode::Enter);
+        assert_eq!(app.player.as_ref().unwrap().visualization.title(), "day 11 part 1: seating area, with the adjacent seats rule");
+        app.handle_key(KeyCode::Char('n'));
+        assert!(app.player.as_ref().unwrap().visualization.status().starts_with("round 1: "));
+        app.handle_key(KeyCode::Char('2'));
+        assert_eq!(app.player.as_ref().unwrap().part, 2);
+        app.handle_key(KeyCode::Esc);
+        assert!(app.player.is_none());
+        assert!(!app.quit);
+
+        app.handle_key(KeyCode::Char('r'));
+        assert_eq!(app.rerun, vec![10]);
+        assert!(app.reports[10].is_none());
+        app.handle_key(KeyCode::Char('q'));
+        assert!(app.quit);
+    }
+}
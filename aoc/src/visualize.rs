@@ -0,0 +1,464 @@
+//! Step-by-step visualizations of a solver's state, for the days where there is something to see.
+//! Each one drives the day's own step functions, and draws the state as text for `aoc tui`.
+
+use std::collections::HashSet;
+use aoc_common::{Grid, ParseError};
+use day11::Seat;
+use day8::{Instruction, TerminationCondition};
+
+/// The days which have a visualization.
+pub const VISUALIZED_DAYS: [u32; 4] = [8, 11, 12, 17];
+
+/// A solver's state, which can be advanced one step at a time and drawn.
+pub trait Visualization {
+    /// What is being shown, e.g. `day 11 part 2: seating area`.
+    fn title(&self) -> String;
+    /// Advance by one step. Returns false once there is nothing more to do.
+    fn step(&mut self) -> bool;
+    /// Describe the current step, e.g. `round 3: 1812 occupied seats`.
+    fn status(&self) -> String;
+    /// Draw the current state. Drawings which can be scaled fit in `width` columns and `height` rows,
+    /// and the rest are cut off by the terminal.
+    fn render(&self, width: usize, height: usize) -> String;
+}
+
+/// Start the visualization of part `part` of day `day` for the input `text`,
+/// or return `None` if the day doesn't have one.
+pub fn visualization(day: u32, part: usize, text: &str) -> Option<Result<Box<dyn Visualization>, ParseError>> {
+    let visualization: Result<Box<dyn Visualization>, ParseError> = match day {
+        8 => Console::new(part, text).map(|console| Box::new(console) as Box<dyn Visualization>),
+        11 => Seating::new(part, text).map(|seating| Box::new(seating) as Box<dyn Visualization>),
+        12 => Ferry::new(part, text).map(|ferry| Box::new(ferry) as Box<dyn Visualization>),
+        17 => ConwayCubes::new(part, text).map(|cubes| Box::new(cubes) as Box<dyn Visualization>),
+        _ => return None,
+    };
+    Some(visualization)
+}
+
+
+/// Day 8: the boot code running one instruction at a time. Part 2 runs the repaired program.
+struct Console {
+    part: usize,
+    program: Vec<Instruction>,
+    /// The index of the instruction which was repaired, for part 2.
+    repaired: Option<usize>,
+    location: usize,
+    accumulator: i32,
+    executed: Vec<bool>,
+    steps: usize,
+    termination: Option<TerminationCondition>,
+}
+
+impl Console {
+    fn new(part: usize, text: &str) -> Result<Console, ParseError> {
+        let mut program = day8::parse_program(text)?;
+        let mut repaired = None;
+        if part == 2 {
+            if let Some((_, i_corrupt)) = day8::fix_corrupt_instruction(&program) {
+                program = day8::repair_instruction(&program, i_corrupt);
+                repaired = Some(i_corrupt);
+            }
+        }
+        let mut executed = vec![false; program.len()];
+        let termination = if program.is_empty() {
+            Some(TerminationCondition::ReachedEnd)
+        } else {
+            executed[0] = true;
+            None
+        };
+        Ok(Console { part, program, repaired, location: 0, accumulator: 0, executed, steps: 0, termination })
+    }
+}
+
+impl Visualization for Console {
+    fn title(&self) -> String {
+        match (self.part, self.repaired) {
+            (1, _) => "day 8 part 1: boot code".to_string(),
+            (_, Some(i_corrupt)) => format!("day 8 part 2: boot code, with instruction {} repaired", i_corrupt),
+            (_, None) => "day 8 part 2: boot code, which could not be repaired".to_string(),
+        }
+    }
+
+    fn step(&mut self) -> bool {
+        if self.termination.is_some() {
+            return false;
+        }
+        let (target, accumulator) = day8::execute(&self.program[self.location], self.location, self.accumulator);
+        self.accumulator = accumulator;
+        self.steps += 1;
+        if target < 0 || target > self.program.len() as i64 {
+            self.termination = Some(TerminationCondition::JumpedOutOfBounds);
+        } else {
+            self.location = target as usize;
+            if self.location == self.program.len() {
+                self.termination = Some(TerminationCondition::ReachedEnd);
+            } else if self.executed[self.location] {
+                self.termination = Some(TerminationCondition::RepeatedInstruction);
+            } else {
+                self.executed[self.location] = true;
+            }
+        }
+        true
+    }
+
+    fn status(&self) -> String {
+        let ending = match self.termination {
+            None => "",
+            Some(TerminationCondition::RepeatedInstruction) => ", about to repeat an instruction",
+            Some(TerminationCondition::ReachedEnd) => ", reached the end",
+            Some(TerminationCondition::JumpedOutOfBounds) => ", jumped out of bounds",
+        };
+        format!("step {}: instruction {}, accumulator {}{}", self.steps, self.location, self.accumulator, ending)
+    }
+
+    /// List the instructions around the current one, marked with `>`. Instructions which have run
+    /// are marked with `*`.
+    fn render(&self, _width: usize, height: usize) -> String {
+        let height = height.max(1);
+        let first = self.location.saturating_sub(height / 2).min(self.program.len().saturating_sub(height));
+        let mut text = String::new();
+        for (index, instruction) in self.program.iter().enumerate().skip(first).take(height) {
+            text.push_str(&format!("{} {:>4} {} {} {:+}{}\n",
+                if index == self.location { '>' } else { ' ' },
+                index,
+                if self.executed[index] { '*' } else { ' ' },
+                format!("{:?}", instruction.operation).to_lowercase(),
+                instruction.argument,
+                if Some(index) == self.repaired { "  (repaired)" } else { "" }));
+        }
+        text
+    }
+}
+
+
+/// Day 11: the seating area, one round of the rules at a time.
+struct Seating {
+    part: usize,
+    round: usize,
+    seating_area: Grid<Seat>,
+    next: Grid<Seat>,
+    settled: bool,
+}
+
+impl Seating {
+    fn new(part: usize, text: &str) -> Result<Seating, ParseError> {
+        let seating_area = day11::parse_seating_area(text)?;
+        Ok(Seating { part, round: 0, next: seating_area.clone(), seating_area, settled: false })
+    }
+}
+
+impl Visualization for Seating {
+    fn title(&self) -> String {
+        let rule = if self.part == 1 { "adjacent seats" } else { "visible seats" };
+        format!("day 11 part {}: seating area, with the {} rule", self.part, rule)
+    }
+
+    fn step(&mut self) -> bool {
+        if self.settled {
+            return false;
+        }
+        let changed = match self.part {
+            1 => day11::sim_step_1(&self.seating_area, &mut self.next),
+            _ => day11::sim_step_2(&self.seating_area, &mut self.next),
+        };
+        if changed {
+            std::mem::swap(&mut self.seating_area, &mut self.next);
+            self.round += 1;
+        } else {
+            self.settled = true;
+        }
+        changed
+    }
+
+    fn status(&self) -> String {
+        let occupied = self.seating_area.iter().filter(|&&seat| seat == Seat::Occupied).count();
+        format!("round {}: {} occupied seats{}", self.round, occupied, if self.settled { ", settled" } else { "" })
+    }
+
+    fn render(&self, _width: usize, _height: usize) -> String {
+        self.seating_area.to_string()
+    }
+}
+
+
+/// Day 12: the ship's path, one navigation instruction at a time.
+struct Ferry {
+    part: usize,
+    instructions: Vec<(char, i32)>,
+    /// The ship's position before the first instruction, and after each one.
+    ships: Vec<(i32, i32)>,
+    /// The heading (part 1) or waypoint (part 2) before the first instruction, and after each one.
+    details: Vec<String>,
+    /// The number of instructions followed.
+    followed: usize,
+}
+
+impl Ferry {
+    fn new(part: usize, text: &str) -> Result<Ferry, ParseError> {
+        let instructions = day12::parse_nav_instructions(text)?;
+        let mut ship = (0, 0);
+        let mut heading = 0;
+        let mut waypoint = (10, 1);
+        let describe = |heading: i32, waypoint: (i32, i32)| match part {
+            1 => format!("heading {}°", heading),
+            _ => format!("waypoint at {:?} from the ship", waypoint),
+        };
+        let mut ships = vec![ship];
+        let mut details = vec![describe(heading, waypoint)];
+        for &(action, arg) in &instructions {
+            match part {
+                1 => (ship, heading) = day12::follow_instruction_1(ship, heading, action, arg),
+                _ => (ship, waypoint) = day12::follow_instruction_2(ship, waypoint, action, arg),
+            }
+            ships.push(ship);
+            details.push(describe(heading, waypoint));
+        }
+        Ok(Ferry { part, instructions, ships, details, followed: 0 })
+    }
+}
+
+impl Visualization for Ferry {
+    fn title(&self) -> String {
+        let rule = if self.part == 1 { "the ship moves" } else { "the waypoint moves" };
+        format!("day 12 part {}: the ship's path, where {}", self.part, rule)
+    }
+
+    fn step(&mut self) -> bool {
+        if self.followed == self.instructions.len() {
+            return false;
+        }
+        self.followed += 1;
+        true
+    }
+
+    fn status(&self) -> String {
+        let ship = self.ships[self.followed];
+        let instruction = match self.followed {
+            0 => "start".to_string(),
+            followed => {
+                let (action, arg) = self.instructions[followed - 1];
+                format!("instruction {} of {}, `{}{}`", followed, self.instructions.len(), action, arg)
+            }
+        };
+        format!("{}: ship at {:?}, {}, Manhattan distance {}",
+            instruction, ship, self.details[self.followed], ship.0.abs() + ship.1.abs())
+    }
+
+    /// Plot the path so far, scaled so that the whole path fits, with north up. The start is `S`
+    /// and the ship is `@`.
+    fn render(&self, width: usize, height: usize) -> String {
+        let (width, height) = (width.max(1) as i64, height.max(1) as i64);
+        let min_x = self.ships.iter().map(|ship| ship.0 as i64).min().unwrap();
+        let max_x = self.ships.iter().map(|ship| ship.0 as i64).max().unwrap();
+        let min_y = self.ships.iter().map(|ship| ship.1 as i64).min().unwrap();
+        let max_y = self.ships.iter().map(|ship| ship.1 as i64).max().unwrap();
+        let cell = |(x, y): (i32, i32)| {
+            let column = (x as i64 - min_x) * (width - 1) / (max_x - min_x).max(1);
+            let row = (max_y - y as i64) * (height - 1) / (max_y - min_y).max(1);
+            (row, column)
+        };
+
+        let mut canvas = vec![vec![' '; width as usize]; height as usize];
+        for pair in self.ships[..=self.followed].windows(2) {
+            let (start, end) = (cell(pair[0]), cell(pair[1]));
+            let steps = (end.0 - start.0).abs().max((end.1 - start.1).abs()).max(1);
+            for i in 0..=steps {
+                let row = start.0 + (end.0 - start.0) * i / steps;
+                let column = start.1 + (end.1 - start.1) * i / steps;
+                canvas[row as usize][column as usize] = '.';
+            }
+        }
+        let (row, column) = cell(self.ships[0]);
+        canvas[row as usize][column as usize] = 'S';
+        let (row, column) = cell(self.ships[self.followed]);
+        canvas[row as usize][column as usize] = '@';
+
+        canvas.iter().map(|row| row.iter().collect::<String>().trim_end().to_string() + "\n").collect()
+    }
+}
+
+
+/// The number of cycles which day 17 simulates.
+const CYCLES: usize = 6;
+
+/// The active cubes of day 17's pocket dimension.
+enum Space {
+    Three(HashSet<[i32; 3]>),
+    Four(HashSet<[i32; 4]>),
+}
+
+/// Day 17: the pocket dimension, one cycle at a time, drawn as 2D slices like in the puzzle.
+struct ConwayCubes {
+    space: Space,
+    cycle: usize,
+}
+
+impl ConwayCubes {
+    fn new(part: usize, text: &str) -> Result<ConwayCubes, ParseError> {
+        let initial_slice = day17::parse_initial_slice(text)?;
+        let space = match part {
+            1 => Space::Three(initial_slice.into_iter().map(|[x, y]| [x, y, 0]).collect()),
+            _ => Space::Four(initial_slice.into_iter().map(|[x, y]| [x, y, 0, 0]).collect()),
+        };
+        Ok(ConwayCubes { space, cycle: 0 })
+    }
+
+    /// The (x, y, z, w) of each active cube, with w = 0 in 3 dimensions.
+    fn active_cubes(&self) -> HashSet<[i32; 4]> {
+        match &self.space {
+            Space::Three(cubes) => cubes.iter().map(|&[x, y, z]| [x, y, z, 0]).collect(),
+            Space::Four(cubes) => cubes.clone(),
+        }
+    }
+}
+
+impl Visualization for ConwayCubes {
+    fn title(&self) -> String {
+        match self.space {
+            Space::Three(_) => "day 17 part 1: Conway cubes in 3 dimensions".to_string(),
+            Space::Four(_) => "day 17 part 2: Conway cubes in 4 dimensions".to_string(),
+        }
+    }
+
+    fn step(&mut self) -> bool {
+        if self.cycle == CYCLES {
+            return false;
+        }
+        self.space = match &self.space {
+            Space::Three(cubes) => Space::Three(day17::sim_step(cubes)),
+            Space::Four(cubes) => Space::Four(day17::sim_step_4d(cubes)),
+        };
+        self.cycle += 1;
+        true
+    }
+
+    fn status(&self) -> String {
+        format!("cycle {} of {}: {} active cubes", self.cycle, CYCLES, self.active_cubes().len())
+    }
+
+    /// Draw each z (and w) slice which has an active cube, side by side as far as `width` allows.
+    fn render(&self, width: usize, _height: usize) -> String {
+        let cubes = self.active_cubes();
+        if cubes.is_empty() {
+            return "no active cubes\n".to_string();
+        }
+        let bound = |axis: usize| {
+            let values = cubes.iter().map(|cube| cube[axis]);
+            (values.clone().min().unwrap(), values.max().unwrap())
+        };
+        let ((min_x, max_x), (min_y, max_y)) = (bound(0), bound(1));
+        let mut slices = cubes.iter().map(|cube| (cube[3], cube[2])).collect::<Vec<(i32, i32)>>();
+        slices.sort();
+        slices.dedup();
+
+        let blocks = slices
+            .into_iter()
+            .map(|(w, z)| {
+                let mut block = vec![match self.space {
+                    Space::Three(_) => format!("z={}", z),
+                    Space::Four(_) => format!("z={}, w={}", z, w),
+                }];
+                for y in min_y..=max_y {
+                    block.push((min_x..=max_x).map(|x| if cubes.contains(&[x, y, z, w]) { '#' } else { '.' }).collect());
+                }
+                block
+            })
+            .collect::<Vec<Vec<String>>>();
+        let block_width = blocks.iter().flatten().map(|line| line.chars().count()).max().unwrap();
+        let per_row = ((width + 2) / (block_width + 2)).max(1);
+
+        let mut text = String::new();
+        for row in blocks.chunks(per_row) {
+            for line in 0..row[0].len() {
+                let parts = row.iter().map(|block| format!("{:<1$}", block[line], block_width)).collect::<Vec<String>>();
+                text.push_str(parts.join("  ").trim_end());
+                text.push('\n');
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use super::*;
+
+    fn example(day: u32) -> String {
+        fs::read_to_string(format!("{}/../day{}/example_input.txt", env!("CARGO_MANIFEST_DIR"), day)).unwrap()
+    }
+
+    fn run_to_end(visualization: &mut dyn Visualization) -> usize {
+        let mut steps = 0;
+        while visualization.step() {
+            steps += 1;
+        }
+        steps
+    }
+
+    #[test]
+    fn test_no_visualization() {
+        assert!(visualization(7, 1, "").is_none());
+        for &day in VISUALIZED_DAYS.iter() {
+            assert!(visualization(day, 1, "").is_some());
+        }
+        assert!(visualization(11, 1, "LLx\n").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_console() {
+        let mut console = visualization(8, 1, &example(8)).unwrap().unwrap();
+        console.step();
+        assert_eq!(console.status(), "step 1: instruction 1, accumulator 0");
+        assert_eq!(console.render(40, 3), "     0 * nop +0\n>    1 * acc +1\n     2   jmp +4\n");
+        run_to_end(&mut *console);
+        assert_eq!(console.status(), "step 7: instruction 1, accumulator 5, about to repeat an instruction");
+
+        let mut console = visualization(8, 2, &example(8)).unwrap().unwrap();
+        assert_eq!(console.title(), "day 8 part 2: boot code, with instruction 7 repaired");
+        run_to_end(&mut *console);
+        assert!(console.status().ends_with("accumulator 8, reached the end"), "{}", console.status());
+    }
+
+    #[test]
+    fn test_seating() {
+        for (part, rounds, occupied) in [(1, 5, 37), (2, 6, 26)] {
+            let mut seating = visualization(11, part, &example(11)).unwrap().unwrap();
+            assert_eq!(run_to_end(&mut *seating), rounds);
+            assert_eq!(seating.status(), format!("round {}: {} occupied seats, settled", rounds, occupied));
+            assert_eq!(seating.render(80, 20).lines().count(), 10);
+        }
+    }
+
+    #[test]
+    fn test_ferry() {
+        let mut ferry = visualization(12, 1, "F10\nN3\nF7\nR90\nF11").unwrap().unwrap();
+        assert_eq!(ferry.status(), "start: ship at (0, 0), heading 0°, Manhattan distance 0");
+        assert_eq!(ferry.render(5, 3), "@\n\n\n");
+        assert_eq!(run_to_end(&mut *ferry), 5);
+        assert_eq!(ferry.status(), "instruction 5 of 5, `F11`: ship at (17, -8), heading 270°, Manhattan distance 25");
+        assert_eq!(ferry.render(5, 3), "S....\n    .\n    @\n");
+
+        let mut ferry = visualization(12, 2, "F10\nN3\nF7\nR90\nF11").unwrap().unwrap();
+        run_to_end(&mut *ferry);
+        assert_eq!(ferry.status(), "instruction 5 of 5, `F11`: ship at (214, -72), waypoint at (4, -10) from the ship, Manhattan distance 286");
+    }
+
+    #[test]
+    fn test_conway_cubes() {
+        let mut cubes = visualization(17, 1, ".#.\n..#\n###\n").unwrap().unwrap();
+        assert_eq!(cubes.render(80, 20), "z=0\n.#.\n..#\n###\n\n");
+        cubes.step();
+        assert_eq!(cubes.status(), "cycle 1 of 6: 11 active cubes");
+        assert_eq!(cubes.render(80, 20), "z=-1  z=0   z=1\n#..   #.#   #..\n..#   .##   ..#\n.#.   .#.   .#.\n\n");
+        assert_eq!(cubes.render(10, 20).lines().next(), Some("z=-1  z=0"));
+        assert_eq!(run_to_end(&mut *cubes), 5);
+        assert_eq!(cubes.status(), "cycle 6 of 6: 112 active cubes");
+
+        let mut cubes = visualization(17, 2, ".#.\n..#\n###\n").unwrap().unwrap();
+        run_to_end(&mut *cubes);
+        assert_eq!(cubes.status(), "cycle 6 of 6: 848 active cubes");
+    }
+}
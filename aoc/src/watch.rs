@@ -0,0 +1,234 @@
+//! Watch mode: re-run one day's tests and solvers whenever its input, examples or sources change,
+//! and show how the answers changed since the previous run.
+//!
+//! The day's code may have changed, so each run builds and runs it through `cargo` in a child process.
+
+use std::{collections::BTreeMap, path::{Path, PathBuf}, process::Command, sync::mpsc, time::Duration};
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde::Deserialize;
+use aoc_days::Day;
+
+/// How long to wait for more changes after the first one, so that saving several files re-runs once.
+const SETTLE_TIME: Duration = Duration::from_millis(300);
+
+/// The answers of one run, as (name, value) pairs sorted by name, e.g. `("part 1", "278")` or
+/// `("part 2 invalid_number", "1639024365")`. A part which failed has its error instead of an answer.
+pub type Answers = Vec<(String, String)>;
+
+/// Root of the repository, where `cargo` is run from.
+fn workspace_root() -> PathBuf {
+    let root: PathBuf = [env!("CARGO_MANIFEST_DIR"), ".."].iter().collect();
+    root.canonicalize().unwrap_or(root)
+}
+
+/// The directories to watch for a day: the day's crate, with its input, examples and sources,
+/// and the sources of `aoc_common`, which every day uses.
+pub fn watched_paths(day: &Day) -> Vec<PathBuf> {
+    vec![
+        workspace_root().join(format!("day{}", day.number)),
+        workspace_root().join("aoc_common").join("src"),
+    ]
+}
+
+/// Check if a change to `path` should trigger a run. Editors' hidden, swap and backup files don't.
+pub fn is_relevant(path: &Path) -> bool {
+    let name = match path.file_name().and_then(|name| name.to_str()) {
+        Some(name) => name,
+        None => return false,
+    };
+    !(name.starts_with('.') || name.ends_with('~') || name.ends_with(".swp") || name.ends_with(".swx"))
+}
+
+#[derive(Deserialize)]
+struct JsonPartResult {
+    part: usize,
+    answer: Option<String>,
+    intermediates: BTreeMap<String, String>,
+    error: Option<String>,
+}
+
+/// Read the answers from the output of `aoc run --format json`.
+pub fn parse_answers(json: &str) -> Result<Answers, String> {
+    let results: Vec<JsonPartResult> = serde_json::from_str(json)
+        .map_err(|error| format!("could not read the solvers' output: {}", error))?;
+    let mut answers = Answers::new();
+    for result in results {
+        let part = format!("part {}", result.part);
+        match (result.answer, result.error) {
+            (Some(answer), _) => {
+                for (name, value) in result.intermediates {
+                    answers.push((format!("{} {}", part, name), value));
+                }
+                answers.push((part, answer));
+            }
+            (None, error) => answers.push((format!("{} error", part), error.unwrap_or_default())),
+        }
+    }
+    answers.sort();
+    Ok(answers)
+}
+
+/// Describe how the answers changed, one line per name: `  ` if the value is unchanged,
+/// `- ` for a previous value and `+ ` for a new one.
+pub fn format_diff(previous: &Answers, current: &Answers) -> String {
+    let previous_values = previous.iter().cloned().collect::<BTreeMap<String, String>>();
+    let current_values = current.iter().cloned().collect::<BTreeMap<String, String>>();
+    let mut names = previous_values.keys().chain(current_values.keys()).collect::<Vec<&String>>();
+    names.sort();
+    names.dedup();
+
+    let mut diff = String::new();
+    for name in names {
+        match (previous_values.get(name), current_values.get(name)) {
+            (Some(old), Some(new)) if old == new => diff.push_str(&format!("  {}: {}\n", name, new)),
+            (old, new) => {
+                if let Some(old) = old {
+                    diff.push_str(&format!("- {}: {}\n", name, old));
+                }
+                if let Some(new) = new {
+                    diff.push_str(&format!("+ {}: {}\n", name, new));
+                }
+            }
+        }
+    }
+    diff
+}
+
+/// Run the day's tests, then its solvers, and print the results.
+/// Returns the answers, or `None` if the solvers could not be built or run.
+fn run_once(day: &Day, part: Option<u8>) -> Option<Answers> {
+    let package = format!("day{}", day.number);
+    println!("Testing {}...", package);
+    match Command::new("cargo").args(["test", "-q", "-p", &package]).current_dir(workspace_root()).output() {
+        Ok(output) if output.status.success() => println!("Tests passed."),
+        Ok(output) => {
+            print!("{}", String::from_utf8_lossy(&output.stdout));
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            println!("Tests FAILED.");
+        }
+        Err(error) => println!("Could not run cargo: {}", error),
+    }
+
+    println!("Solving day {}...", day.number);
+    let mut command = Command::new("cargo");
+    command.args(["run", "-q", "--release", "-p", "aoc", "--", "run", &day.number.to_string(), "--format", "json"]);
+    if let Some(part) = part {
+        command.args(["--part", &part.to_string()]);
+    }
+    let output = match command.current_dir(workspace_root()).output() {
+        Ok(output) => output,
+        Err(error) => {
+            println!("Could not run cargo: {}", error);
+            return None;
+        }
+    };
+    // `aoc run` fails if any part has no answer, but still gives the JSON of every part.
+    match parse_answers(&String::from_utf8_lossy(&output.stdout)) {
+        Ok(answers) => Some(answers),
+        Err(_) => {
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            println!("Could not build or run the solvers.");
+            None
+        }
+    }
+}
+
+/// Run the day's tests and solvers, then again after each change to its files, until interrupted.
+pub fn watch(day: &Day, part: Option<u8>) -> Result<(), String> {
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)
+        .map_err(|error| format!("could not watch for changes: {}", error))?;
+    for path in watched_paths(day) {
+        watcher.watch(&path, RecursiveMode::Recursive)
+            .map_err(|error| format!("could not watch {}: {}", path.display(), error))?;
+    }
+
+    let mut previous = run_once(day, part);
+    if let Some(answers) = &previous {
+        print!("{}", format_diff(answers, answers));
+    }
+    loop {
+        println!("Watching day {} for changes. Press Ctrl-C to stop.", day.number);
+        // Wait for a relevant change, then for the changes to settle.
+        let mut changed = Vec::<PathBuf>::new();
+        while changed.is_empty() {
+            let event = receiver.recv().map_err(|_| "stopped watching for changes".to_string())?;
+            collect_changes(event, &mut changed);
+        }
+        while let Ok(event) = receiver.recv_timeout(SETTLE_TIME) {
+            collect_changes(event, &mut changed);
+        }
+        changed.sort();
+        changed.dedup();
+        let root = workspace_root();
+        println!();
+        for path in &changed {
+            println!("Changed: {}", path.strip_prefix(&root).unwrap_or(path).display());
+        }
+
+        let current = run_once(day, part);
+        if let Some(answers) = &current {
+            print!("{}", format_diff(previous.as_ref().unwrap_or(answers), answers));
+            previous = current;
+        }
+    }
+}
+
+/// Add the relevant paths changed by a file system event to `changed`.
+fn collect_changes(event: notify::Result<notify::Event>, changed: &mut Vec<PathBuf>) {
+    match event {
+        Ok(event) => {
+            if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)) {
+                changed.extend(event.paths.into_iter().filter(|path| is_relevant(path)));
+            }
+        }
+        Err(error) => tracing::warn!(%error, "error watching for changes"),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc_days::find_day;
+    use super::*;
+
+    fn answers(pairs: &[(&str, &str)]) -> Answers {
+        pairs.iter().map(|&(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn test_watched_paths_exist() {
+        for path in watched_paths(find_day(7).unwrap()) {
+            assert!(path.is_dir(), "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn test_is_relevant() {
+        assert!(is_relevant(Path::new("day7/input.txt")));
+        assert!(is_relevant(Path::new("day7/src/lib.rs")));
+        assert!(!is_relevant(Path::new("day7/src/.lib.rs.swp")));
+        assert!(!is_relevant(Path::new("day7/src/lib.rs~")));
+        assert!(!is_relevant(Path::new("day7/src/lib.rs.swx")));
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let json = r#"[
+            {"day": 9, "part": 1, "answer": "127", "intermediates": {}, "error": null, "elapsed_seconds": 0.1},
+            {"day": 9, "part": 2, "answer": "62", "intermediates": {"invalid_number": "127"}, "error": null, "elapsed_seconds": 0.1}
+        ]"#;
+        assert_eq!(parse_answers(json), Ok(answers(&[("part 1", "127"), ("part 2", "62"), ("part 2 invalid_number", "127")])));
+        let json = r#"[{"day": 9, "part": 1, "answer": null, "intermediates": {}, "error": "no input", "elapsed_seconds": 0.0}]"#;
+        assert_eq!(parse_answers(json), Ok(answers(&[("part 1 error", "no input")])));
+        assert!(parse_answers("error: could not compile `day9`").is_err());
+    }
+
+    #[test]
+    fn test_format_diff() {
+        let previous = answers(&[("part 1", "127"), ("part 2", "62")]);
+        let current = answers(&[("part 1", "127"), ("part 2 error", "no input")]);
+        assert_eq!(format_diff(&previous, &current), "  part 1: 127\n- part 2: 62\n+ part 2 error: no input\n");
+        assert_eq!(format_diff(&current, &current), "  part 1: 127\n  part 2 error: no input\n");
+    }
+}
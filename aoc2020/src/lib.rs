@@ -0,0 +1,160 @@
+//! `aoc2020`: a Python module with the solvers for every day, implemented in Rust using PyO3.
+//!
+//! Each day is a submodule, with `solve_part_1` and `solve_part_2` functions which take the puzzle
+//! input text, e.g. `aoc2020.day7.solve_part_1(text)`. Some days have extra functions,
+//! such as `aoc2020.day7.bags_inside(text, "shiny gold")`.
+//! Malformed puzzle input raises `aoc2020.ParseError`, which is a `ValueError`.
+
+use std::collections::{HashMap, HashSet};
+use pyo3::{create_exception, exceptions::{PyKeyError, PyValueError}, prelude::*, wrap_pyfunction};
+
+create_exception!(aoc2020, ParseError, PyValueError,
+    "The puzzle input is malformed. The `line`, `column` and `text` attributes point at the problem.");
+
+/// Convert a Rust parse error to a Python `ParseError`.
+fn parse_error(error: aoc_common::ParseError) -> PyErr {
+    Python::with_gil(|py| {
+        let exception = ParseError::new_err(error.to_string());
+        let value = exception.value(py);
+        value.setattr("line", error.line)
+            .and_then(|_| value.setattr("column", error.column))
+            .and_then(|_| value.setattr("text", &error.text))
+            .expect("exception attributes are writable");
+        exception
+    })
+}
+
+/// Add an empty submodule called `name` to `parent`.
+fn add_submodule<'py>(py: Python<'py>, parent: &PyModule, name: &str) -> PyResult<&'py PyModule> {
+    let module = PyModule::new(py, name)?;
+    parent.add_submodule(module)?;
+    // Register the submodule, so that `import aoc2020.day7` works as well as `aoc2020.day7`.
+    py.import("sys")?
+        .getattr("modules")?
+        .set_item(format!("{}.{}", parent.name()?, name), module)?;
+    Ok(module)
+}
+
+/// Add a submodule for a day to `parent`, with a Python function for each of the day's
+/// `solve_part_N` functions. Evaluates to the submodule, so that more functions can be added.
+macro_rules! day_module {
+    ($py:expr, $parent:expr, $day:ident, [$($part:ident),+]) => {{
+        let module = add_submodule($py, $parent, stringify!($day))?;
+        $(
+            /// Solve this part of the day's puzzle, given the puzzle input text.
+            #[pyfunction]
+            fn $part(py: Python<'_>, text: &str) -> PyResult<PyObject> {
+                $day::$part(text).map(|answer| answer.into_py(py)).map_err(parse_error)
+            }
+            module.add_function(wrap_pyfunction!($part, module)?)?;
+        )+
+        module
+    }};
+}
+
+/// The number of bags required inside a bag of color `bag_color`.
+#[pyfunction]
+fn bags_inside(text: &str, bag_color: &str) -> PyResult<u32> {
+    let graph = day7::parse_graph_from_text(text).map_err(parse_error)?;
+    if !graph.contains_node(bag_color) {
+        return Err(PyKeyError::new_err(format!("no rule mentions {} bags", bag_color)));
+    }
+    Ok(day7::compute_bags_inside(&graph, bag_color))
+}
+
+/// The set of bag colors which can eventually contain a bag of color `bag_color`.
+#[pyfunction]
+fn bags_that_can_contain(text: &str, bag_color: &str) -> PyResult<HashSet<String>> {
+    let graph = day7::parse_graph_from_text(text).map_err(parse_error)?;
+    if !graph.contains_node(bag_color) {
+        return Err(PyKeyError::new_err(format!("no rule mentions {} bags", bag_color)));
+    }
+    Ok(day7::compute_bags_that_can_contain(&graph, bag_color).into_iter().map(String::from).collect())
+}
+
+/// Run `f`, printing its trace events to stdout if `verbose`.
+fn traced<T>(verbose: bool, f: impl FnOnce() -> T) -> T {
+    if !verbose {
+        return f();
+    }
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::TRACE)
+        .with_writer(std::io::stdout)
+        .without_time()
+        .finish();
+    tracing::subscriber::with_default(subscriber, f)
+}
+
+/// The Manhattan distance travelled when the instructions move the ship (part 1).
+#[pyfunction]
+#[pyo3(signature = (nav_instructions_text, verbose=false))]
+fn nav_instructions_manhattan_distance_1(nav_instructions_text: &str, verbose: bool) -> PyResult<i32> {
+    traced(verbose, || day12::nav_instructions_manhattan_distance_1(nav_instructions_text)).map_err(parse_error)
+}
+
+/// The Manhattan distance travelled when the instructions move the waypoint (part 2).
+#[pyfunction]
+#[pyo3(signature = (nav_instructions_text, verbose=false))]
+fn nav_instructions_manhattan_distance_2(nav_instructions_text: &str, verbose: bool) -> PyResult<i32> {
+    traced(verbose, || day12::nav_instructions_manhattan_distance_2(nav_instructions_text)).map_err(parse_error)
+}
+
+/// Play the memory game with the given starting numbers, and return the number spoken on turn `end_turn`.
+#[pyfunction]
+fn elf_game(starting_numbers: Vec<usize>, end_turn: usize) -> PyResult<usize> {
+    if starting_numbers.is_empty() || end_turn <= starting_numbers.len() {
+        return Err(PyValueError::new_err("end_turn must be after all of the starting numbers are spoken"));
+    }
+    Ok(day15::elf_game(&starting_numbers, end_turn))
+}
+
+/// The value of each field on your ticket, by field name.
+#[pyfunction]
+fn find_fields_your_ticket(text: &str) -> PyResult<HashMap<String, u32>> {
+    day16::find_fields_your_ticket(text).map_err(parse_error)
+}
+
+/// Simulate the Conway space in `dims` (3 or 4) dimensions for `steps` cycles,
+/// and return the number of active cubes.
+#[pyfunction]
+#[pyo3(signature = (text, dims=3, steps=6))]
+fn simulate(text: &str, dims: usize, steps: usize) -> PyResult<usize> {
+    if dims != 3 && dims != 4 {
+        return Err(PyValueError::new_err(format!("dims must be 3 or 4, not {}", dims)));
+    }
+    day17::simulate(text, dims, steps).map_err(parse_error)
+}
+
+#[pymodule]
+fn aoc2020(py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add("ParseError", py.get_type::<ParseError>())?;
+
+    day_module!(py, m, day1, [solve_part_1, solve_part_2]);
+    day_module!(py, m, day2, [solve_part_1, solve_part_2]);
+    day_module!(py, m, day3, [solve_part_1, solve_part_2]);
+    day_module!(py, m, day4, [solve_part_2]);
+    day_module!(py, m, day5, [solve_part_1, solve_part_2]);
+    day_module!(py, m, day6, [solve_part_1, solve_part_2]);
+    let day7_module = day_module!(py, m, day7, [solve_part_1, solve_part_2]);
+    day7_module.add_function(wrap_pyfunction!(bags_inside, day7_module)?)?;
+    day7_module.add_function(wrap_pyfunction!(bags_that_can_contain, day7_module)?)?;
+    day_module!(py, m, day8, [solve_part_1, solve_part_2]);
+    day_module!(py, m, day9, [solve_part_1, solve_part_2]);
+    day_module!(py, m, day10, [solve_part_1, solve_part_2]);
+    day_module!(py, m, day11, [solve_part_1, solve_part_2]);
+    let day12_module = day_module!(py, m, day12, [solve_part_1, solve_part_2]);
+    day12_module.add_function(wrap_pyfunction!(nav_instructions_manhattan_distance_1, day12_module)?)?;
+    day12_module.add_function(wrap_pyfunction!(nav_instructions_manhattan_distance_2, day12_module)?)?;
+    day_module!(py, m, day13, [solve_part_1, solve_part_2]);
+    day_module!(py, m, day14, [solve_part_1, solve_part_2]);
+    let day15_module = day_module!(py, m, day15, [solve_part_1, solve_part_2]);
+    day15_module.add_function(wrap_pyfunction!(elf_game, day15_module)?)?;
+    let day16_module = day_module!(py, m, day16, [solve_part_1, solve_part_2]);
+    day16_module.add_function(wrap_pyfunction!(find_fields_your_ticket, day16_module)?)?;
+    let day17_module = day_module!(py, m, day17, [solve_part_1, solve_part_2]);
+    day17_module.add_function(wrap_pyfunction!(simulate, day17_module)?)?;
+    day_module!(py, m, day18, [solve_part_1, solve_part_2]);
+    day_module!(py, m, day19, [solve_part_1]);
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+"""Tests of the `aoc2020` Python module. Build it with `maturin develop`, then run `pytest`."""
+
+from pathlib import Path
+
+import pytest
+
+import aoc2020
+
+REPO_ROOT = Path(__file__).resolve().parents[2]
+
+
+def read_example(day, filename):
+    return (REPO_ROOT / f"day{day}" / filename).read_text()
+
+
+def test_every_day_has_a_module():
+    for day in range(1, 20):
+        module = getattr(aoc2020, f"day{day}")
+        # There are no solutions for day 4 part 1 or day 19 part 2.
+        assert hasattr(module, "solve_part_1") == (day != 4)
+        assert hasattr(module, "solve_part_2") == (day != 19)
+
+
+def test_import_submodule():
+    import aoc2020.day7
+    assert aoc2020.day7.solve_part_1 is aoc2020.day7.solve_part_1
+
+
+def test_solvers_on_examples():
+    assert aoc2020.day3.solve_part_1(read_example(3, "test_input.txt")) == 7
+    assert aoc2020.day3.solve_part_2(read_example(3, "test_input.txt")) == 336
+    assert aoc2020.day8.solve_part_1(read_example(8, "example_input.txt")) == 5
+    assert aoc2020.day8.solve_part_2(read_example(8, "example_input.txt")) == 8
+    assert aoc2020.day13.solve_part_1("939\n7,13,x,x,59,x,31,19") == 295
+    assert aoc2020.day13.solve_part_2("939\n7,13,x,x,59,x,31,19") == 1068781
+
+
+def test_day7_bags():
+    text = read_example(7, "example_input.txt")
+    assert aoc2020.day7.solve_part_1(text) == 4
+    assert aoc2020.day7.bags_inside(text, "shiny gold") == 32
+    assert aoc2020.day7.bags_that_can_contain(text, "shiny gold") == {
+        "bright white", "muted yellow", "dark orange", "light red"}
+    with pytest.raises(KeyError):
+        aoc2020.day7.bags_inside(text, "plaid purple")
+
+
+def test_day12_nav_instructions():
+    text = "F10\nN3\nF7\nR90\nF11"
+    assert aoc2020.day12.nav_instructions_manhattan_distance_1(text) == 25
+    assert aoc2020.day12.nav_instructions_manhattan_distance_2(text, verbose=False) == 286
+    assert aoc2020.day12.solve_part_1(text) == 25
+
+
+def test_day15_elf_game():
+    assert aoc2020.day15.elf_game([0, 3, 6], 2020) == 436
+    with pytest.raises(ValueError):
+        aoc2020.day15.elf_game([0, 3, 6], 3)
+
+
+def test_day17_simulate():
+    text = ".#.\n..#\n###"
+    assert aoc2020.day17.simulate(text) == 112
+    assert aoc2020.day17.simulate(text, dims=4, steps=6) == 848
+    assert aoc2020.day17.simulate(text, dims=3, steps=0) == 5
+    with pytest.raises(ValueError):
+        aoc2020.day17.simulate(text, dims=5)
+
+
+def test_parse_error():
+    with pytest.raises(aoc2020.ParseError) as error_info:
+        aoc2020.day12.solve_part_1("F10\nQ3")
+    error = error_info.value
+    assert isinstance(error, ValueError)
+    assert (error.line, error.column, error.text) == (2, 1, "Q3")
+    assert str(error) == "line 2, column 1: expected an instruction like `F10`, found `Q3`"
@@ -0,0 +1,88 @@
+//! Command line arguments shared by every day's binary.
+
+use std::{env, sync::Once};
+use crate::{parse_level, LogSettings, OutputFormat, UnwrapOrExit};
+
+/// The arguments of a day's binary: `[--format text|json] [--log-level LEVEL] [--log-file PATH] [INPUT]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Args {
+    /// Path to the puzzle input, or `-` for stdin.
+    pub input: Option<String>,
+    pub format: OutputFormat,
+    pub log: LogSettings,
+}
+
+impl Args {
+    /// Parse the arguments, not including the program name.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Args, String> {
+        let mut parsed = Args { input: None, format: OutputFormat::Text, log: LogSettings::default() };
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            // Options take their value from the next argument, or after `=`.
+            let (option, inline_value) = match arg.split_once('=') {
+                Some((option, value)) if option.starts_with("--") => (option, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = |expected: &str| {
+                inline_value.clone().or_else(|| args.next()).ok_or(format!("{} needs a value: {}", option, expected))
+            };
+            match option {
+                "--format" => parsed.format = value("`text` or `json`")?.parse()?,
+                "--log-level" => parsed.log.level = parse_level(&value("a log level like `debug`")?)?,
+                "--log-file" => parsed.log.file = Some(value("a path")?.into()),
+                _ if option.starts_with("--") => return Err(format!("unknown option `{}`", arg)),
+                _ if parsed.input.is_none() => parsed.input = Some(arg),
+                _ => return Err(format!("unexpected argument `{}`", arg)),
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// Parse this process's command line arguments, and start writing the solvers' tracing
+    /// output as the arguments ask.
+    /// Prints the problem and exits if they are invalid.
+    /// Can be called more than once, e.g. by `InputSource::from_env` and by `main`, but only the
+    /// first call starts the tracing output.
+    pub fn from_env() -> Args {
+        static START_TRACING: Once = Once::new();
+        let args = Args::parse(env::args().skip(1)).unwrap_or_exit();
+        START_TRACING.call_once(|| args.log.init().unwrap_or_exit());
+        args
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let log = LogSettings::default();
+        assert_eq!(parse(&[]), Ok(Args { input: None, format: OutputFormat::Text, log: log.clone() }));
+        assert_eq!(parse(&["in.txt", "--format", "json"]),
+            Ok(Args { input: Some("in.txt".to_string()), format: OutputFormat::Json, log: log.clone() }));
+        assert_eq!(parse(&["--format=json", "-"]),
+            Ok(Args { input: Some("-".to_string()), format: OutputFormat::Json, log }));
+    }
+
+    #[test]
+    fn test_parse_log_args() {
+        let args = parse(&["--log-level", "trace", "--log-file=trace.log"]).unwrap();
+        assert_eq!(args.log.level, tracing::level_filters::LevelFilter::TRACE);
+        assert_eq!(args.log.file, Some("trace.log".into()));
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert!(parse(&["--format"]).is_err());
+        assert!(parse(&["--format", "xml"]).is_err());
+        assert!(parse(&["--verbose"]).is_err());
+        assert!(parse(&["--log-level", "loud"]).is_err());
+        assert!(parse(&["a.txt", "b.txt"]).is_err());
+    }
+}
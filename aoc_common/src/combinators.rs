@@ -0,0 +1,484 @@
+//! Parser combinators for puzzle inputs: small parsers for numbers, words and literal text, and
+//! combinators which build them into parsers for lines, paragraphs and whole inputs.
+//!
+//! A parser takes the text still to be parsed, and returns the value it parsed with the rest of
+//! the text, or a `Failure` which holds the offending text. The offending text is always a slice of
+//! the input, so `parse_all` can turn a failure into a `ParseError` with its line and column.
+//!
+//! Tuples of parsers are parsers which run each parser in turn, e.g.
+//! `(integer(), tag("-"), integer())` parses `1-3` into `(1, "-", 3)`.
+
+use std::str::FromStr;
+use crate::{ParseError, ParseErrorKind};
+
+/// The result of a parser: the parsed value and the rest of the text, or a failure.
+pub type PResult<'a, T> = Result<(T, &'a str), Failure<'a>>;
+
+/// Why a parser failed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Failure<'a> {
+    /// The offending text, a slice of the input. Empty if the line or input ended too soon.
+    pub at: &'a str,
+    pub kind: ParseErrorKind,
+    /// Whether the failure is certain: the text had the right form but a wrong value, e.g. a bus
+    /// ID of 0 or a number out of range, or an `expect` has already described what was expected. Fatal failures are not
+    /// retried by `or`, or replaced by `expect`, so the most specific description is kept.
+    pub fatal: bool,
+}
+
+impl<'a> Failure<'a> {
+    pub fn new(at: &'a str, kind: ParseErrorKind) -> Failure<'a> {
+        Failure { at, kind, fatal: false }
+    }
+
+    pub fn fatal(at: &'a str, kind: ParseErrorKind) -> Failure<'a> {
+        Failure { at, kind, fatal: true }
+    }
+
+    /// Convert to a `ParseError`. `input` must be the whole input which the parser was given a slice of.
+    pub fn into_error(self, input: &str) -> ParseError {
+        match self.kind {
+            ParseErrorKind::MissingSection(section) => ParseError::missing_section(input, section),
+            kind => ParseError::new(input, self.at, kind),
+        }
+    }
+}
+
+/// A parser of values of type `T`. Every function or closure from text to a `PResult` is one.
+pub trait Parser<'a, T> {
+    fn parse(&self, s: &'a str) -> PResult<'a, T>;
+
+    /// Convert the parsed value with `f`.
+    fn map<U, F>(self, f: F) -> impl Parser<'a, U>
+    where Self: Sized, F: Fn(T) -> U {
+        move |s: &'a str| self.parse(s).map(|(value, rest)| (f(value), rest))
+    }
+
+    /// Convert the parsed value with `f`, which returns `None` if the value is invalid.
+    /// An invalid value is a fatal failure, reported as `expected` at the parsed text.
+    fn try_map<U, F>(self, f: F, expected: &'static str) -> impl Parser<'a, U>
+    where Self: Sized, F: Fn(T) -> Option<U> {
+        move |s: &'a str| {
+            let (value, rest) = self.parse(s)?;
+            match f(value) {
+                Some(value) => Ok((value, rest)),
+                None => Err(Failure::fatal(&s[..s.len() - rest.len()], ParseErrorKind::Expected(expected))),
+            }
+        }
+    }
+
+    /// Check the parsed value with `check`. A value which fails the check is a fatal failure,
+    /// reported as `expected` at the parsed text, e.g. "expected a bus ID of at least 1, found `0`".
+    fn verify<F>(self, check: F, expected: &'static str) -> impl Parser<'a, T>
+    where Self: Sized, F: Fn(&T) -> bool {
+        self.try_map(move |value| if check(&value) { Some(value) } else { None }, expected)
+    }
+
+    /// Describe what this parser parses, e.g. "an instruction like `acc +1`".
+    /// If the text doesn't have the expected form, the failure is a fatal one of `expected`, with
+    /// all of the rest of the line as the offending text. Fatal failures are kept.
+    fn expect(self, expected: &'static str) -> impl Parser<'a, T>
+    where Self: Sized {
+        move |s: &'a str| self.parse(s).map_err(|failure| {
+            if failure.fatal { failure } else { Failure::fatal(current_line(s), ParseErrorKind::Expected(expected)) }
+        })
+    }
+
+    /// Try this parser, then `other` if this one fails.
+    /// If both fail, the failure of whichever got further into the text is kept.
+    fn or<P>(self, other: P) -> impl Parser<'a, T>
+    where Self: Sized, P: Parser<'a, T> {
+        move |s: &'a str| match self.parse(s) {
+            Err(first) if !first.fatal => other.parse(s).map_err(|second| {
+                if second.fatal || second.at.as_ptr() >= first.at.as_ptr() { second } else { first }
+            }),
+            result => result,
+        }
+    }
+}
+
+impl<'a, T, F> Parser<'a, T> for F where F: Fn(&'a str) -> PResult<'a, T> {
+    fn parse(&self, s: &'a str) -> PResult<'a, T> {
+        self(s)
+    }
+}
+
+macro_rules! impl_parser_for_tuple {
+    ($($parser:ident $value:ident),+) => {
+        impl<'a, $($value,)+ $($parser: Parser<'a, $value>,)+> Parser<'a, ($($value,)+)> for ($($parser,)+) {
+            #[allow(non_snake_case)]
+            fn parse(&self, s: &'a str) -> PResult<'a, ($($value,)+)> {
+                let ($($parser,)+) = self;
+                let rest = s;
+                $(let ($value, rest) = $parser.parse(rest)?;)+
+                Ok((($($value,)+), rest))
+            }
+        }
+    };
+}
+
+impl_parser_for_tuple!(P1 T1, P2 T2);
+impl_parser_for_tuple!(P1 T1, P2 T2, P3 T3);
+impl_parser_for_tuple!(P1 T1, P2 T2, P3 T3, P4 T4);
+impl_parser_for_tuple!(P1 T1, P2 T2, P3 T3, P4 T4, P5 T5);
+impl_parser_for_tuple!(P1 T1, P2 T2, P3 T3, P4 T4, P5 T5, P6 T6);
+impl_parser_for_tuple!(P1 T1, P2 T2, P3 T3, P4 T4, P5 T5, P6 T6, P7 T7);
+
+/// The text up to the end of the line, without the line ending.
+fn current_line(s: &str) -> &str {
+    let line = &s[..s.find('\n').unwrap_or(s.len())];
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+/// The text up to the end of the next word, for showing what was found instead of what was expected.
+fn next_token(s: &str) -> &str {
+    let line = current_line(s);
+    let start = line.len() - line.trim_start().len();
+    let end = line[start..].find(char::is_whitespace).map_or(line.len(), |i| start + i);
+    &line[..end]
+}
+
+/// The first character of `s`, or nothing at the end of the line.
+fn next_char(s: &str) -> &str {
+    let line = current_line(s);
+    &line[..line.chars().next().map_or(0, char::len_utf8)]
+}
+
+/// Parse all of `s` with `parser`, failing if it leaves any text unparsed.
+fn complete<'a, T>(parser: &impl Parser<'a, T>, s: &'a str) -> Result<T, Failure<'a>> {
+    match parser.parse(s)? {
+        (value, "") => Ok(value),
+        (_, rest) if s[..s.len() - rest.len()].ends_with('\n') =>
+            Err(Failure::new(current_line(rest), ParseErrorKind::Expected("no more lines"))),
+        (_, rest) => Err(Failure::new(current_line(rest), ParseErrorKind::Expected("the end of the line"))),
+    }
+}
+
+/// Parse the whole of `input`, apart from any whitespace at its end, with `parser`.
+pub fn parse_all<'a, T>(input: &'a str, parser: impl Parser<'a, T>) -> Result<T, ParseError> {
+    match parser.parse(input.trim_end()) {
+        Ok((value, "")) => Ok(value),
+        Ok((_, rest)) => Err(ParseError::new(input, current_line(rest.trim_start()),
+            ParseErrorKind::Expected("the end of the input"))),
+        Err(failure) => Err(failure.into_error(input)),
+    }
+}
+
+/// Parse `text` exactly.
+pub fn tag<'a>(text: &'static str) -> impl Parser<'a, &'a str> {
+    move |s: &'a str| match s.strip_prefix(text) {
+        Some(rest) => Ok((&s[..text.len()], rest)),
+        None => Err(Failure::new(next_token(s), ParseErrorKind::ExpectedText(text))),
+    }
+}
+
+/// Parse a number: an optional `+` or `-` sign, then digits. The number runs to the end of the word,
+/// so `97x9` is an invalid number rather than `97` followed by `x9`. Invalid numbers are fatal.
+pub fn integer<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |s: &'a str| -> PResult<'a, T> {
+        let sign = if s.starts_with(['+', '-']) { 1 } else { 0 };
+        if !s[sign..].starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(Failure::new(next_token(s), ParseErrorKind::Expected("a number")));
+        }
+        let end = s[sign..].find(|c: char| !c.is_alphanumeric()).map_or(s.len(), |i| sign + i);
+        let number = &s[..end];
+        match number.parse() {
+            Ok(value) => Ok((value, &s[end..])),
+            Err(_) => Err(Failure::fatal(number, ParseErrorKind::InvalidNumber)),
+        }
+    }
+}
+
+/// Parse a word of one or more ASCII letters.
+pub fn word<'a>() -> impl Parser<'a, &'a str> {
+    take_while1("a word", |c| c.is_ascii_alphabetic())
+}
+
+/// Parse one character for which `check` is true, described by `expected`.
+pub fn satisfy<'a, F: Fn(char) -> bool>(expected: &'static str, check: F) -> impl Parser<'a, char> {
+    move |s: &'a str| match s.chars().next() {
+        Some(c) if c != '\n' && check(c) => Ok((c, &s[c.len_utf8()..])),
+        _ => Err(Failure::new(next_char(s), ParseErrorKind::Expected(expected))),
+    }
+}
+
+/// Parse one or more characters on this line for which `check` is true, described by `expected`.
+pub fn take_while1<'a, F: Fn(char) -> bool>(expected: &'static str, check: F) -> impl Parser<'a, &'a str> {
+    move |s: &'a str| {
+        let end = s.find(|c: char| c == '\n' || !check(c)).unwrap_or(s.len());
+        if end == 0 {
+            return Err(Failure::new(next_char(s), ParseErrorKind::Expected(expected)));
+        }
+        Ok((&s[..end], &s[end..]))
+    }
+}
+
+/// Parse the end of the text, e.g. `lines(item).or(end().map(|_| Vec::new()))` parses no lines at all.
+pub fn end<'a>() -> impl Parser<'a, ()> {
+    move |s: &'a str| match s {
+        "" => Ok(((), s)),
+        _ => Err(Failure::new(next_token(s), ParseErrorKind::Expected("the end of the text"))),
+    }
+}
+
+/// Parse all of the rest of the text, whatever it is.
+pub fn rest<'a>() -> impl Parser<'a, &'a str> {
+    move |s: &'a str| Ok((s, &s[s.len()..]))
+}
+
+/// Run `parser` without using up any text, to check what comes next.
+pub fn peek<'a, T, P: Parser<'a, T>>(parser: P) -> impl Parser<'a, T> {
+    move |s: &'a str| parser.parse(s).map(|(value, _)| (value, s))
+}
+
+/// Run `parser`, and return the text it parsed with its value.
+pub fn consumed<'a, T, P: Parser<'a, T>>(parser: P) -> impl Parser<'a, (&'a str, T)> {
+    move |s: &'a str| parser.parse(s).map(|(value, rest)| ((&s[..s.len() - rest.len()], value), rest))
+}
+
+/// Run `parser`, and return the text it parsed instead of its value.
+pub fn recognize<'a, T, P: Parser<'a, T>>(parser: P) -> impl Parser<'a, &'a str> {
+    consumed(parser).map(|(text, _)| text)
+}
+
+/// Make any failure of `parser` fatal, once the text so far has committed to what must come next,
+/// e.g. the `)` after `(1 + 2`.
+pub fn cut<'a, T, P: Parser<'a, T>>(parser: P) -> impl Parser<'a, T> {
+    move |s: &'a str| parser.parse(s).map_err(|failure| Failure { fatal: true, ..failure })
+}
+
+/// Run `parser` if it matches. It doesn't match if it fails without a fatal failure.
+pub fn opt<'a, T, P: Parser<'a, T>>(parser: P) -> impl Parser<'a, Option<T>> {
+    move |s: &'a str| match parser.parse(s) {
+        Ok((value, rest)) => Ok((Some(value), rest)),
+        Err(failure) if !failure.fatal => Ok((None, s)),
+        Err(failure) => Err(failure),
+    }
+}
+
+/// Parse one or more items separated by `separator`, e.g. `1 + 2 + 3`.
+/// An item must follow each separator.
+pub fn separated<'a, T, S, P, Q>(item: P, separator: Q) -> impl Parser<'a, Vec<T>>
+where P: Parser<'a, T>, Q: Parser<'a, S> {
+    move |s: &'a str| {
+        let (first, mut rest) = item.parse(s)?;
+        let mut items = vec![first];
+        while let Ok((_, after_separator)) = separator.parse(rest) {
+            let (next, after_item) = item.parse(after_separator)?;
+            items.push(next);
+            rest = after_item;
+        }
+        Ok((items, rest))
+    }
+}
+
+/// Parse all of the rest of the text with `parser`, again and again, e.g. each character of a line.
+pub fn repeat<'a, T, P: Parser<'a, T>>(parser: P) -> impl Parser<'a, Vec<T>> {
+    move |s: &'a str| {
+        let mut items = Vec::new();
+        let mut rest = s;
+        loop {
+            let (item, after) = parser.parse(rest)?;
+            items.push(item);
+            rest = after;
+            if rest.is_empty() {
+                return Ok((items, rest));
+            }
+        }
+    }
+}
+
+/// Split all of the rest of the text at each `separator`, and parse each piece with `item`,
+/// e.g. `split(",", integer())` parses `1,2,3`.
+pub fn split<'a, T, P: Parser<'a, T>>(separator: &'static str, item: P) -> impl Parser<'a, Vec<T>> {
+    move |s: &'a str| {
+        let items = s.split(separator).map(|piece| complete(&item, piece)).collect::<Result<_, _>>()?;
+        Ok((items, &s[s.len()..]))
+    }
+}
+
+/// Split all of the rest of the text at whitespace, including line endings, and parse each word with `item`.
+pub fn split_whitespace<'a, T, P: Parser<'a, T>>(item: P) -> impl Parser<'a, Vec<T>> {
+    move |s: &'a str| {
+        let items = s.split_whitespace().map(|piece| complete(&item, piece)).collect::<Result<_, _>>()?;
+        Ok((items, &s[s.len()..]))
+    }
+}
+
+/// Parse all of the rest of the text, which must end with `suffix`, with `parser`, e.g.
+/// `ending_with(".", split(", ", word()))` parses `a, b, c.`
+pub fn ending_with<'a, T, P: Parser<'a, T>>(suffix: &'static str, parser: P) -> impl Parser<'a, T> {
+    move |s: &'a str| match s.strip_suffix(suffix) {
+        Some(before) => Ok((complete(&parser, before)?, &s[s.len()..])),
+        None => Err(Failure::new(&s[s.len()..], ParseErrorKind::ExpectedText(suffix))),
+    }
+}
+
+/// Parse one line with `parser`, which must parse all of it, and move on to the next line.
+pub fn line<'a, T, P: Parser<'a, T>>(parser: P) -> impl Parser<'a, T> {
+    move |s: &'a str| {
+        let text = current_line(s);
+        let value = complete(&parser, text)?;
+        let rest = &s[text.len()..];
+        let rest = rest.strip_prefix("\r\n").or_else(|| rest.strip_prefix('\n')).unwrap_or(rest);
+        Ok((value, rest))
+    }
+}
+
+/// Parse each line of all of the rest of the text with `item`, which must parse all of the line.
+pub fn lines<'a, T, P: Parser<'a, T>>(item: P) -> impl Parser<'a, Vec<T>> {
+    move |s: &'a str| {
+        let items = s
+            .split('\n')
+            .map(|line| complete(&item, line.strip_suffix('\r').unwrap_or(line)))
+            .collect::<Result<_, _>>()?;
+        Ok((items, &s[s.len()..]))
+    }
+}
+
+/// Parse a paragraph, up to the next blank line, with `parser`, which must parse all of it,
+/// and move on past the blank line.
+pub fn paragraph<'a, T, P: Parser<'a, T>>(parser: P) -> impl Parser<'a, T> {
+    move |s: &'a str| {
+        let (text, rest) = match s.find("\n\n").or_else(|| s.find("\n\r\n")) {
+            Some(end) => (&s[..end], s[end..].trim_start_matches(['\r', '\n'])),
+            None => (s, &s[s.len()..]),
+        };
+        Ok((complete(&parser, text.strip_suffix('\r').unwrap_or(text))?, rest))
+    }
+}
+
+/// Parse each paragraph of all of the rest of the text with `item`.
+pub fn paragraphs<'a, T, P: Parser<'a, T>>(item: P) -> impl Parser<'a, Vec<T>> {
+    let paragraph = paragraph(item);
+    move |s: &'a str| {
+        let mut items = Vec::new();
+        let mut rest = s;
+        loop {
+            let (item, after) = paragraph.parse(rest)?;
+            items.push(item);
+            rest = after;
+            if rest.is_empty() {
+                return Ok((items, rest));
+            }
+        }
+    }
+}
+
+/// Parse a required section of the input, named `name`, with `parser`.
+/// If the input has already ended, the failure is that the input ended before `name`.
+pub fn section<'a, T, P: Parser<'a, T>>(name: &'static str, parser: P) -> impl Parser<'a, T> {
+    move |s: &'a str| {
+        if s.trim().is_empty() {
+            return Err(Failure::fatal(s, ParseErrorKind::MissingSection(name)));
+        }
+        parser.parse(s)
+    }
+}
+
+/// Parse a `key:value` pair, where the key is a word and the value runs to the next whitespace.
+pub fn key_value<'a>() -> impl Parser<'a, (&'a str, &'a str)> {
+    (word(), tag(":"), take_while1("a value", |c| !c.is_whitespace())).map(|(key, _, value)| (key, value))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range<'a>() -> impl Parser<'a, (u32, u32)> {
+        (integer(), tag("-"), integer()).map(|(low, _, high)| (low, high)).expect("a range like `1-3`")
+    }
+
+    #[test]
+    fn test_sequence() {
+        assert_eq!(range().parse("1-3 a"), Ok(((1, 3), " a")));
+        assert_eq!(parse_all("12-345\n", range()), Ok((12, 345)));
+    }
+
+    #[test]
+    fn test_integer() {
+        assert_eq!(integer::<i32>().parse("-12,3"), Ok((-12, ",3")));
+        assert_eq!(integer::<i32>().parse("+7"), Ok((7, "")));
+        let error = parse_all("1\n97x9", lines(integer::<u32>())).unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 1: invalid number `97x9`");
+        let error = parse_all("12\n\n4\n", lines(integer::<u32>())).unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 1: expected a number, found the end of the line");
+    }
+
+    #[test]
+    fn test_expect() {
+        let error = parse_all("1-3\n1 3 x", lines(range())).unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 1: expected a range like `1-3`, found `1 3 x`");
+        // Invalid numbers are reported as they are.
+        let error = parse_all("1-99999999999", range()).unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 3: invalid number `99999999999`");
+    }
+
+    #[test]
+    fn test_verify() {
+        let positive = integer::<u32>().verify(|&n| n > 0, "a number of at least 1");
+        let error = parse_all("1,0,2", split(",", positive)).unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 3: expected a number of at least 1, found `0`");
+    }
+
+    #[test]
+    fn test_or() {
+        let bus = || tag("x").map(|_| None).or(integer::<u32>().map(Some));
+        assert_eq!(parse_all("7,x,13", split(",", bus())), Ok(vec![Some(7), None, Some(13)]));
+        let error = parse_all("7,y", split(",", bus().expect("`x` or a bus ID"))).unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 3: expected `x` or a bus ID, found `y`");
+    }
+
+    #[test]
+    fn test_separated() {
+        let sum = separated(integer::<u32>(), tag(" + "));
+        assert_eq!(sum.parse("1 + 2 * 3"), Ok((vec![1, 2], " * 3")));
+        let error = parse_all("1 + x", sum).unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 5: expected a number, found `x`");
+    }
+
+    #[test]
+    fn test_repeat() {
+        let letters = || lines(repeat(satisfy("a letter from `a` to `z`", |c| c.is_ascii_lowercase())));
+        assert_eq!(parse_all("ab\nc", letters()), Ok(vec![vec!['a', 'b'], vec!['c']]));
+        let error = parse_all("ab\ncD", letters()).unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 2: expected a letter from `a` to `z`, found `D`");
+    }
+
+    #[test]
+    fn test_ending_with() {
+        let list = || ending_with(".", split(", ", word()));
+        assert_eq!(parse_all("a, b.", list()), Ok(vec!["a", "b"]));
+        let error = parse_all("a, b", list()).unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 5: expected `.`, found the end of the line");
+    }
+
+    #[test]
+    fn test_paragraphs() {
+        let passports = || paragraphs(split_whitespace(key_value().expect("a field like `byr:1937`")));
+        let input = "a:1 b:2\nc:3\r\n\r\nd:4\n";
+        assert_eq!(parse_all(input, passports()), Ok(vec![vec![("a", "1"), ("b", "2"), ("c", "3")], vec![("d", "4")]]));
+        let error = parse_all("a:1\n\nb:2 c 3", passports()).unwrap_err();
+        assert_eq!(error.to_string(), "line 3, column 5: expected a field like `byr:1937`, found `c`");
+    }
+
+    #[test]
+    fn test_sections() {
+        let notes = || (
+            paragraph(lines(integer::<u32>())),
+            section("the messages", paragraph((line(tag("messages:")), lines(word())))),
+        );
+        assert_eq!(parse_all("1\n2\n\nmessages:\nab", notes()), Ok((vec![1, 2], ("messages:", vec!["ab"]))));
+        let error = parse_all("1\n2\n", notes()).unwrap_err();
+        assert_eq!(error.to_string(), "line 3, column 1: input ended before the messages");
+        let error = parse_all("1\n\nmessages:\nab\n\nextra", notes()).unwrap_err();
+        assert_eq!(error.to_string(), "line 6, column 1: expected the end of the input, found `extra`");
+    }
+
+    #[test]
+    fn test_tag_at_end_of_line() {
+        let error = parse_all("(1\n", (tag("("), integer::<u32>(), tag(")"))).unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 3: expected `)`, found the end of the line");
+    }
+}
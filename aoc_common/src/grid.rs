@@ -0,0 +1,308 @@
+//! A rectangular 2D grid of cells, stored row by row in one `Vec`.
+
+use std::{fmt, ops::{Index, IndexMut}};
+use crate::{combinators::{consumed, lines, parse_all, repeat, satisfy, Parser}, ParseError, ParseErrorKind};
+
+/// A cell which is written as a single character in puzzle inputs, e.g. `#` for a tree.
+pub trait Cell: Copy {
+    /// Describes the allowed characters, for parse errors, e.g. "`.` or `#`".
+    const EXPECTED: &'static str;
+
+    /// The cell written as `c`, or `None` if `c` is not a valid cell.
+    fn from_char(c: char) -> Option<Self>;
+
+    fn to_char(self) -> char;
+}
+
+/// A position in a grid, as (row, column) counting from the top left.
+pub type Position = (usize, usize);
+
+/// A step between positions, as (rows down, columns right).
+pub type Direction = (isize, isize);
+
+/// The 4 directions up, left, right and down.
+pub const ORTHOGONAL_DIRECTIONS: [Direction; 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// The 8 directions to the adjacent positions, including diagonals.
+pub const ALL_DIRECTIONS: [Direction; 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+/// Which edges of a grid wrap around to the opposite edge.
+/// For example, with `horizontal` wrapping, moving right from the last column goes to the first column.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Wrap {
+    pub horizontal: bool,
+    pub vertical: bool,
+}
+
+/// A rectangular grid of cells.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    wrap: Wrap,
+    /// The cells, row by row. The cell at (row, col) is at index `row * width + col`.
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid of `height` rows of `width` cells, all set to `fill`. The grid does not wrap.
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> where T: Clone {
+        Grid { width, height, wrap: Wrap::default(), cells: vec![fill; width * height] }
+    }
+
+    /// Make a grid from its rows. The grid does not wrap.
+    ///
+    /// # Panics
+    /// Panics if the rows are not all the same width.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        assert!(rows.iter().all(|row| row.len() == width), "grid rows must all be the same width");
+        Grid { width, height, wrap: Wrap::default(), cells: rows.into_iter().flatten().collect() }
+    }
+
+    /// Set which edges of the grid wrap around.
+    pub fn with_wrap(mut self, wrap: Wrap) -> Grid<T> {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn wrap(&self) -> Wrap {
+        self.wrap
+    }
+
+    /// The cell at `position`, or `None` if it is outside the grid.
+    pub fn get(&self, (row, col): Position) -> Option<&T> {
+        if row < self.height && col < self.width {
+            Some(&self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    /// All of the cells, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// All of the positions in the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// The rows of the grid, from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `max(1)` because `chunks` panics on a size of 0. A grid with no columns has no cells to chunk.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The position one `direction` step from `position`, wrapping around the edges which wrap.
+    /// `None` if the step leaves the grid.
+    pub fn step(&self, (row, col): Position, (d_row, d_col): Direction) -> Option<Position> {
+        Some((
+            step_coordinate(row, d_row, self.height, self.wrap.vertical)?,
+            step_coordinate(col, d_col, self.width, self.wrap.horizontal)?,
+        ))
+    }
+
+    /// The positions one step from `position` in each of `directions` which are in the grid.
+    pub fn neighbors<'a>(&'a self, position: Position, directions: &'a [Direction])
+            -> impl Iterator<Item = Position> + 'a {
+        directions.iter().filter_map(move |&direction| self.step(position, direction))
+    }
+
+    /// The positions up, left, right and down from `position` which are in the grid.
+    pub fn neighbors_4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        self.neighbors(position, &ORTHOGONAL_DIRECTIONS)
+    }
+
+    /// The positions adjacent to `position`, including diagonally, which are in the grid.
+    pub fn neighbors_8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        self.neighbors(position, &ALL_DIRECTIONS)
+    }
+
+    /// The positions on a ray from `start` (not including `start`), taking repeated steps in
+    /// `direction` until the ray leaves the grid, or wraps around back to `start`.
+    pub fn ray(&self, start: Position, direction: Direction) -> Ray<'_, T> {
+        Ray { grid: self, start, position: start, direction }
+    }
+
+    /// A grid of the same size and wrapping, with `f` applied to each cell.
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid { width: self.width, height: self.height, wrap: self.wrap, cells: self.cells.iter().map(f).collect() }
+    }
+}
+
+/// Move `x` by `dx` in a dimension of size `size`.
+fn step_coordinate(x: usize, dx: isize, size: usize, wrap: bool) -> Option<usize> {
+    let moved = x as isize + dx;
+    if wrap && size > 0 {
+        Some(moved.rem_euclid(size as isize) as usize)
+    } else if 0 <= moved && moved < size as isize {
+        Some(moved as usize)
+    } else {
+        None
+    }
+}
+
+impl<T: Cell> Grid<T> {
+    /// Parse a grid, one row per line, where each character is a cell.
+    /// Every row must be the same width. The grid does not wrap.
+    pub fn parse(input: &str) -> Result<Grid<T>, ParseError> {
+        let cell = satisfy(T::EXPECTED, |c| T::from_char(c).is_some()).map(|c| T::from_char(c).unwrap());
+        let rows = parse_all(input, lines(consumed(repeat(cell))))?;
+        let width = rows[0].1.len();
+        if let Some(&(line, _)) = rows.iter().find(|(_, row)| row.len() != width) {
+            return Err(ParseError::new(input, line, ParseErrorKind::Expected("a row as wide as the first row")));
+        }
+        Ok(Grid::from_rows(rows.into_iter().map(|(_, row)| row).collect()))
+    }
+}
+
+/// Render the grid in the same format that [`Grid::parse`] reads, with a newline after each row.
+impl<T: Cell> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            writeln!(f, "{}", row.iter().map(|&cell| cell.to_char()).collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): Position) -> &T {
+        assert!(row < self.height && col < self.width, "position {:?} is outside the grid", (row, col));
+        &self.cells[row * self.width + col]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, (row, col): Position) -> &mut T {
+        assert!(row < self.height && col < self.width, "position {:?} is outside the grid", (row, col));
+        &mut self.cells[row * self.width + col]
+    }
+}
+
+/// Iterator over the positions on a ray through a grid. See [`Grid::ray`].
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    start: Position,
+    position: Position,
+    direction: Direction,
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Position> {
+        let next = self.grid.step(self.position, self.direction)?;
+        if next == self.start {
+            return None;
+        }
+        self.position = next;
+        Some(next)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Square {
+        Open,
+        Wall,
+    }
+
+    impl Cell for Square {
+        const EXPECTED: &'static str = "`.` or `#`";
+
+        fn from_char(c: char) -> Option<Square> {
+            match c {
+                '.' => Some(Square::Open),
+                '#' => Some(Square::Wall),
+                _ => None,
+            }
+        }
+
+        fn to_char(self) -> char {
+            match self {
+                Square::Open => '.',
+                Square::Wall => '#',
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_and_render() {
+        let grid = Grid::<Square>::parse("#..\n.#.\r\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(0, 0)], Square::Wall);
+        assert_eq!(grid[(1, 2)], Square::Open);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.to_string(), "#..\n.#.\n");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Grid::<Square>::parse("#.\n.x\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 2: expected `.` or `#`, found `x`");
+        let error = Grid::<Square>::parse("#.\n.#.\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 1: expected a row as wide as the first row, found `.#.`");
+    }
+
+    #[test]
+    fn test_step_and_wrap() {
+        let grid = Grid::new(3, 2, 0);
+        assert_eq!(grid.step((0, 2), (0, 1)), None);
+        assert_eq!(grid.step((0, 2), (1, -2)), Some((1, 0)));
+        let grid = grid.with_wrap(Wrap { horizontal: true, vertical: false });
+        assert_eq!(grid.step((0, 2), (0, 1)), Some((0, 0)));
+        assert_eq!(grid.step((0, 0), (0, -7)), Some((0, 2)));
+        assert_eq!(grid.step((1, 0), (1, 0)), None);
+        let grid = grid.with_wrap(Wrap { horizontal: true, vertical: true });
+        assert_eq!(grid.step((1, 0), (1, -1)), Some((0, 2)));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(grid.neighbors_4((0, 0)).collect::<Vec<Position>>(), vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbors_8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbors_8((2, 2)).collect::<Vec<Position>>(), vec![(1, 1), (1, 2), (2, 1)]);
+        let grid = grid.with_wrap(Wrap { horizontal: true, vertical: true });
+        assert_eq!(grid.neighbors_8((0, 0)).count(), 8);
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = Grid::new(4, 3, 0);
+        assert_eq!(grid.ray((0, 0), (1, 1)).collect::<Vec<Position>>(), vec![(1, 1), (2, 2)]);
+        assert_eq!(grid.ray((0, 3), (0, 1)).count(), 0);
+        // A wrapping ray stops when it gets back to the start.
+        let grid = grid.with_wrap(Wrap { horizontal: true, vertical: false });
+        assert_eq!(grid.ray((1, 1), (0, 1)).collect::<Vec<Position>>(), vec![(1, 2), (1, 3), (1, 0)]);
+        assert_eq!(grid.ray((1, 1), (0, 0)).count(), 0);
+    }
+
+    #[test]
+    fn test_positions_rows_and_map() {
+        let grid = Grid::from_rows(vec![vec![1, 2], vec![3, 4], vec![5, 6]]);
+        assert_eq!(grid.positions().collect::<Vec<Position>>(), vec![(0, 0), (0, 1), (1, 0), (1, 1), (2, 0), (2, 1)]);
+        assert_eq!(grid.rows().collect::<Vec<&[i32]>>(), vec![&[1, 2], &[3, 4], &[5, 6]]);
+        let doubled = grid.map(|&x| x * 2);
+        assert_eq!(doubled.iter().copied().collect::<Vec<i32>>(), vec![2, 4, 6, 8, 10, 12]);
+    }
+}
@@ -0,0 +1,167 @@
+use std::{env, error, fmt, fs::File, io::{self, BufRead, Read}, path::PathBuf, ffi::OsString};
+use crate::Args;
+
+/// Environment variable which names the puzzle input file when no path is given on the
+/// command line. Set it to `-` to read from stdin.
+pub const INPUT_ENV_VAR: &str = "AOC_INPUT";
+
+/// Where to read a puzzle input from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Interpret a command line argument or environment variable value.
+    /// `-` means stdin, anything else is a file path.
+    pub fn from_arg(arg: &str) -> InputSource {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(arg))
+        }
+    }
+
+    /// Choose the input source for a day's binary.
+    /// In order of preference, this is:
+    ///   1. the input path on the command line (see [`Args`]),
+    ///   2. the `AOC_INPUT` environment variable,
+    ///   3. `default_path`, which is usually the `input.txt` next to the day's `Cargo.toml`.
+    pub fn from_env(default_path: &str) -> InputSource {
+        select_source(Args::from_env().input, env::var_os(INPUT_ENV_VAR), default_path)
+    }
+
+    /// Open the input for reading line by line.
+    pub fn open(&self) -> Result<Box<dyn BufRead>, InputError> {
+        match self {
+            InputSource::File(path) => match File::open(path) {
+                Ok(file) => Ok(Box::new(io::BufReader::new(file))),
+                Err(error) => Err(InputError::Io { source: self.clone(), error }),
+            },
+            InputSource::Stdin => Ok(Box::new(io::BufReader::new(io::stdin()))),
+        }
+    }
+
+    /// Read the whole input into a string.
+    /// Returns an error if the input can't be read, or if it is empty.
+    pub fn read_to_string(&self) -> Result<String, InputError> {
+        let mut text = String::new();
+        if let Err(error) = self.open()?.read_to_string(&mut text) {
+            return Err(InputError::Io { source: self.clone(), error });
+        }
+        if text.trim().is_empty() {
+            return Err(InputError::Empty(self.clone()));
+        }
+        Ok(text)
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+fn select_source(arg: Option<String>, env_value: Option<OsString>, default_path: &str) -> InputSource {
+    if let Some(arg) = arg {
+        return InputSource::from_arg(&arg);
+    }
+    if let Some(env_value) = env_value {
+        if !env_value.is_empty() {
+            return InputSource::from_arg(&env_value.to_string_lossy());
+        }
+    }
+    InputSource::File(PathBuf::from(default_path))
+}
+
+/// Errors from loading a puzzle input.
+#[derive(Debug)]
+pub enum InputError {
+    /// The input could not be opened or read.
+    Io { source: InputSource, error: io::Error },
+    /// The input was read, but contained no text.
+    Empty(InputSource),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Io { source, error } => write!(f, "could not read input {}: {}", source, error),
+            InputError::Empty(source) => write!(f, "input {} is empty", source),
+        }
+    }
+}
+
+impl error::Error for InputError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            InputError::Io { error, .. } => Some(error),
+            InputError::Empty(_) => None,
+        }
+    }
+}
+
+/// Read the whole puzzle input from the source chosen by [`InputSource::from_env`].
+pub fn load_input(default_path: &str) -> Result<String, InputError> {
+    InputSource::from_env(default_path).read_to_string()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use super::*;
+
+    fn temp_file(name: &str, contents: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("aoc_common_{}_{}", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_select_source_precedence() {
+        let env_value = Some(OsString::from("from_env.txt"));
+        assert_eq!(select_source(Some("from_arg.txt".to_string()), env_value.clone(), "default.txt"),
+            InputSource::File(PathBuf::from("from_arg.txt")));
+        assert_eq!(select_source(None, env_value, "default.txt"),
+            InputSource::File(PathBuf::from("from_env.txt")));
+        assert_eq!(select_source(None, Some(OsString::new()), "default.txt"),
+            InputSource::File(PathBuf::from("default.txt")));
+        assert_eq!(select_source(None, None, "default.txt"),
+            InputSource::File(PathBuf::from("default.txt")));
+    }
+
+    #[test]
+    fn test_dash_means_stdin() {
+        assert_eq!(select_source(Some("-".to_string()), None, "default.txt"), InputSource::Stdin);
+        assert_eq!(select_source(None, Some(OsString::from("-")), "default.txt"), InputSource::Stdin);
+    }
+
+    #[test]
+    fn test_read_file() {
+        let path = temp_file("read.txt", "1\n2\n3\n");
+        let text = InputSource::File(path.clone()).read_to_string().unwrap();
+        assert_eq!(text, "1\n2\n3\n");
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_empty_file_is_an_error() {
+        let path = temp_file("empty.txt", " \n");
+        let result = InputSource::File(path.clone()).read_to_string();
+        assert!(matches!(result, Err(InputError::Empty(_))));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_missing_file_is_an_error() {
+        let source = InputSource::File(PathBuf::from("./does_not_exist.txt"));
+        let error = source.read_to_string().unwrap_err();
+        assert!(matches!(error, InputError::Io { .. }));
+        assert!(error.to_string().starts_with("could not read input ./does_not_exist.txt"));
+    }
+}
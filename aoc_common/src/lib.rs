@@ -0,0 +1,36 @@
+//! Helpers shared by all of the Advent of Code 2020 day crates.
+
+use std::{fmt, process};
+
+mod args;
+pub mod combinators;
+mod grid;
+mod input;
+mod logging;
+mod parse;
+mod solution;
+
+pub use args::Args;
+pub use grid::{Cell, Direction, Grid, Position, Ray, Wrap, ALL_DIRECTIONS, ORTHOGONAL_DIRECTIONS};
+pub use input::{load_input, InputError, InputSource, INPUT_ENV_VAR};
+pub use logging::{parse_level, DayLevel, LogSettings};
+pub use parse::{parse_number, parse_number_lines, ParseError, ParseErrorKind};
+pub use solution::{format_json, OutputFormat, PartResult, Solution, Solver};
+
+/// Extension for `Result`s in `main` functions.
+/// On error, print a readable message and exit, instead of panicking with a backtrace.
+pub trait UnwrapOrExit<T> {
+    fn unwrap_or_exit(self) -> T;
+}
+
+impl<T, E: fmt::Display> UnwrapOrExit<T> for Result<T, E> {
+    fn unwrap_or_exit(self) -> T {
+        match self {
+            Ok(value) => value,
+            Err(error) => {
+                eprintln!("error: {}", error);
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Tracing output from the solvers, which report their progress as `tracing` spans and events.
+//!
+//! The events of each day have the day's crate as their target, like `day11`, so each day can
+//! have its own log level.
+
+use std::{fmt, fs::File, io, path::PathBuf, str::FromStr, sync::Mutex};
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::{fmt::format::FmtSpan, EnvFilter};
+
+/// Which spans and events to write, and where to write them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogSettings {
+    /// The most detailed level to write for every day, unless the day is in `day_levels`.
+    pub level: LevelFilter,
+    pub day_levels: Vec<DayLevel>,
+    /// Write to this file instead of stderr.
+    pub file: Option<PathBuf>,
+}
+
+impl Default for LogSettings {
+    fn default() -> LogSettings {
+        LogSettings { level: LevelFilter::WARN, day_levels: Vec::new(), file: None }
+    }
+}
+
+impl LogSettings {
+    /// The `EnvFilter` directives for these settings, like `warn,day11=trace`.
+    pub fn filter_directives(&self) -> String {
+        let mut directives = vec![self.level.to_string().to_lowercase()];
+        directives.extend(self.day_levels.iter().map(|day_level| day_level.to_string()));
+        directives.join(",")
+    }
+
+    /// Install the global subscriber, which writes each event, and the time spent in each span
+    /// when it closes. Fails if the file can't be created, or a global subscriber is already
+    /// installed.
+    pub fn init(&self) -> Result<(), String> {
+        let filter = EnvFilter::try_new(self.filter_directives()).map_err(|error| error.to_string())?;
+        let builder = tracing_subscriber::fmt()
+            .with_env_filter(filter)
+            .with_span_events(FmtSpan::CLOSE);
+        let result = match &self.file {
+            Some(path) => {
+                let file = File::create(path)
+                    .map_err(|error| format!("could not create {}: {}", path.display(), error))?;
+                builder.with_ansi(false).with_writer(Mutex::new(file)).try_init()
+            }
+            None => builder.with_writer(io::stderr).try_init(),
+        };
+        result.map_err(|error| error.to_string())
+    }
+}
+
+/// Parse a log level: `off`, `error`, `warn`, `info`, `debug` or `trace`.
+pub fn parse_level(s: &str) -> Result<LevelFilter, String> {
+    s.parse().map_err(|_| format!("expected a log level from `off`, `error`, `warn`, `info`, `debug` or `trace`, found `{}`", s))
+}
+
+/// The log level for one day, written like `11=trace` or `day11=trace`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DayLevel {
+    pub day: u32,
+    pub level: LevelFilter,
+}
+
+impl FromStr for DayLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<DayLevel, String> {
+        let (day, level) = s.split_once('=').ok_or_else(|| format!("expected a day and level like `11=trace`, found `{}`", s))?;
+        let day = day.strip_prefix("day").unwrap_or(day).parse()
+            .map_err(|_| format!("expected a day number, found `{}`", day))?;
+        Ok(DayLevel { day, level: parse_level(level)? })
+    }
+}
+
+impl fmt::Display for DayLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "day{}={}", self.day, self.level.to_string().to_lowercase())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_day_level() {
+        assert_eq!("11=trace".parse(), Ok(DayLevel { day: 11, level: LevelFilter::TRACE }));
+        assert_eq!("8=off".parse(), Ok(DayLevel { day: 8, level: LevelFilter::OFF }));
+        assert!("11".parse::<DayLevel>().is_err());
+        assert_eq!("day11=trace".parse(), Ok(DayLevel { day: 11, level: LevelFilter::TRACE }));
+        assert!("eleven=trace".parse::<DayLevel>().is_err());
+        assert!("11=loud".parse::<DayLevel>().is_err());
+    }
+
+    #[test]
+    fn test_filter_directives() {
+        assert_eq!(LogSettings::default().filter_directives(), "warn");
+        let settings = LogSettings {
+            level: LevelFilter::INFO,
+            day_levels: vec!["11=trace".parse().unwrap(), "4=debug".parse().unwrap()],
+            file: None,
+        };
+        assert_eq!(settings.filter_directives(), "info,day11=trace,day4=debug");
+        assert!(EnvFilter::try_new(settings.filter_directives()).is_ok());
+    }
+}
@@ -0,0 +1,133 @@
+//! Errors for malformed puzzle input, which point at the offending text.
+
+use std::{error, fmt, str::FromStr};
+use crate::combinators::{integer, lines, parse_all};
+
+/// An error from parsing a puzzle input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    /// Line of the input where the error is, counting from 1.
+    pub line: usize,
+    /// Column of the start of the offending text, counting characters from 1.
+    pub column: usize,
+    /// The offending text. Empty if the input ended too soon.
+    pub text: String,
+    pub kind: ParseErrorKind,
+}
+
+/// What is wrong with the offending text.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseErrorKind {
+    /// The text should be a number, but isn't one or is out of range.
+    InvalidNumber,
+    /// The text doesn't have the expected form. Holds a description of what was expected,
+    /// e.g. "an instruction like `acc +1`".
+    Expected(&'static str),
+    /// The text should start with this exact text, e.g. `)`.
+    ExpectedText(&'static str),
+    /// The input ended before a required section, e.g. "the list of messages".
+    MissingSection(&'static str),
+    /// The text refers to something which isn't defined, e.g. "rule".
+    Undefined(&'static str),
+}
+
+impl ParseError {
+    /// Create an error for `offending`, which must be a slice of `input`.
+    /// The line and column are found from where `offending` is in `input`.
+    ///
+    /// # Panics
+    /// Panics if `offending` is not a slice of `input`.
+    pub fn new(input: &str, offending: &str, kind: ParseErrorKind) -> ParseError {
+        let start = input.as_ptr() as usize;
+        let offset = (offending.as_ptr() as usize).wrapping_sub(start);
+        assert!(offset <= input.len() && offset + offending.len() <= input.len(),
+            "offending text is not a slice of the input");
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[line_start..].chars().count() + 1;
+        ParseError { line, column, text: offending.to_string(), kind }
+    }
+
+    /// Move an error found by parsing a single line on its own to line `line` of the whole input.
+    pub fn on_line(self, line: usize) -> ParseError {
+        ParseError { line, ..self }
+    }
+
+    /// Create an error for an input which ends before a required section.
+    pub fn missing_section(input: &str, section: &'static str) -> ParseError {
+        ParseError::new(input, &input[input.len()..], ParseErrorKind::MissingSection(section))
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match &self.kind {
+            ParseErrorKind::InvalidNumber => write!(f, "invalid number `{}`", self.text),
+            ParseErrorKind::Expected(expected) if self.text.is_empty() =>
+                write!(f, "expected {}, found the end of the line", expected),
+            ParseErrorKind::Expected(expected) => write!(f, "expected {}, found `{}`", expected, self.text),
+            ParseErrorKind::ExpectedText(expected) if self.text.is_empty() =>
+                write!(f, "expected `{}`, found the end of the line", expected),
+            ParseErrorKind::ExpectedText(expected) => write!(f, "expected `{}`, found `{}`", expected, self.text),
+            ParseErrorKind::MissingSection(section) => write!(f, "input ended before {}", section),
+            ParseErrorKind::Undefined(what) => write!(f, "undefined {} `{}`", what, self.text),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Parse `s`, which must be a slice of `input`, as a number.
+pub fn parse_number<T: FromStr>(input: &str, s: &str) -> Result<T, ParseError> {
+    s.parse::<T>().map_err(|_| ParseError::new(input, s, ParseErrorKind::InvalidNumber))
+}
+
+/// Parse a list of numbers, one per line.
+pub fn parse_number_lines<T: FromStr>(input: &str) -> Result<Vec<T>, ParseError> {
+    parse_all(input, lines(integer()))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_position() {
+        let input = "12\n34\nab5\n";
+        let error = ParseError::new(input, &input[7..9], ParseErrorKind::Expected("a digit"));
+        assert_eq!((error.line, error.column, error.text.as_str()), (3, 2, "b5"));
+        assert_eq!(error.to_string(), "line 3, column 2: expected a digit, found `b5`");
+    }
+
+    #[test]
+    fn test_parse_number() {
+        let input = "12\n3x4";
+        assert_eq!(parse_number::<u32>(input, &input[..2]), Ok(12));
+        let error = parse_number::<u32>(input, &input[3..]).unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 1: invalid number `3x4`");
+    }
+
+    #[test]
+    fn test_parse_number_lines() {
+        assert_eq!(parse_number_lines::<i64>("12\n-3\n"), Ok(vec![12, -3]));
+        let error = parse_number_lines::<i64>("12\n\n4\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 1: expected a number, found the end of the line");
+    }
+
+    #[test]
+    fn test_missing_section() {
+        let input = "rules\n";
+        let error = ParseError::missing_section(input, "the messages");
+        assert_eq!(error.to_string(), "line 2, column 1: input ended before the messages");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_offending_text_outside_input() {
+        let other = String::from("abc");
+        ParseError::new("abc", &other, ParseErrorKind::InvalidNumber);
+    }
+}
@@ -0,0 +1,189 @@
+//! Answers together with the intermediate values behind them, and reports of them as text or JSON.
+
+use std::{borrow::Cow, collections::BTreeMap, fmt, str::FromStr, time::{Duration, Instant}};
+use serde::Serialize;
+use crate::ParseError;
+
+/// The answer to one part of a puzzle, with named intermediate values which led to it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Solution {
+    pub answer: String,
+    /// Named intermediate values in snake case, e.g. `("bus_id", "59")` for day 13 part 1.
+    /// Solvers name them with string literals, and the names of cached answers are read from a file.
+    pub intermediates: Vec<(Cow<'static, str>, String)>,
+}
+
+impl Solution {
+    /// A solution with no intermediate values.
+    pub fn new<T: fmt::Display>(answer: T) -> Solution {
+        Solution { answer: answer.to_string(), intermediates: Vec::new() }
+    }
+
+    /// Add an intermediate value.
+    pub fn with<T: fmt::Display>(mut self, name: &'static str, value: T) -> Solution {
+        self.intermediates.push((name.into(), value.to_string()));
+        self
+    }
+}
+
+/// Solves one part of a day's puzzle, given the puzzle input text.
+/// Fails if the input text is malformed.
+pub type Solver = fn(&str) -> Result<Solution, ParseError>;
+
+/// The outcome of running one part of one day's puzzle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub day: u32,
+    pub part: usize,
+    /// The solution, or a description of why there is no solution.
+    pub answer: Result<Solution, String>,
+    pub elapsed: Duration,
+}
+
+impl PartResult {
+    /// Run `solver` on the puzzle input `text`, and time how long it takes.
+    /// The solver runs inside a `part` span, which records the day and part.
+    pub fn run(day: u32, part: usize, solver: Solver, text: &str) -> PartResult {
+        let _span = tracing::info_span!("part", day, part).entered();
+        let start = Instant::now();
+        let answer = solver(text).map_err(|error| error.to_string());
+        PartResult { day, part, answer, elapsed: start.elapsed() }
+    }
+}
+
+/// How a binary prints its results.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    /// Free-form text for people to read.
+    Text,
+    /// The schema described at [`format_json`].
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<OutputFormat, String> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(format!("expected output format `text` or `json`, found `{}`", s)),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputFormat::Text => write!(f, "text"),
+            OutputFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonPartResult<'a> {
+    day: u32,
+    part: usize,
+    answer: Option<&'a str>,
+    intermediates: BTreeMap<&'a str, &'a str>,
+    error: Option<&'a str>,
+    elapsed_seconds: f64,
+}
+
+/// Render the results as a JSON array, with one object per day and part:
+///
+/// ```json
+/// [
+///   {
+///     "day": 13,
+///     "part": 1,
+///     "answer": "295",
+///     "intermediates": { "bus_id": "59", "wait_time": "5" },
+///     "error": null,
+///     "elapsed_seconds": 0.000004
+///   }
+/// ]
+/// ```
+///
+/// Answers and intermediate values are strings, because some answers are too big for a JSON number.
+/// If a part has no answer, `answer` is null, `intermediates` is empty, and `error` says why.
+pub fn format_json(results: &[PartResult]) -> String {
+    let json_results = results
+        .iter()
+        .map(|result| {
+            let (answer, intermediates, error) = match &result.answer {
+                Ok(solution) => (
+                    Some(solution.answer.as_str()),
+                    solution.intermediates.iter().map(|(name, value)| (name.as_ref(), value.as_str())).collect(),
+                    None,
+                ),
+                Err(error) => (None, BTreeMap::new(), Some(error.as_str())),
+            };
+            JsonPartResult {
+                day: result.day,
+                part: result.part,
+                answer,
+                intermediates,
+                error,
+                elapsed_seconds: result.elapsed.as_secs_f64(),
+            }
+        })
+        .collect::<Vec<JsonPartResult>>();
+    let mut json = serde_json::to_string_pretty(&json_results).unwrap();
+    json.push('\n');
+    json
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_output_format() {
+        assert_eq!("json".parse::<OutputFormat>(), Ok(OutputFormat::Json));
+        assert_eq!("text".parse::<OutputFormat>(), Ok(OutputFormat::Text));
+        assert!("yaml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn test_run() {
+        let result = PartResult::run(1, 2, |text| Ok(Solution::new(text.len()).with("text", text)), "abc");
+        assert_eq!(result.answer, Ok(Solution { answer: "3".to_string(), intermediates: vec![("text".into(), "abc".to_string())] }));
+        let result = PartResult::run(1, 2, |text| Err(ParseError::missing_section(text, "a number")), "abc");
+        assert_eq!(result.answer, Err("line 1, column 4: input ended before a number".to_string()));
+    }
+
+    #[test]
+    fn test_format_json() {
+        let results = vec![
+            PartResult {
+                day: 13,
+                part: 1,
+                answer: Ok(Solution::new(295).with("wait_time", 5).with("bus_id", 59)),
+                elapsed: Duration::from_micros(4),
+            },
+            PartResult { day: 13, part: 2, answer: Err("no input".to_string()), elapsed: Duration::from_micros(0) },
+        ];
+        let json: serde_json::Value = serde_json::from_str(&format_json(&results)).unwrap();
+        assert_eq!(json, serde_json::json!([
+            {
+                "day": 13,
+                "part": 1,
+                "answer": "295",
+                "intermediates": { "bus_id": "59", "wait_time": "5" },
+                "error": null,
+                "elapsed_seconds": 0.000004,
+            },
+            {
+                "day": 13,
+                "part": 2,
+                "answer": null,
+                "intermediates": {},
+                "error": "no input",
+                "elapsed_seconds": 0.0,
+            },
+        ]));
+    }
+}
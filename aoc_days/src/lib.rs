@@ -0,0 +1,131 @@
+//! Registry of every day's solvers, shared by the `aoc` runner, `aoc_server` and `aoc_ffi`.
+//! Each solver calls the `solve_part_1` or `solve_part_2` function of that day's library.
+
+use std::path::PathBuf;
+use aoc_common::{ParseError, Solution, Solver};
+
+pub struct Day {
+    pub number: u32,
+    /// Solvers for each part of the puzzle: `parts[0]` solves part 1 and `parts[1]` solves part 2.
+    /// A part is `None` if this repository has no solution for it.
+    pub parts: [Option<Solver>; 2],
+}
+
+impl Day {
+    /// Path to the puzzle input stored in this day's crate directory.
+    pub fn default_input_path(&self) -> PathBuf {
+        [env!("CARGO_MANIFEST_DIR"), "..", &format!("day{}", self.number), "input.txt"]
+            .iter()
+            .collect()
+    }
+
+    /// Get the solver for part `part` (counting from 1), if this day has that part.
+    pub fn part(&self, part: usize) -> Option<Solver> {
+        if part == 0 || part > self.parts.len() {
+            return None;
+        }
+        self.parts[part - 1]
+    }
+}
+
+/// Adapt a day's `solve_part_N` function, whose answer can be any displayable type, to a `Solver`.
+/// Days which give intermediate values have `solve_part_N_detailed` functions, which are already `Solver`s.
+macro_rules! solver {
+    ($solve:path) => {{
+        fn solve(text: &str) -> Result<Solution, ParseError> {
+            $solve(text).map(Solution::new)
+        }
+        Some(solve as Solver)
+    }};
+}
+
+pub const DAYS: [Day; 19] = [
+    Day { number: 1, parts: [Some(day1::solve_part_1_detailed), Some(day1::solve_part_2_detailed)] },
+    Day { number: 2, parts: [solver!(day2::solve_part_1), solver!(day2::solve_part_2)] },
+    Day { number: 3, parts: [solver!(day3::solve_part_1), solver!(day3::solve_part_2)] },
+    Day { number: 4, parts: [None, solver!(day4::solve_part_2)] },
+    Day { number: 5, parts: [solver!(day5::solve_part_1), solver!(day5::solve_part_2)] },
+    Day { number: 6, parts: [solver!(day6::solve_part_1), solver!(day6::solve_part_2)] },
+    Day { number: 7, parts: [solver!(day7::solve_part_1), solver!(day7::solve_part_2)] },
+    Day { number: 8, parts: [solver!(day8::solve_part_1), Some(day8::solve_part_2_detailed)] },
+    Day { number: 9, parts: [solver!(day9::solve_part_1), Some(day9::solve_part_2_detailed)] },
+    Day { number: 10, parts: [solver!(day10::solve_part_1), solver!(day10::solve_part_2)] },
+    Day { number: 11, parts: [solver!(day11::solve_part_1), solver!(day11::solve_part_2)] },
+    Day { number: 12, parts: [solver!(day12::solve_part_1), solver!(day12::solve_part_2)] },
+    Day { number: 13, parts: [Some(day13::solve_part_1_detailed), solver!(day13::solve_part_2)] },
+    Day { number: 14, parts: [solver!(day14::solve_part_1), solver!(day14::solve_part_2)] },
+    Day { number: 15, parts: [solver!(day15::solve_part_1), solver!(day15::solve_part_2)] },
+    Day { number: 16, parts: [solver!(day16::solve_part_1), solver!(day16::solve_part_2)] },
+    Day { number: 17, parts: [solver!(day17::solve_part_1), solver!(day17::solve_part_2)] },
+    Day { number: 18, parts: [solver!(day18::solve_part_1), solver!(day18::solve_part_2)] },
+    Day { number: 19, parts: [solver!(day19::solve_part_1), None] },
+];
+
+/// Look up a day by its number.
+pub fn find_day(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use super::*;
+
+    fn example(day: u32, filename: &str) -> String {
+        let path = find_day(day).unwrap().default_input_path().with_file_name(filename);
+        fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn test_days_are_in_order() {
+        for (i, day) in DAYS.iter().enumerate() {
+            assert_eq!(day.number, i as u32 + 1);
+        }
+    }
+
+    #[test]
+    fn test_part_lookup() {
+        let day = find_day(19).unwrap();
+        assert!(day.part(0).is_none());
+        assert!(day.part(1).is_some());
+        assert!(day.part(2).is_none());
+        assert!(day.part(3).is_none());
+        assert!(find_day(4).unwrap().part(1).is_none());
+        assert!(find_day(20).is_none());
+    }
+
+    #[test]
+    fn test_default_input_paths_exist() {
+        for day in DAYS.iter() {
+            assert!(day.default_input_path().is_file(), "missing input for day {}", day.number);
+        }
+    }
+
+    fn solve(day: u32, part: usize, text: &str) -> String {
+        find_day(day).unwrap().part(part).unwrap()(text).unwrap().answer
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(solve(3, 1, &example(3, "test_input.txt")), "7");
+        assert_eq!(solve(3, 2, &example(3, "test_input.txt")), "336");
+        assert_eq!(solve(7, 1, &example(7, "example_input.txt")), "4");
+        assert_eq!(solve(7, 2, &example(7, "example_input.txt")), "32");
+        assert_eq!(solve(8, 1, &example(8, "example_input.txt")), "5");
+        assert_eq!(solve(8, 2, &example(8, "example_input.txt")), "8");
+        assert_eq!(solve(11, 1, &example(11, "example_input.txt")), "37");
+        assert_eq!(solve(11, 2, &example(11, "example_input.txt")), "26");
+        assert_eq!(solve(12, 1, "F10\nN3\nF7\nR90\nF11"), "25");
+        assert_eq!(solve(12, 2, "F10\nN3\nF7\nR90\nF11"), "286");
+        assert_eq!(solve(13, 1, "939\n7,13,x,x,59,x,31,19"), "295");
+        assert_eq!(solve(13, 2, "939\n7,13,x,x,59,x,31,19"), "1068781");
+        assert_eq!(solve(15, 1, "0,3,6"), "436");
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        let error = find_day(12).unwrap().part(1).unwrap()("F10\nQ3").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+}
@@ -0,0 +1,15 @@
+//! Generate the C header `include/aoc_ffi.h` from the `extern "C"` functions in `src/lib.rs`.
+
+use std::env;
+
+fn main() {
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir)).unwrap())
+        .generate()
+        .expect("could not generate the C header")
+        .write_to_file(format!("{}/include/aoc_ffi.h", crate_dir));
+}
@@ -0,0 +1,108 @@
+/*
+ * C ABI for the Advent of Code 2020 solvers.
+ *
+ * Ownership rules:
+ * - Input strings and arrays are borrowed only for the duration of the call.
+ *   Input strings must be NUL-terminated UTF-8.
+ * - Strings returned through a `char **` out parameter are owned by the caller,
+ *   who must free each of them exactly once with `aoc_string_free`.
+ * - Buffers returned through an `AocU32Buffer *` out parameter are owned by the caller,
+ *   who must free each of them exactly once with `aoc_u32_buffer_free`.
+ * - Out parameters are only written when a function returns `AOC_STATUS_OK`.
+ * - The message returned by `aoc_last_error` is owned by the library. It is only valid until
+ *   the next call to an `aoc_` function on the same thread.
+ */
+
+#ifndef AOC_FFI_H
+#define AOC_FFI_H
+
+/* Generated from aoc_ffi/src/lib.rs by cbindgen, when aoc_ffi is built. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Result of every function which can fail. On failure, `aoc_last_error` describes the problem.
+typedef enum AocStatus {
+  AOC_STATUS_OK = 0,
+  // A required pointer argument was null.
+  AOC_STATUS_NULL_POINTER = 1,
+  // An input string was not valid UTF-8.
+  AOC_STATUS_INVALID_UTF8 = 2,
+  // The puzzle input is malformed.
+  AOC_STATUS_PARSE_ERROR = 3,
+  // An argument is out of range, e.g. a day with no solver.
+  AOC_STATUS_INVALID_ARGUMENT = 4,
+  // The solver panicked, e.g. because the puzzle has no solution.
+  AOC_STATUS_PANIC = 5,
+} AocStatus;
+
+// An array of `u32`s allocated by the library. Free it with `aoc_u32_buffer_free`.
+typedef struct AocU32Buffer {
+  uint32_t *data;
+  size_t len;
+} AocU32Buffer;
+
+// Solve part `part` of day `day`'s puzzle for the puzzle input `input`.
+// On success, `*answer_out` is set to the answer as a decimal string, which the caller must free
+// with `aoc_string_free`. Answers are strings because some of them don't fit in 64 bits.
+//
+// # Safety
+// `input` must be a NUL-terminated string, and `answer_out` must point to writable memory.
+enum AocStatus aoc_solve(uint32_t day, uint32_t part, const char *input, char **answer_out);
+
+// Decode a boarding pass seat code like `FBFBBFFRLR` into its row and column (day 5).
+//
+// # Safety
+// `seat_code` must be a NUL-terminated string, and `row_out` and `column_out` must point to writable memory.
+enum AocStatus aoc_day5_decode_seat(const char *seat_code,
+                                    uint32_t *row_out,
+                                    uint32_t *column_out);
+
+// Decode every boarding pass in the puzzle input into its seat ID (day 5).
+// On success, `*seat_ids_out` is set to a buffer of the seat IDs, which the caller must free with
+// `aoc_u32_buffer_free`.
+//
+// # Safety
+// `input` must be a NUL-terminated string, and `seat_ids_out` must point to writable memory.
+enum AocStatus aoc_day5_seat_ids(const char *input, struct AocU32Buffer *seat_ids_out);
+
+// Play the Elves' memory game (day 15) with the `len` starting numbers, and set `*number_out` to the
+// number spoken on turn `end_turn`, which must be after all of the starting numbers are spoken.
+//
+// # Safety
+// `starting_numbers` must point to `len` numbers, and `number_out` must point to writable memory.
+enum AocStatus aoc_day15_elf_game(const uint64_t *starting_numbers,
+                                  size_t len,
+                                  uint64_t end_turn,
+                                  uint64_t *number_out);
+
+// Evaluate a homework expression like `1 + (2 * 3)` (day 18). `operator_precedence` lists the
+// operators from highest to lowest precedence, e.g. `"+*"` for part 2.
+//
+// # Safety
+// `expression` and `operator_precedence` must be NUL-terminated strings, and `value_out` must
+// point to writable memory.
+enum AocStatus aoc_day18_evaluate_infix_expression(const char *expression,
+                                                   const char *operator_precedence,
+                                                   int64_t *value_out);
+
+// Describe why the last failed call on this thread failed. Empty after a call which succeeded.
+// The message belongs to the library, and is only valid until the next `aoc_` call on this thread.
+const char *aoc_last_error(void);
+
+// Free a string returned by the library. Does nothing if `s` is null.
+//
+// # Safety
+// `s` must be null, or a string returned by the library which has not been freed yet.
+void aoc_string_free(char *s);
+
+// Free a buffer returned by the library. Does nothing if its data is null.
+//
+// # Safety
+// `buffer` must have been returned by the library, and not freed yet.
+void aoc_u32_buffer_free(struct AocU32Buffer buffer);
+
+#endif  /* AOC_FFI_H */
@@ -0,0 +1,302 @@
+//! C ABI for the Advent of Code 2020 solvers, for calling them from C and C++.
+//! The C header `include/aoc_ffi.h` is generated from this file when the crate is built.
+//!
+//! # Ownership rules
+//!
+//! - Input strings and arrays are borrowed only for the duration of the call.
+//!   Input strings must be NUL-terminated UTF-8.
+//! - Strings returned through a `char **` out parameter are owned by the caller,
+//!   who must free each of them exactly once with `aoc_string_free`.
+//! - Buffers returned through an `AocU32Buffer *` out parameter are owned by the caller,
+//!   who must free each of them exactly once with `aoc_u32_buffer_free`.
+//! - Out parameters are only written when a function returns `AOC_STATUS_OK`.
+//! - The message returned by `aoc_last_error` is owned by the library. It is only valid until
+//!   the next call to an `aoc_` function on the same thread.
+//!
+//! No function unwinds a panic into the caller: a solver which panics returns `AOC_STATUS_PANIC`.
+
+use std::{cell::RefCell, ffi::{CStr, CString}, os::raw::c_char, panic, ptr, slice};
+use aoc_common::ParseError;
+use aoc_days::find_day;
+
+/// Result of every function which can fail. On failure, `aoc_last_error` describes the problem.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AocStatus {
+    Ok = 0,
+    /// A required pointer argument was null.
+    NullPointer = 1,
+    /// An input string was not valid UTF-8.
+    InvalidUtf8 = 2,
+    /// The puzzle input is malformed.
+    ParseError = 3,
+    /// An argument is out of range, e.g. a day with no solver.
+    InvalidArgument = 4,
+    /// The solver panicked, e.g. because the puzzle has no solution.
+    Panic = 5,
+}
+
+/// An array of `u32`s allocated by the library. Free it with `aoc_u32_buffer_free`.
+#[repr(C)]
+pub struct AocU32Buffer {
+    pub data: *mut u32,
+    pub len: usize,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+/// A failed call: the status to return, and a message for `aoc_last_error`.
+struct Failure {
+    status: AocStatus,
+    message: String,
+}
+
+impl Failure {
+    fn new(status: AocStatus, message: String) -> Failure {
+        Failure { status, message }
+    }
+}
+
+impl From<ParseError> for Failure {
+    fn from(error: ParseError) -> Failure {
+        Failure::new(AocStatus::ParseError, error.to_string())
+    }
+}
+
+/// Run the body of an `extern "C"` function: catch panics, and record the error message on failure.
+fn ffi_call<F: FnOnce() -> Result<(), Failure>>(body: F) -> AocStatus {
+    let (status, message) = match panic::catch_unwind(panic::AssertUnwindSafe(body)) {
+        Ok(Ok(())) => (AocStatus::Ok, String::new()),
+        Ok(Err(failure)) => (failure.status, failure.message),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            (AocStatus::Panic, format!("solver panicked: {}", message))
+        }
+    };
+    let message = CString::new(message.replace('\0', " ")).unwrap();
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = message);
+    status
+}
+
+/// Borrow a NUL-terminated UTF-8 string from C.
+///
+/// # Safety
+/// `s` must be null or point to a NUL-terminated string which outlives `'a`.
+unsafe fn borrow_str<'a>(s: *const c_char, name: &str) -> Result<&'a str, Failure> {
+    if s.is_null() {
+        return Err(Failure::new(AocStatus::NullPointer, format!("{} is null", name)));
+    }
+    CStr::from_ptr(s).to_str().map_err(|error| Failure::new(AocStatus::InvalidUtf8, format!("{} is not UTF-8: {}", name, error)))
+}
+
+/// Check that an out parameter is not null.
+fn check_out<T>(out: *mut T, name: &str) -> Result<(), Failure> {
+    if out.is_null() {
+        return Err(Failure::new(AocStatus::NullPointer, format!("{} is null", name)));
+    }
+    Ok(())
+}
+
+/// Solve part `part` of day `day`'s puzzle for the puzzle input `input`.
+/// On success, `*answer_out` is set to the answer as a decimal string, which the caller must free
+/// with `aoc_string_free`. Answers are strings because some of them don't fit in 64 bits.
+///
+/// # Safety
+/// `input` must be a NUL-terminated string, and `answer_out` must point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(day: u32, part: u32, input: *const c_char, answer_out: *mut *mut c_char) -> AocStatus {
+    ffi_call(|| {
+        let input = borrow_str(input, "input")?;
+        check_out(answer_out, "answer_out")?;
+        let solve = find_day(day).and_then(|day| day.part(part as usize)).ok_or_else(|| {
+            Failure::new(AocStatus::InvalidArgument, format!("there is no solver for day {} part {}", day, part))
+        })?;
+        let answer = solve(input)?.answer;
+        *answer_out = CString::new(answer).unwrap().into_raw();
+        Ok(())
+    })
+}
+
+/// Decode a boarding pass seat code like `FBFBBFFRLR` into its row and column (day 5).
+///
+/// # Safety
+/// `seat_code` must be a NUL-terminated string, and `row_out` and `column_out` must point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_day5_decode_seat(seat_code: *const c_char, row_out: *mut u32, column_out: *mut u32) -> AocStatus {
+    ffi_call(|| {
+        let seat_code = borrow_str(seat_code, "seat_code")?;
+        check_out(row_out, "row_out")?;
+        check_out(column_out, "column_out")?;
+        let (row, column) = day5::decode_seat(seat_code).ok_or_else(|| {
+            Failure::new(AocStatus::InvalidArgument, format!("invalid seat code `{}`", seat_code))
+        })?;
+        *row_out = row;
+        *column_out = column;
+        Ok(())
+    })
+}
+
+/// Decode every boarding pass in the puzzle input into its seat ID (day 5).
+/// On success, `*seat_ids_out` is set to a buffer of the seat IDs, which the caller must free with
+/// `aoc_u32_buffer_free`.
+///
+/// # Safety
+/// `input` must be a NUL-terminated string, and `seat_ids_out` must point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_day5_seat_ids(input: *const c_char, seat_ids_out: *mut AocU32Buffer) -> AocStatus {
+    ffi_call(|| {
+        let input = borrow_str(input, "input")?;
+        check_out(seat_ids_out, "seat_ids_out")?;
+        let seat_ids = day5::parse_seat_ids(input)?.into_boxed_slice();
+        let len = seat_ids.len();
+        *seat_ids_out = AocU32Buffer { data: Box::into_raw(seat_ids) as *mut u32, len };
+        Ok(())
+    })
+}
+
+/// Play the Elves' memory game (day 15) with the `len` starting numbers, and set `*number_out` to the
+/// number spoken on turn `end_turn`, which must be after all of the starting numbers are spoken.
+///
+/// # Safety
+/// `starting_numbers` must point to `len` numbers, and `number_out` must point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_day15_elf_game(starting_numbers: *const u64, len: usize, end_turn: u64,
+        number_out: *mut u64) -> AocStatus {
+    ffi_call(|| {
+        if starting_numbers.is_null() {
+            return Err(Failure::new(AocStatus::NullPointer, "starting_numbers is null".to_string()));
+        }
+        check_out(number_out, "number_out")?;
+        if len == 0 || end_turn <= len as u64 {
+            return Err(Failure::new(AocStatus::InvalidArgument,
+                "end_turn must be after all of the starting numbers are spoken".to_string()));
+        }
+        let starting_numbers = slice::from_raw_parts(starting_numbers, len)
+            .iter()
+            .map(|&number| number as usize)
+            .collect::<Vec<usize>>();
+        *number_out = day15::elf_game(&starting_numbers, end_turn as usize) as u64;
+        Ok(())
+    })
+}
+
+/// Evaluate a homework expression like `1 + (2 * 3)` (day 18). `operator_precedence` lists the
+/// operators from highest to lowest precedence, e.g. `"+*"` for part 2.
+///
+/// # Safety
+/// `expression` and `operator_precedence` must be NUL-terminated strings, and `value_out` must
+/// point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_day18_evaluate_infix_expression(expression: *const c_char,
+        operator_precedence: *const c_char, value_out: *mut i64) -> AocStatus {
+    ffi_call(|| {
+        let expression = borrow_str(expression, "expression")?;
+        let operator_precedence = borrow_str(operator_precedence, "operator_precedence")?;
+        check_out(value_out, "value_out")?;
+        day18::check_expression(expression, expression)?;
+        let operators = operator_precedence.chars().collect::<Vec<char>>();
+        if !(operators.contains(&'+') && operators.contains(&'*')) {
+            return Err(Failure::new(AocStatus::InvalidArgument,
+                format!("operator_precedence must contain `+` and `*`, found `{}`", operator_precedence)));
+        }
+        *value_out = day18::evaluate_infix_expression(expression, &operators);
+        Ok(())
+    })
+}
+
+/// Describe why the last failed call on this thread failed. Empty after a call which succeeded.
+/// The message belongs to the library, and is only valid until the next `aoc_` call on this thread.
+#[no_mangle]
+pub extern "C" fn aoc_last_error() -> *const c_char {
+    LAST_ERROR.with(|last_error| last_error.borrow().as_ptr())
+}
+
+/// Free a string returned by the library. Does nothing if `s` is null.
+///
+/// # Safety
+/// `s` must be null, or a string returned by the library which has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Free a buffer returned by the library. Does nothing if its data is null.
+///
+/// # Safety
+/// `buffer` must have been returned by the library, and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_u32_buffer_free(buffer: AocU32Buffer) {
+    if !buffer.data.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(buffer.data, buffer.len)));
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_error() -> String {
+        unsafe { CStr::from_ptr(aoc_last_error()) }.to_str().unwrap().to_string()
+    }
+
+    fn solve(day: u32, part: u32, input: &str) -> Result<String, (AocStatus, String)> {
+        let input = CString::new(input).unwrap();
+        let mut answer: *mut c_char = ptr::null_mut();
+        let status = unsafe { aoc_solve(day, part, input.as_ptr(), &mut answer) };
+        if status != AocStatus::Ok {
+            return Err((status, last_error()));
+        }
+        let answer_string = unsafe { CStr::from_ptr(answer) }.to_str().unwrap().to_string();
+        unsafe { aoc_string_free(answer) };
+        Ok(answer_string)
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(solve(13, 2, "939\n7,13,x,x,59,x,31,19"), Ok("1068781".to_string()));
+        assert_eq!(last_error(), "");
+        assert_eq!(solve(12, 1, "F10\nQ3"), Err((AocStatus::ParseError,
+            "line 2, column 1: expected an instruction like `F10`, found `Q3`".to_string())));
+        assert_eq!(solve(4, 1, "").unwrap_err().0, AocStatus::InvalidArgument);
+        assert_eq!(solve(1, 1, "1\n2\n3").unwrap_err().0, AocStatus::Panic);
+    }
+
+    #[test]
+    fn test_null_pointers() {
+        let mut answer: *mut c_char = ptr::null_mut();
+        assert_eq!(unsafe { aoc_solve(1, 1, ptr::null(), &mut answer) }, AocStatus::NullPointer);
+        assert_eq!(last_error(), "input is null");
+        let input = CString::new("1721\n299").unwrap();
+        assert_eq!(unsafe { aoc_solve(1, 1, input.as_ptr(), ptr::null_mut()) }, AocStatus::NullPointer);
+    }
+
+    #[test]
+    fn test_seat_ids() {
+        let input = CString::new("FBFBBFFRLR\nBFFFBBFRRR\n").unwrap();
+        let mut buffer = AocU32Buffer { data: ptr::null_mut(), len: 0 };
+        assert_eq!(unsafe { aoc_day5_seat_ids(input.as_ptr(), &mut buffer) }, AocStatus::Ok);
+        assert_eq!(unsafe { slice::from_raw_parts(buffer.data, buffer.len) }, &[357, 567]);
+        unsafe { aoc_u32_buffer_free(buffer) };
+    }
+
+    #[test]
+    fn test_evaluate_infix_expression() {
+        let expression = CString::new("1 + (2 * 3) + (4 * (5 + 6))").unwrap();
+        let precedence = CString::new("+*").unwrap();
+        let mut value = 0;
+        let status = unsafe { aoc_day18_evaluate_infix_expression(expression.as_ptr(), precedence.as_ptr(), &mut value) };
+        assert_eq!((status, value), (AocStatus::Ok, 51));
+        let bad_expression = CString::new("1 + (2").unwrap();
+        let status = unsafe { aoc_day18_evaluate_infix_expression(bad_expression.as_ptr(), precedence.as_ptr(), &mut value) };
+        assert_eq!(status, AocStatus::ParseError);
+    }
+}
@@ -0,0 +1,76 @@
+/* Calls each function in aoc_ffi.h, and checks the results. Exits with status 0 if every check passes. */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc_ffi.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                                         \
+    do {                                                                         \
+        if (!(condition)) {                                                      \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #condition); \
+            failures++;                                                          \
+        }                                                                        \
+    } while (0)
+
+static void test_solve(void) {
+    char *answer = NULL;
+    CHECK(aoc_solve(13, 2, "939\n7,13,x,x,59,x,31,19\n", &answer) == AOC_STATUS_OK);
+    CHECK(answer != NULL && strcmp(answer, "1068781") == 0);
+    aoc_string_free(answer);
+
+    answer = NULL;
+    CHECK(aoc_solve(12, 1, "F10\nQ3\n", &answer) == AOC_STATUS_PARSE_ERROR);
+    CHECK(answer == NULL);
+    CHECK(strcmp(aoc_last_error(), "line 2, column 1: expected an instruction like `F10`, found `Q3`") == 0);
+
+    CHECK(aoc_solve(4, 1, "", &answer) == AOC_STATUS_INVALID_ARGUMENT);
+    CHECK(aoc_solve(1, 1, NULL, &answer) == AOC_STATUS_NULL_POINTER);
+    /* No pair of these entries sums to 2020, so the solver panics. */
+    CHECK(aoc_solve(1, 1, "1\n2\n", &answer) == AOC_STATUS_PANIC);
+}
+
+static void test_day5(void) {
+    uint32_t row = 0, column = 0;
+    CHECK(aoc_day5_decode_seat("FBFBBFFRLR", &row, &column) == AOC_STATUS_OK);
+    CHECK(row == 44 && column == 5);
+    CHECK(aoc_day5_decode_seat("FBFBBFFRLX", &row, &column) == AOC_STATUS_INVALID_ARGUMENT);
+
+    AocU32Buffer seat_ids = { NULL, 0 };
+    CHECK(aoc_day5_seat_ids("BFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL\n", &seat_ids) == AOC_STATUS_OK);
+    CHECK(seat_ids.len == 3);
+    CHECK(seat_ids.data[0] == 567 && seat_ids.data[1] == 119 && seat_ids.data[2] == 820);
+    aoc_u32_buffer_free(seat_ids);
+}
+
+static void test_day15(void) {
+    const uint64_t starting_numbers[] = { 0, 3, 6 };
+    uint64_t number = 0;
+    CHECK(aoc_day15_elf_game(starting_numbers, 3, 2020, &number) == AOC_STATUS_OK);
+    CHECK(number == 436);
+    CHECK(aoc_day15_elf_game(starting_numbers, 3, 2, &number) == AOC_STATUS_INVALID_ARGUMENT);
+}
+
+static void test_day18(void) {
+    int64_t value = 0;
+    CHECK(aoc_day18_evaluate_infix_expression("2 * 3 + (4 * 5)", "+*", &value) == AOC_STATUS_OK);
+    CHECK(value == 46);
+    CHECK(aoc_day18_evaluate_infix_expression("2 * 3 + (4 * 5)", "*+", &value) == AOC_STATUS_OK);
+    CHECK(value == 6 + 20);
+    CHECK(aoc_day18_evaluate_infix_expression("2 * (3", "+*", &value) == AOC_STATUS_PARSE_ERROR);
+}
+
+int main(void) {
+    test_solve();
+    test_day5();
+    test_day15();
+    test_day18();
+    if (failures > 0) {
+        fprintf(stderr, "%d checks failed\n", failures);
+        return 1;
+    }
+    printf("all checks passed\n");
+    return 0;
+}
@@ -0,0 +1,30 @@
+//! Compile `tests/c/test_aoc_ffi.c` against the `cdylib` and the generated header, and run it.
+
+use std::{env, path::PathBuf, process::Command};
+
+#[test]
+fn test_c_program() {
+    let crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // This test runs from `target/<profile>/deps`, and the library is in `target/<profile>`.
+    let library_dir = env::current_exe().unwrap().parent().unwrap().parent().unwrap().to_path_buf();
+    let program = library_dir.join("test_aoc_ffi");
+
+    let compile = Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg(crate_dir.join("tests/c/test_aoc_ffi.c"))
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(crate_dir.join("include"))
+        .arg("-L")
+        .arg(&library_dir)
+        .arg("-laoc_ffi")
+        .arg(format!("-Wl,-rpath,{}", library_dir.display()))
+        .arg("-o")
+        .arg(&program)
+        .status()
+        .expect("could not run the C compiler");
+    assert!(compile.success(), "could not compile the C test program");
+
+    let output = Command::new(&program).output().unwrap();
+    assert!(output.status.success(), "C test program failed:\n{}", String::from_utf8_lossy(&output.stderr));
+}
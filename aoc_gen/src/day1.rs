@@ -0,0 +1,68 @@
+//! Day 1: expense reports with exactly one pair and one triplet of entries which sum to 2020.
+
+use rand::{seq::SliceRandom, Rng as _};
+use crate::{parse_param, unknown_param, Generated, Rng};
+
+const TARGET_SUM: u32 = 2020;
+
+pub const SIZE_PARAM: &str = "entries";
+
+#[derive(Clone, Debug)]
+pub struct Params {
+    /// The number of entries in the report.
+    pub entries: usize,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params { entries: 200 }
+    }
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "entries" => self.entries = parse_param(name, value, 5..=10_000_000)?,
+            _ => return Err(unknown_param(name, &["entries"])),
+        }
+        Ok(())
+    }
+}
+
+/// Count the pairs and triplets of `entries` which sum to `TARGET_SUM`.
+fn count_sums(entries: &[u32]) -> (usize, usize) {
+    let mut pairs = 0;
+    let mut triplets = 0;
+    for (i, &x) in entries.iter().enumerate() {
+        for (j, &y) in entries.iter().enumerate().skip(i + 1) {
+            pairs += (x + y == TARGET_SUM) as usize;
+            triplets += entries[j + 1..].iter().filter(|&&z| x + y + z == TARGET_SUM).count();
+        }
+    }
+    (pairs, triplets)
+}
+
+/// Plant a pair and a triplet which sum to 2020, and fill the rest of the report with entries
+/// larger than 2020, which cannot be part of any sum.
+pub fn generate(params: &Params, rng: &mut Rng) -> Result<Generated, String> {
+    let planted = loop {
+        let x = rng.gen_range(1011..TARGET_SUM);
+        let a = rng.gen_range(1..TARGET_SUM / 3);
+        let b = rng.gen_range(1..(TARGET_SUM - a) / 2);
+        let planted = [x, TARGET_SUM - x, a, b, TARGET_SUM - a - b];
+        // The planted entries must not make any other pair or triplet with each other.
+        if count_sums(&planted) == (1, 1) {
+            break planted;
+        }
+    };
+
+    let mut entries = planted.to_vec();
+    while entries.len() < params.entries {
+        entries.push(rng.gen_range(TARGET_SUM + 1..10_000));
+    }
+    entries.shuffle(rng);
+
+    let text = entries.iter().map(|entry| format!("{}\n", entry)).collect::<String>();
+    let [x, y, a, b, c] = planted;
+    Ok(Generated::new(text, Some(x * y), Some(a * b * c)))
+}
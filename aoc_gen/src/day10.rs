@@ -0,0 +1,68 @@
+//! Day 10: adapters which can all be chained together, with runs of 1-jolt differences
+//! separated by 3-jolt differences.
+
+use rand::{seq::SliceRandom, Rng as _};
+use crate::{parse_param, unknown_param, Generated, Rng};
+
+/// `ARRANGEMENTS[k]` is the number of ways to arrange a run of `k` 1-jolt differences, which
+/// has a 3-jolt difference on either side: the adapters at the ends of the run are needed,
+/// and no two adapters in the chain can be more than 3 jolts apart.
+const ARRANGEMENTS: [u64; 5] = [1, 1, 2, 4, 7];
+
+pub const SIZE_PARAM: &str = "adapters";
+
+#[derive(Clone, Debug)]
+pub struct Params {
+    /// The number of adapters. The part 1 answer must fit in a `u32`, which limits this to
+    /// 100000.
+    pub adapters: usize,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params { adapters: 100 }
+    }
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "adapters" => self.adapters = parse_param(name, value, 1..=100_000)?,
+            _ => return Err(unknown_param(name, &["adapters"])),
+        }
+        Ok(())
+    }
+}
+
+pub fn generate(params: &Params, rng: &mut Rng) -> Result<Generated, String> {
+    // The differences between each adapter and the one below it in the chain, followed by the
+    // 3-jolt difference to the device's built-in adapter.
+    let mut differences = Vec::<u32>::new();
+    let mut arrangements: u64 = 1;
+    while differences.len() <= params.adapters {
+        let remaining = params.adapters + 1 - differences.len();
+        let mut run = rng.gen_range(0..ARRANGEMENTS.len()).min(remaining - 1);
+        // Once the number of arrangements is near the limit of a `u64`, only use runs which
+        // have one arrangement.
+        match arrangements.checked_mul(ARRANGEMENTS[run]) {
+            Some(product) => arrangements = product,
+            None => run = run.min(1),
+        }
+        differences.extend(std::iter::repeat_n(1, run));
+        differences.push(3);
+    }
+
+    let ones = differences.iter().filter(|&&d| d == 1).count() as u32;
+    let threes = differences.iter().filter(|&&d| d == 3).count() as u32;
+    let mut adapters = differences[..params.adapters]
+        .iter()
+        .scan(0, |joltage, difference| {
+            *joltage += difference;
+            Some(*joltage)
+        })
+        .collect::<Vec<u32>>();
+    adapters.shuffle(rng);
+
+    let text = adapters.iter().map(|adapter| format!("{}\n", adapter)).collect::<String>();
+    Ok(Generated::new(text, Some(ones * threes), Some(arrangements)))
+}
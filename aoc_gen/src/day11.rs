@@ -0,0 +1,45 @@
+//! Day 11: seating areas of empty seats (`L`) and floor (`.`).
+//!
+//! The answers depend on the whole simulation, so the generator does not plant them.
+
+use rand::Rng as _;
+use crate::{parse_param, unknown_param, Generated, Rng};
+
+pub const SIZE_PARAM: &str = "rows";
+
+#[derive(Clone, Debug)]
+pub struct Params {
+    pub rows: usize,
+    pub columns: usize,
+    /// The chance that each position is floor rather than a seat.
+    pub floor_density: f64,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params { rows: 90, columns: 98, floor_density: 0.15 }
+    }
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "rows" => self.rows = parse_param(name, value, 1..=10_000)?,
+            "columns" => self.columns = parse_param(name, value, 1..=10_000)?,
+            "floor_density" => self.floor_density = parse_param(name, value, 0.0..=1.0)?,
+            _ => return Err(unknown_param(name, &["rows", "columns", "floor_density"])),
+        }
+        Ok(())
+    }
+}
+
+pub fn generate(params: &Params, rng: &mut Rng) -> Result<Generated, String> {
+    let mut text = String::with_capacity(params.rows * (params.columns + 1));
+    for _ in 0..params.rows {
+        for _ in 0..params.columns {
+            text.push(if rng.gen_bool(params.floor_density) { '.' } else { 'L' });
+        }
+        text.push('\n');
+    }
+    Ok(Generated::new(text, None::<u32>, None::<u32>))
+}
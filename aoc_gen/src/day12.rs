@@ -0,0 +1,103 @@
+//! Day 12: navigation instructions, which keep the ship and waypoint within reach of the
+//! solvers' `i32` coordinates.
+
+use rand::{seq::SliceRandom, Rng as _};
+use crate::{parse_param, unknown_param, Generated, Rng};
+
+/// The largest coordinate which the ship or waypoint may reach in either part. The Manhattan
+/// distance is the sum of two coordinates, and must also fit in an `i32`.
+const MAX_COORDINATE: i64 = 1 << 29;
+
+pub const SIZE_PARAM: &str = "instructions";
+
+#[derive(Clone, Debug)]
+pub struct Params {
+    pub instructions: usize,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params { instructions: 780 }
+    }
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "instructions" => self.instructions = parse_param(name, value, 1..=10_000_000)?,
+            _ => return Err(unknown_param(name, &["instructions"])),
+        }
+        Ok(())
+    }
+}
+
+/// The ship and waypoint in both parts, following the same instructions as the solvers.
+#[derive(Clone, Copy)]
+struct Navigation {
+    /// The part 1 ship position, and its heading as a unit vector.
+    ship_1: (i64, i64),
+    heading: (i64, i64),
+    /// The part 2 ship position, and the waypoint relative to it.
+    ship_2: (i64, i64),
+    waypoint: (i64, i64),
+}
+
+impl Navigation {
+    fn follow(mut self, action: char, value: i64) -> Navigation {
+        let rotate = |(x, y): (i64, i64), degrees: i64| {
+            (0..degrees.rem_euclid(360) / 90).fold((x, y), |(x, y), _| (-y, x))
+        };
+        let unit = match action {
+            'N' => (0, 1),
+            'S' => (0, -1),
+            'E' => (1, 0),
+            'W' => (-1, 0),
+            _ => (0, 0),
+        };
+        self.ship_1 = (self.ship_1.0 + unit.0 * value, self.ship_1.1 + unit.1 * value);
+        self.waypoint = (self.waypoint.0 + unit.0 * value, self.waypoint.1 + unit.1 * value);
+        match action {
+            'L' | 'R' => {
+                let degrees = if action == 'L' { value } else { -value };
+                self.heading = rotate(self.heading, degrees);
+                self.waypoint = rotate(self.waypoint, degrees);
+            }
+            'F' => {
+                self.ship_1 = (self.ship_1.0 + self.heading.0 * value, self.ship_1.1 + self.heading.1 * value);
+                self.ship_2 = (self.ship_2.0 + self.waypoint.0 * value, self.ship_2.1 + self.waypoint.1 * value);
+            }
+            _ => {}
+        }
+        self
+    }
+
+    fn in_range(&self) -> bool {
+        [self.ship_1, self.ship_2, self.waypoint]
+            .iter()
+            .all(|(x, y)| x.abs() <= MAX_COORDINATE && y.abs() <= MAX_COORDINATE)
+    }
+}
+
+pub fn generate(params: &Params, rng: &mut Rng) -> Result<Generated, String> {
+    let mut navigation = Navigation { ship_1: (0, 0), heading: (1, 0), ship_2: (0, 0), waypoint: (10, 1) };
+    let mut text = String::new();
+    for _ in 0..params.instructions {
+        // Turns never move anything further away, so there is always an instruction which
+        // keeps the coordinates in range.
+        let (action, value) = loop {
+            let action = *b"NSEWLRFF".choose(rng).unwrap() as char;
+            let value = match action {
+                'L' | 'R' => 90 * rng.gen_range(1..=3),
+                'F' => rng.gen_range(1..=100),
+                _ => rng.gen_range(1..=5),
+            };
+            if navigation.follow(action, value).in_range() {
+                break (action, value);
+            }
+        };
+        navigation = navigation.follow(action, value);
+        text.push_str(&format!("{}{}\n", action, value));
+    }
+    let manhattan = |(x, y): (i64, i64)| x.abs() + y.abs();
+    Ok(Generated::new(text, Some(manhattan(navigation.ship_1)), Some(manhattan(navigation.ship_2))))
+}
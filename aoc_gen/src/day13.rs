@@ -0,0 +1,102 @@
+//! Day 13: bus schedules with a planted earliest timestamp for part 2.
+//!
+//! The bus IDs are distinct primes, so the buses' departures always line up at some timestamp.
+//! Each bus is placed in the schedule at an offset which makes it depart at the planted
+//! timestamp plus that offset.
+
+use rand::{seq::SliceRandom, Rng as _};
+use crate::{parse_param, unknown_param, Generated, Rng};
+
+/// The largest product of the bus IDs, which is the period of the part 2 timestamps. This keeps
+/// the solver's intermediate values well within an `i128`.
+const MAX_PERIOD: u64 = 1 << 53;
+
+/// The most buses in service.
+const MAX_BUSES: usize = 9;
+
+pub const SIZE_PARAM: &str = "length";
+
+#[derive(Clone, Debug)]
+pub struct Params {
+    /// The number of entries in the schedule, including the out of service buses.
+    /// Every bus ID is smaller than this, and the part 1 answer, which is less than the square
+    /// of a bus ID, must fit in a `u32`.
+    pub length: usize,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params { length: 90 }
+    }
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "length" => self.length = parse_param(name, value, 4..=1 << 16)?,
+            _ => return Err(unknown_param(name, &["length"])),
+        }
+        Ok(())
+    }
+}
+
+fn primes_below(limit: usize) -> Vec<u64> {
+    let mut is_prime = vec![true; limit];
+    let mut primes = Vec::new();
+    for n in 2..limit {
+        if is_prime[n] {
+            primes.push(n as u64);
+            for multiple in (n * n..limit).step_by(n) {
+                is_prime[multiple] = false;
+            }
+        }
+    }
+    primes
+}
+
+pub fn generate(params: &Params, rng: &mut Rng) -> Result<Generated, String> {
+    let mut primes = primes_below(params.length);
+    primes.shuffle(rng);
+    let mut buses = Vec::<u64>::new();
+    let mut period: u64 = 1;
+    for prime in primes {
+        match period.checked_mul(prime) {
+            Some(product) if buses.len() < MAX_BUSES && product <= MAX_PERIOD => {
+                buses.push(prime);
+                period = product;
+            }
+            _ => {}
+        }
+    }
+
+    // Plant the timestamp, and place each bus at the first free offset which suits it.
+    let (timestamp, schedule) = 'plant: loop {
+        let timestamp = rng.gen_range(1..period.max(2));
+        let mut schedule = vec![None; params.length];
+        for &bus in &buses {
+            let first_offset = ((bus - timestamp % bus) % bus) as usize;
+            match (first_offset..params.length).step_by(bus as usize).find(|&offset| schedule[offset].is_none()) {
+                Some(offset) => schedule[offset] = Some(bus),
+                None => continue 'plant,
+            }
+        }
+        break (timestamp, schedule);
+    };
+
+    // Choose a ready time at which exactly one bus has the shortest wait.
+    let (ready_time, part_1) = loop {
+        let ready_time = rng.gen_range(1000..=1_000_000);
+        let mut waits = buses.iter().map(|&bus| (bus - ready_time % bus, bus)).collect::<Vec<(u64, u64)>>();
+        waits.sort_unstable();
+        if waits.len() == 1 || waits[0].0 < waits[1].0 {
+            break (ready_time, waits[0].0 * waits[0].1);
+        }
+    };
+
+    let schedule = schedule
+        .iter()
+        .map(|bus| bus.map_or("x".to_string(), |bus| bus.to_string()))
+        .collect::<Vec<String>>();
+    let text = format!("{}\n{}\n", ready_time, schedule.join(","));
+    Ok(Generated::new(text, Some(part_1), Some(timestamp)))
+}
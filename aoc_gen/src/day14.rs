@@ -0,0 +1,82 @@
+//! Day 14: initialization programs whose writes never overlap, in either part.
+//!
+//! Every mask leaves the top `TAG_BITS` bits of addresses alone, and each write has its own tag
+//! in those bits of its address. So no write overwrites another, and the answers are sums over
+//! the writes.
+
+use rand::{seq::index::sample, Rng as _};
+use crate::{parse_param, unknown_param, Generated, Rng};
+
+const WORD_BITS: usize = 36;
+
+/// The number of address bits which tag each write.
+const TAG_BITS: usize = 16;
+
+/// The most floating bits in each mask.
+const MAX_FLOATING_BITS: usize = 9;
+
+pub const SIZE_PARAM: &str = "writes";
+
+#[derive(Clone, Debug)]
+pub struct Params {
+    /// The number of memory writes, which is limited by the number of tags.
+    pub writes: usize,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params { writes: 450 }
+    }
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "writes" => self.writes = parse_param(name, value, 1..=1 << TAG_BITS)?,
+            _ => return Err(unknown_param(name, &["writes"])),
+        }
+        Ok(())
+    }
+}
+
+/// A random mask, with `0` in the top `TAG_BITS` bits.
+fn random_mask(rng: &mut Rng) -> String {
+    let low_bits = WORD_BITS - TAG_BITS;
+    let floating_count = rng.gen_range(0..=MAX_FLOATING_BITS);
+    let floating = sample(rng, low_bits, floating_count).into_vec();
+    let mut mask = "0".repeat(TAG_BITS);
+    for bit in 0..low_bits {
+        mask.push(if floating.contains(&bit) { 'X' } else if rng.gen_bool(0.5) { '1' } else { '0' });
+    }
+    mask
+}
+
+pub fn generate(params: &Params, rng: &mut Rng) -> Result<Generated, String> {
+    let tag_shift = WORD_BITS - TAG_BITS;
+    let tags = sample(rng, 1 << TAG_BITS, params.writes).into_vec();
+    let mut text = String::new();
+    let mut sum_1: u64 = 0;
+    let mut sum_2: u64 = 0;
+    let mut mask = String::new();
+    for (i, tag) in tags.into_iter().enumerate() {
+        if i == 0 || rng.gen_range(0..4) == 0 {
+            mask = random_mask(rng);
+            text.push_str(&format!("mask = {}\n", mask));
+        }
+        let address = (tag as u64) << tag_shift | rng.gen_range(0..1 << tag_shift);
+        let value = rng.gen_range(1..1 << tag_shift);
+        text.push_str(&format!("mem[{}] = {}\n", address, value));
+
+        let mut masked_value = value;
+        for (bit, c) in mask.chars().rev().enumerate() {
+            match c {
+                '0' => masked_value &= !(1 << bit),
+                '1' => masked_value |= 1 << bit,
+                _ => {}
+            }
+        }
+        sum_1 += masked_value;
+        sum_2 += value << mask.matches('X').count();
+    }
+    Ok(Generated::new(text, Some(sum_1), Some(sum_2)))
+}
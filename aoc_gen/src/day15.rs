@@ -0,0 +1,44 @@
+//! Day 15: distinct starting numbers for the memory game.
+//!
+//! The answers depend on the whole game, so the generator does not plant them.
+
+use rand::seq::index::sample;
+use crate::{parse_param, unknown_param, Generated, Rng};
+
+pub const SIZE_PARAM: &str = "numbers";
+
+#[derive(Clone, Debug)]
+pub struct Params {
+    /// The number of starting numbers.
+    pub numbers: usize,
+    /// Every starting number is less than this.
+    pub max_number: usize,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params { numbers: 6, max_number: 20 }
+    }
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "numbers" => self.numbers = parse_param(name, value, 1..=2020)?,
+            "max_number" => self.max_number = parse_param(name, value, 1..=10_000_000)?,
+            _ => return Err(unknown_param(name, &["numbers", "max_number"])),
+        }
+        Ok(())
+    }
+}
+
+pub fn generate(params: &Params, rng: &mut Rng) -> Result<Generated, String> {
+    if params.numbers > params.max_number {
+        return Err(format!("{} distinct starting numbers do not fit below {}", params.numbers, params.max_number));
+    }
+    let numbers = sample(rng, params.max_number, params.numbers)
+        .into_iter()
+        .map(|number| number.to_string())
+        .collect::<Vec<String>>();
+    Ok(Generated::new(format!("{}\n", numbers.join(",")), None::<usize>, None::<usize>))
+}
@@ -0,0 +1,130 @@
+//! Day 16: ticket notes in which exactly one assignment of fields to positions fits the
+//! valid nearby tickets.
+//!
+//! Field `k` is valid for values from 1 to `100 * (k + 1) - 1`, maybe with a gap below
+//! `100 * k`, and the position of field `k` holds values from `100 * k` to `100 * k + 99`. So a
+//! position's values rule out every field before its own, and the solver can work out the
+//! fields from the last to the first. Invalid values are above every field's ranges.
+
+use std::collections::HashSet;
+use rand::{seq::SliceRandom, Rng as _};
+use crate::{parse_param, random_word, unknown_param, Generated, Rng};
+
+const DEPARTURE_FIELDS: [&str; 6] = [
+    "departure location", "departure station", "departure platform",
+    "departure track", "departure date", "departure time",
+];
+
+const OTHER_FIELDS: [&str; 14] = [
+    "arrival location", "arrival station", "arrival platform", "arrival track",
+    "class", "duration", "price", "route", "row", "seat", "train", "type", "wagon", "zone",
+];
+
+pub const SIZE_PARAM: &str = "tickets";
+
+#[derive(Clone, Debug)]
+pub struct Params {
+    /// The number of nearby tickets.
+    pub tickets: usize,
+    /// The number of fields, and values on each ticket. The part 1 answer must fit in a `u32`,
+    /// which limits both this and the number of tickets.
+    pub fields: usize,
+    /// The chance that each nearby ticket has an invalid value.
+    pub invalid_chance: f64,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params { tickets: 240, fields: 20, invalid_chance: 0.25 }
+    }
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "tickets" => self.tickets = parse_param(name, value, 1..=100_000)?,
+            "fields" => self.fields = parse_param(name, value, 1..=100)?,
+            "invalid_chance" => self.invalid_chance = parse_param(name, value, 0.0..=1.0)?,
+            _ => return Err(unknown_param(name, &["tickets", "fields", "invalid_chance"])),
+        }
+        Ok(())
+    }
+}
+
+/// Names for `count` fields, with the departure fields first.
+fn field_names(count: usize, rng: &mut Rng) -> Vec<String> {
+    let mut names = DEPARTURE_FIELDS
+        .iter()
+        .chain(OTHER_FIELDS.iter())
+        .take(count)
+        .map(|name| name.to_string())
+        .collect::<Vec<String>>();
+    let mut used = names.iter().cloned().collect::<HashSet<String>>();
+    while names.len() < count {
+        let name = format!("{} {}", random_word(rng, 5), random_word(rng, 5));
+        if !name.starts_with("departure") && used.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+pub fn generate(params: &Params, rng: &mut Rng) -> Result<Generated, String> {
+    let names = field_names(params.fields, rng);
+    let mut rules = Vec::<String>::new();
+    for (k, name) in names.iter().enumerate() {
+        let high = 100 * (k as u32 + 1) - 1;
+        // The gap is from `gap_start` to `gap_end - 1`, and may be empty. The first field has
+        // no values to spare for a gap.
+        let (gap_start, gap_end) = if k == 0 {
+            let split = rng.gen_range(2..=high);
+            (split, split)
+        } else {
+            let gap_start = rng.gen_range(2..=100 * k as u32);
+            (gap_start, rng.gen_range(gap_start..=100 * k as u32))
+        };
+        rules.push(format!("{}: 1-{} or {}-{}\n", name, gap_start - 1, gap_end, high));
+    }
+
+    // `positions[k]` is the position of field `k` on the tickets.
+    let mut positions = (0..params.fields).collect::<Vec<usize>>();
+    positions.shuffle(rng);
+    let random_ticket = |rng: &mut Rng| {
+        let mut ticket = vec![0; params.fields];
+        for (k, &position) in positions.iter().enumerate() {
+            ticket[position] = rng.gen_range((100 * k as u32).max(1)..=100 * k as u32 + 99);
+        }
+        ticket
+    };
+
+    let your_ticket = random_ticket(rng);
+    let departure_product = (0..DEPARTURE_FIELDS.len().min(params.fields))
+        .map(|k| your_ticket[positions[k]] as u64)
+        .product::<u64>();
+
+    let invalid_above = 100 * params.fields as u32;
+    let mut error_rate = 0;
+    let mut nearby_tickets = Vec::<Vec<u32>>::new();
+    for i in 0..params.tickets {
+        let mut ticket = random_ticket(rng);
+        // The first ticket is valid, so that every position has a valid value.
+        if i > 0 && rng.gen_bool(params.invalid_chance) {
+            let value = rng.gen_range(invalid_above..invalid_above + 900);
+            error_rate += value;
+            ticket[rng.gen_range(0..params.fields)] = value;
+        }
+        nearby_tickets.push(ticket);
+    }
+    nearby_tickets.shuffle(rng);
+
+    let format_ticket = |ticket: &Vec<u32>| {
+        ticket.iter().map(|value| value.to_string()).collect::<Vec<String>>().join(",") + "\n"
+    };
+    let text = format!(
+        "{}\nyour ticket:\n{}\nnearby tickets:\n{}",
+        rules.concat(),
+        format_ticket(&your_ticket),
+        nearby_tickets.iter().map(format_ticket).collect::<String>(),
+    );
+    Ok(Generated::new(text, Some(error_rate), Some(departure_product)))
+}
@@ -0,0 +1,44 @@
+//! Day 17: initial slices of active (`#`) and inactive (`.`) cubes.
+//!
+//! The answers depend on the whole simulation, so the generator does not plant them.
+
+use rand::Rng as _;
+use crate::{parse_param, unknown_param, Generated, Rng};
+
+pub const SIZE_PARAM: &str = "side";
+
+#[derive(Clone, Debug)]
+pub struct Params {
+    /// The number of rows and columns of the square slice.
+    pub side: usize,
+    /// The chance that each cube is active.
+    pub active_density: f64,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params { side: 8, active_density: 0.5 }
+    }
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "side" => self.side = parse_param(name, value, 1..=1000)?,
+            "active_density" => self.active_density = parse_param(name, value, 0.0..=1.0)?,
+            _ => return Err(unknown_param(name, &["side", "active_density"])),
+        }
+        Ok(())
+    }
+}
+
+pub fn generate(params: &Params, rng: &mut Rng) -> Result<Generated, String> {
+    let mut text = String::with_capacity(params.side * (params.side + 1));
+    for _ in 0..params.side {
+        for _ in 0..params.side {
+            text.push(if rng.gen_bool(params.active_density) { '#' } else { '.' });
+        }
+        text.push('\n');
+    }
+    Ok(Generated::new(text, None::<usize>, None::<usize>))
+}
@@ -0,0 +1,89 @@
+//! Day 18: homework expressions of single digit numbers, `+`, `*` and parentheses.
+
+use rand::Rng as _;
+use crate::{parse_param, unknown_param, Generated, Rng};
+
+/// The largest value of any one expression, under either part's rules, so that the sum over
+/// all the lines fits in the solvers' answers.
+const MAX_VALUE: u64 = 1 << 40;
+
+pub const SIZE_PARAM: &str = "lines";
+
+#[derive(Clone, Debug)]
+pub struct Params {
+    pub lines: usize,
+    /// The most levels of parentheses.
+    pub max_depth: usize,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params { lines: 373, max_depth: 2 }
+    }
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "lines" => self.lines = parse_param(name, value, 1..=1_000_000)?,
+            "max_depth" => self.max_depth = parse_param(name, value, 0..=20)?,
+            _ => return Err(unknown_param(name, &["lines", "max_depth"])),
+        }
+        Ok(())
+    }
+}
+
+/// An expression, with its value under the part 1 and part 2 rules, or `None` if either value
+/// is larger than `MAX_VALUE`.
+fn random_expression(rng: &mut Rng, depth: usize) -> Option<(String, u64, u64)> {
+    let mut text = String::new();
+    // Part 1 is a left fold. Part 2 is a product of sums: `term` is the sum being added up.
+    let mut value_1: u64 = 0;
+    let mut product_2: u64 = 1;
+    let mut term_2: u64 = 0;
+    for i in 0..rng.gen_range(2..=6) {
+        let operator = if i == 0 || rng.gen_bool(0.5) { '+' } else { '*' };
+        if i > 0 {
+            text.push_str(&format!(" {} ", operator));
+        }
+        let (operand, operand_1, operand_2) = if depth > 0 && rng.gen_bool(0.25) {
+            let (subexpression, value_1, value_2) = random_expression(rng, depth - 1)?;
+            (format!("({})", subexpression), value_1, value_2)
+        } else {
+            let digit = rng.gen_range(1..=9);
+            (digit.to_string(), digit, digit)
+        };
+        text.push_str(&operand);
+        if operator == '+' {
+            value_1 += operand_1;
+            term_2 += operand_2;
+        } else {
+            value_1 = value_1.checked_mul(operand_1)?;
+            product_2 = product_2.checked_mul(term_2)?;
+            term_2 = operand_2;
+        }
+        if value_1 > MAX_VALUE || product_2 > MAX_VALUE || term_2 > MAX_VALUE {
+            return None;
+        }
+    }
+    let value_2 = product_2.checked_mul(term_2).filter(|&value| value <= MAX_VALUE)?;
+    Some((text, value_1, value_2))
+}
+
+pub fn generate(params: &Params, rng: &mut Rng) -> Result<Generated, String> {
+    let mut text = String::new();
+    let mut sum_1: u64 = 0;
+    let mut sum_2: u64 = 0;
+    for _ in 0..params.lines {
+        let (expression, value_1, value_2) = loop {
+            if let Some(expression) = random_expression(rng, params.max_depth) {
+                break expression;
+            }
+        };
+        text.push_str(&expression);
+        text.push('\n');
+        sum_1 += value_1;
+        sum_2 += value_2;
+    }
+    Ok(Generated::new(text, Some(sum_1), Some(sum_2)))
+}
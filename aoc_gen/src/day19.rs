@@ -0,0 +1,149 @@
+//! Day 19: message rules in levels, and messages which do or do not match rule 0.
+//!
+//! The two literal rules, `"a"` and `"b"`, are level 0. Each rule of a higher level has one or
+//! two alternatives, each of which is a sequence of two rules of the level below, so every
+//! message matched by a level `l` rule is `2^l` characters long. Rule 0 is a sequence of
+//! top level rules. Messages are made from rule 0, and some are then changed by one character.
+
+use rand::{seq::SliceRandom, Rng as _};
+use crate::{parse_param, unknown_param, Generated, Rng};
+
+pub const SIZE_PARAM: &str = "messages";
+
+#[derive(Clone, Debug)]
+pub struct Params {
+    pub messages: usize,
+    /// The number of levels of rules above the literal rules.
+    pub levels: usize,
+    /// The number of rules in each level above the literal rules.
+    pub rules_per_level: usize,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params { messages: 400, levels: 3, rules_per_level: 12 }
+    }
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "messages" => self.messages = parse_param(name, value, 1..=1_000_000)?,
+            "levels" => self.levels = parse_param(name, value, 1..=10)?,
+            "rules_per_level" => self.rules_per_level = parse_param(name, value, 1..=1000)?,
+            _ => return Err(unknown_param(name, &["messages", "levels", "rules_per_level"])),
+        }
+        Ok(())
+    }
+}
+
+enum Rule {
+    Literal(u8),
+    Alternatives(Vec<Vec<usize>>),
+}
+
+/// The rules, indexed by their IDs, and the length of the messages which each rule matches.
+struct Ruleset {
+    rules: Vec<Rule>,
+    lengths: Vec<usize>,
+}
+
+impl Ruleset {
+    fn sample(&self, rule_id: usize, rng: &mut Rng, message: &mut Vec<u8>) {
+        match &self.rules[rule_id] {
+            Rule::Literal(c) => message.push(*c),
+            Rule::Alternatives(alternatives) => {
+                for &subrule_id in alternatives.choose(rng).unwrap() {
+                    self.sample(subrule_id, rng, message);
+                }
+            }
+        }
+    }
+
+    fn matches(&self, rule_id: usize, message: &[u8]) -> bool {
+        if message.len() != self.lengths[rule_id] {
+            return false;
+        }
+        match &self.rules[rule_id] {
+            Rule::Literal(c) => message[0] == *c,
+            Rule::Alternatives(alternatives) => alternatives.iter().any(|sequence| {
+                let mut rest = message;
+                sequence.iter().all(|&subrule_id| {
+                    let (start, end) = rest.split_at(self.lengths[subrule_id]);
+                    rest = end;
+                    self.matches(subrule_id, start)
+                })
+            }),
+        }
+    }
+}
+
+pub fn generate(params: &Params, rng: &mut Rng) -> Result<Generated, String> {
+    let rule_count = 3 + params.levels * params.rules_per_level;
+    // Rule 0 keeps its ID, and the others are numbered at random.
+    let mut ids = (1..rule_count).collect::<Vec<usize>>();
+    ids.shuffle(rng);
+    let mut ids = ids.into_iter();
+
+    let mut ruleset = Ruleset {
+        rules: (0..rule_count).map(|_| Rule::Alternatives(Vec::new())).collect(),
+        lengths: vec![0; rule_count],
+    };
+    let mut lines = Vec::<(usize, String)>::new();
+    let mut add_rule = |ruleset: &mut Ruleset, id: usize, rule: Rule, length: usize, line: String| {
+        ruleset.rules[id] = rule;
+        ruleset.lengths[id] = length;
+        lines.push((id, line));
+    };
+    let format_sequences = |sequences: &[Vec<usize>]| {
+        sequences
+            .iter()
+            .map(|sequence| sequence.iter().map(|id| id.to_string()).collect::<Vec<String>>().join(" "))
+            .collect::<Vec<String>>()
+            .join(" | ")
+    };
+
+    let mut level = Vec::<usize>::new();
+    for &c in b"ab" {
+        let id = ids.next().unwrap();
+        add_rule(&mut ruleset, id, Rule::Literal(c), 1, format!("\"{}\"", c as char));
+        level.push(id);
+    }
+    for l in 1..=params.levels {
+        let mut next_level = Vec::new();
+        for _ in 0..params.rules_per_level {
+            let id = ids.next().unwrap();
+            let alternatives = (0..rng.gen_range(1..=2))
+                .map(|_| vec![*level.choose(rng).unwrap(), *level.choose(rng).unwrap()])
+                .collect::<Vec<Vec<usize>>>();
+            let line = format_sequences(&alternatives);
+            add_rule(&mut ruleset, id, Rule::Alternatives(alternatives), 1 << l, line);
+            next_level.push(id);
+        }
+        level = next_level;
+    }
+    let sequence = (0..rng.gen_range(2..=3)).map(|_| *level.choose(rng).unwrap()).collect::<Vec<usize>>();
+    let line = format_sequences(std::slice::from_ref(&sequence));
+    let length = sequence.len() << params.levels;
+    add_rule(&mut ruleset, 0, Rule::Alternatives(vec![sequence]), length, line);
+    lines.shuffle(rng);
+
+    let mut text = lines.iter().map(|(id, line)| format!("{}: {}\n", id, line)).collect::<String>();
+    text.push('\n');
+    let mut matching = 0;
+    for _ in 0..params.messages {
+        let mut message = Vec::new();
+        ruleset.sample(0, rng, &mut message);
+        let i = rng.gen_range(0..message.len());
+        match rng.gen_range(0..4) {
+            0 => message[i] = if message[i] == b'a' { b'b' } else { b'a' },
+            1 => message.insert(i, *b"ab".choose(rng).unwrap()),
+            2 => { message.remove(i); }
+            _ => {}
+        }
+        matching += ruleset.matches(0, &message) as usize;
+        text.push_str(std::str::from_utf8(&message).unwrap());
+        text.push('\n');
+    }
+    Ok(Generated::new(text, Some(matching), None::<usize>))
+}
@@ -0,0 +1,51 @@
+//! Day 2: password databases, with each policy's valid passwords counted as they are generated.
+
+use rand::Rng as _;
+use crate::{parse_param, unknown_param, Generated, Rng};
+
+pub const SIZE_PARAM: &str = "lines";
+
+#[derive(Clone, Debug)]
+pub struct Params {
+    /// The number of passwords in the database.
+    pub lines: usize,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params { lines: 1000 }
+    }
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "lines" => self.lines = parse_param(name, value, 1..=10_000_000)?,
+            _ => return Err(unknown_param(name, &["lines"])),
+        }
+        Ok(())
+    }
+}
+
+pub fn generate(params: &Params, rng: &mut Rng) -> Result<Generated, String> {
+    let mut text = String::new();
+    let mut valid_1 = 0;
+    let mut valid_2 = 0;
+    for _ in 0..params.lines {
+        let ch = rng.gen_range(b'a'..=b'z') as char;
+        let length = rng.gen_range(3..=20);
+        let first = rng.gen_range(1..length);
+        let second = rng.gen_range(first + 1..=length);
+        // Use the policy's letter often, so that many passwords are valid.
+        let password = (0..length)
+            .map(|_| if rng.gen_bool(0.4) { ch } else { rng.gen_range(b'a'..=b'z') as char })
+            .collect::<String>();
+
+        let count = password.chars().filter(|&c| c == ch).count();
+        valid_1 += (first..=second).contains(&count) as usize;
+        let at = |position: usize| password.chars().nth(position - 1) == Some(ch);
+        valid_2 += (at(first) != at(second)) as usize;
+        text += &format!("{}-{} {}: {}\n", first, second, ch, password);
+    }
+    Ok(Generated::new(text, Some(valid_1), Some(valid_2)))
+}
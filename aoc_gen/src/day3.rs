@@ -0,0 +1,57 @@
+//! Day 3: terrain maps, with the trees on each slope counted as they are generated.
+
+use rand::Rng as _;
+use crate::{parse_param, unknown_param, Generated, Rng};
+
+/// The (right, down) slopes of part 2. The first is also the slope of part 1.
+const SLOPES: [(usize, usize); 5] = [(3, 1), (1, 1), (5, 1), (7, 1), (1, 2)];
+
+pub const SIZE_PARAM: &str = "rows";
+
+#[derive(Clone, Debug)]
+pub struct Params {
+    pub rows: usize,
+    /// The width of the pattern, which repeats to the right.
+    pub columns: usize,
+    /// The chance that each square is a tree.
+    pub tree_density: f64,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params { rows: 323, columns: 31, tree_density: 0.25 }
+    }
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "rows" => self.rows = parse_param(name, value, 1..=10_000_000)?,
+            "columns" => self.columns = parse_param(name, value, 1..=100_000)?,
+            "tree_density" => self.tree_density = parse_param(name, value, 0.0..=1.0)?,
+            _ => return Err(unknown_param(name, &["rows", "columns", "tree_density"])),
+        }
+        Ok(())
+    }
+}
+
+pub fn generate(params: &Params, rng: &mut Rng) -> Result<Generated, String> {
+    let terrain = (0..params.rows)
+        .map(|_| (0..params.columns).map(|_| rng.gen_bool(params.tree_density)).collect::<Vec<bool>>())
+        .collect::<Vec<Vec<bool>>>();
+
+    let trees_on_slope = |(right, down): (usize, usize)| {
+        (0..params.rows)
+            .step_by(down)
+            .enumerate()
+            .filter(|&(step, row)| terrain[row][(step * right) % params.columns])
+            .count() as u64
+    };
+    let answer_2 = SLOPES.iter().map(|&slope| trees_on_slope(slope)).product::<u64>();
+
+    let text = terrain
+        .iter()
+        .map(|row| row.iter().map(|&tree| if tree {'#'} else {'.'}).collect::<String>() + "\n")
+        .collect::<String>();
+    Ok(Generated::new(text, Some(trees_on_slope(SLOPES[0])), Some(answer_2)))
+}
@@ -0,0 +1,108 @@
+//! Day 4: passport batch files, with a known number of complete and valid passports.
+
+use rand::{seq::SliceRandom, Rng as _};
+use crate::{parse_param, unknown_param, Generated, Rng};
+
+const EYE_COLORS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+pub const SIZE_PARAM: &str = "passports";
+
+#[derive(Clone, Debug)]
+pub struct Params {
+    pub passports: usize,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params { passports: 290 }
+    }
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "passports" => self.passports = parse_param(name, value, 1..=10_000_000)?,
+            _ => return Err(unknown_param(name, &["passports"])),
+        }
+        Ok(())
+    }
+}
+
+/// How a generated passport is broken, if at all.
+#[derive(Clone, Copy, PartialEq)]
+enum Defect {
+    None,
+    /// A required field is missing, so the passport is neither complete nor valid.
+    MissingField,
+    /// A field has an invalid value, so the passport is complete but not valid.
+    InvalidValue,
+}
+
+/// The required fields, with valid values.
+fn valid_fields(rng: &mut Rng) -> Vec<(&'static str, String)> {
+    let height = if rng.gen_bool(0.5) {
+        format!("{}cm", rng.gen_range(150..=193))
+    } else {
+        format!("{}in", rng.gen_range(59..=76))
+    };
+    vec![
+        ("byr", rng.gen_range(1920..=2002).to_string()),
+        ("iyr", rng.gen_range(2010..=2020).to_string()),
+        ("eyr", rng.gen_range(2020..=2030).to_string()),
+        ("hgt", height),
+        ("hcl", format!("#{:06x}", rng.gen_range(0..0x100_0000))),
+        ("ecl", EYE_COLORS.choose(rng).unwrap().to_string()),
+        ("pid", format!("{:09}", rng.gen_range(0..1_000_000_000))),
+    ]
+}
+
+/// An invalid value for the required field `key`.
+fn invalid_value(key: &str, rng: &mut Rng) -> String {
+    match key {
+        "byr" => rng.gen_range(2003..=2020).to_string(),
+        "iyr" => rng.gen_range(1990..=2009).to_string(),
+        "eyr" => rng.gen_range(2031..=2040).to_string(),
+        "hgt" => format!("{}cm", rng.gen_range(100..150)),
+        "hcl" => format!("{:06x}", rng.gen_range(0..0x100_0000)),
+        "ecl" => ["xyz", "zzz", "red"].choose(rng).unwrap().to_string(),
+        "pid" => format!("{:08}", rng.gen_range(0..100_000_000)),
+        _ => unreachable!("only required fields are made invalid"),
+    }
+}
+
+pub fn generate(params: &Params, rng: &mut Rng) -> Result<Generated, String> {
+    let mut passports = Vec::<String>::new();
+    let mut complete = 0;
+    let mut valid = 0;
+    for _ in 0..params.passports {
+        let defect = *[Defect::None, Defect::MissingField, Defect::InvalidValue].choose(rng).unwrap();
+        let mut fields = valid_fields(rng);
+        match defect {
+            Defect::None => valid += 1,
+            Defect::MissingField => {
+                fields.remove(rng.gen_range(0..fields.len()));
+            },
+            Defect::InvalidValue => {
+                let i = rng.gen_range(0..fields.len());
+                fields[i].1 = invalid_value(fields[i].0, rng);
+            },
+        }
+        if defect != Defect::MissingField {
+            complete += 1;
+        }
+        if rng.gen_bool(0.5) {
+            fields.push(("cid", rng.gen_range(1..350).to_string()));
+        }
+        fields.shuffle(rng);
+
+        let mut passport = String::new();
+        for (i, (key, value)) in fields.iter().enumerate() {
+            if i > 0 {
+                passport.push(if rng.gen_bool(0.3) {'\n'} else {' '});
+            }
+            passport += &format!("{}:{}", key, value);
+        }
+        passports.push(passport);
+    }
+    Ok(Generated::new(passports.join("\n\n") + "\n", Some(complete), Some(valid)))
+}
@@ -0,0 +1,59 @@
+//! Day 5: boarding passes for a contiguous range of seats, with one seat missing.
+
+use rand::{seq::SliceRandom, Rng as _};
+use crate::{parse_param, unknown_param, Generated, Rng};
+
+/// The number of seat IDs on the plane: 128 rows of 8 seats.
+const SEATS: u32 = 1024;
+
+pub const SIZE_PARAM: &str = "passes";
+
+#[derive(Clone, Debug)]
+pub struct Params {
+    /// The number of boarding passes. The plane limits this to 1021, leaving room for the seats
+    /// on either side of the range and the missing seat.
+    pub passes: u32,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params { passes: 800 }
+    }
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "passes" => self.passes = parse_param(name, value, 2..=SEATS - 3)?,
+            _ => return Err(unknown_param(name, &["passes"])),
+        }
+        Ok(())
+    }
+}
+
+fn seat_code(seat_id: u32) -> String {
+    (0..10)
+        .rev()
+        .map(|bit| {
+            let set = seat_id >> bit & 1 == 1;
+            match (bit >= 3, set) {
+                (true, false) => 'F',
+                (true, true) => 'B',
+                (false, false) => 'L',
+                (false, true) => 'R',
+            }
+        })
+        .collect()
+}
+
+pub fn generate(params: &Params, rng: &mut Rng) -> Result<Generated, String> {
+    // The range of seats includes the missing one, which is not at either end.
+    let first = rng.gen_range(1..SEATS - params.passes - 1);
+    let last = first + params.passes;
+    let missing = rng.gen_range(first + 1..last);
+    let mut seat_ids = (first..=last).filter(|&id| id != missing).collect::<Vec<u32>>();
+    seat_ids.shuffle(rng);
+
+    let text = seat_ids.iter().map(|&id| seat_code(id) + "\n").collect::<String>();
+    Ok(Generated::new(text, Some(last), Some(missing)))
+}
@@ -0,0 +1,56 @@
+//! Day 6: groups' customs answers, with the questions anyone and everyone answered counted.
+
+use rand::Rng as _;
+use crate::{parse_param, unknown_param, Generated, Rng};
+
+pub const SIZE_PARAM: &str = "groups";
+
+#[derive(Clone, Debug)]
+pub struct Params {
+    pub groups: usize,
+    pub max_group_size: usize,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params { groups: 480, max_group_size: 5 }
+    }
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "groups" => self.groups = parse_param(name, value, 1..=10_000_000)?,
+            "max_group_size" => self.max_group_size = parse_param(name, value, 1..=1000)?,
+            _ => return Err(unknown_param(name, &["groups", "max_group_size"])),
+        }
+        Ok(())
+    }
+}
+
+pub fn generate(params: &Params, rng: &mut Rng) -> Result<Generated, String> {
+    let mut groups = Vec::<String>::new();
+    let mut anyone_total = 0;
+    let mut everyone_total = 0;
+    for _ in 0..params.groups {
+        // Bit `i` of each set of answers is set if question `i` was answered yes.
+        let people = (0..rng.gen_range(1..=params.max_group_size))
+            .map(|_| loop {
+                let answers = rng.gen_range(0u32..1 << 26) & rng.gen_range(0u32..1 << 26);
+                // Everyone answered yes to at least one question.
+                if answers != 0 {
+                    break answers;
+                }
+            })
+            .collect::<Vec<u32>>();
+        anyone_total += people.iter().fold(0, |a, b| a | b).count_ones();
+        everyone_total += people.iter().fold(!0, |a, b| a & b).count_ones();
+
+        let format_answers = |answers: u32| (0..26)
+            .filter(|i| answers >> i & 1 == 1)
+            .map(|i| (b'a' + i as u8) as char)
+            .collect::<String>();
+        groups.push(people.into_iter().map(format_answers).collect::<Vec<String>>().join("\n"));
+    }
+    Ok(Generated::new(groups.join("\n\n") + "\n", Some(anyone_total), Some(everyone_total)))
+}
@@ -0,0 +1,142 @@
+//! Day 7: bag rule graphs of a given number of colors and depth.
+//!
+//! The colors are arranged in layers, and bags only contain bags from deeper layers, so the
+//! graph has no cycles. Bags in the deepest layer contain no other bags.
+
+use std::collections::{HashMap, HashSet};
+use rand::{seq::SliceRandom, Rng as _};
+use crate::{parse_param, random_word, unknown_param, Generated, Rng};
+
+const MY_BAG: &str = "shiny gold";
+
+/// The most layers below `MY_BAG`, which keeps the number of bags inside it within a `u32`.
+const MAX_LAYERS_BELOW_MY_BAG: usize = 6;
+
+pub const SIZE_PARAM: &str = "colors";
+
+#[derive(Clone, Debug)]
+pub struct Params {
+    /// The number of bag colors, each of which has one rule.
+    pub colors: usize,
+    /// The number of layers below the top layer. The longest chain of bags inside bags has
+    /// `depth` bags inside the outermost bag.
+    pub depth: usize,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params { colors: 594, depth: 8 }
+    }
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "colors" => self.colors = parse_param(name, value, 2..=10_000_000)?,
+            "depth" => self.depth = parse_param(name, value, 1..=1000)?,
+            _ => return Err(unknown_param(name, &["colors", "depth"])),
+        }
+        Ok(())
+    }
+}
+
+/// `count` distinct two word colors, like `dotted black`, including `MY_BAG`.
+fn random_colors(count: usize, rng: &mut Rng) -> Vec<String> {
+    let mut colors = HashSet::<String>::new();
+    colors.insert(MY_BAG.to_string());
+    while colors.len() < count {
+        let (first_length, second_length) = (rng.gen_range(3..=7), rng.gen_range(3..=7));
+        colors.insert(format!("{} {}", random_word(rng, first_length), random_word(rng, second_length)));
+    }
+    let mut colors = colors.into_iter().filter(|color| color != MY_BAG).collect::<Vec<String>>();
+    // Sort first, because the order of a `HashSet` is not reproducible.
+    colors.sort();
+    colors.shuffle(rng);
+    colors
+}
+
+pub fn generate(params: &Params, rng: &mut Rng) -> Result<Generated, String> {
+    if params.colors < params.depth + 2 {
+        return Err(format!("{} colors are too few for {} layers below the top layer", params.colors, params.depth));
+    }
+
+    // Deal the colors into layers. Every layer has at least one color other than `MY_BAG`.
+    let mut layers = vec![Vec::<String>::new(); params.depth + 1];
+    let my_layer = params.depth - params.depth.min(MAX_LAYERS_BELOW_MY_BAG);
+    layers[my_layer].push(MY_BAG.to_string());
+    for (i, color) in random_colors(params.colors, rng).into_iter().enumerate() {
+        let layer = if i <= params.depth { i } else { rng.gen_range(0..=params.depth) };
+        layers[layer].push(color);
+    }
+
+    // Each bag outside the deepest layer contains 1 to 3 kinds of bags: at least one from the
+    // next layer, and maybe some from the layer after that.
+    let mut contents = HashMap::<&str, Vec<(&str, u32)>>::new();
+    for (l, layer) in layers.iter().enumerate() {
+        for color in layer {
+            let mut inside = Vec::<(&str, u32)>::new();
+            if l < params.depth {
+                let kinds = rng.gen_range(1..=3);
+                let mut candidates = layers[l + 1].iter().collect::<Vec<&String>>();
+                let first = candidates.swap_remove(rng.gen_range(0..candidates.len()));
+                if l + 2 <= params.depth {
+                    candidates.extend(layers[l + 2].iter());
+                }
+                inside.push((first.as_str(), rng.gen_range(1..=4)));
+                for other in candidates.choose_multiple(rng, kinds - 1) {
+                    inside.push((other.as_str(), rng.gen_range(1..=4)));
+                }
+            }
+            contents.insert(color.as_str(), inside);
+        }
+    }
+
+    // Part 1: search up the graph from `MY_BAG`.
+    let mut containers = HashMap::<&str, Vec<&str>>::new();
+    for (&outer, inside) in contents.iter() {
+        for &(inner, _) in inside {
+            containers.entry(inner).or_default().push(outer);
+        }
+    }
+    let mut can_contain_my_bag = HashSet::<&str>::new();
+    let mut search_stack = vec![MY_BAG];
+    while let Some(color) = search_stack.pop() {
+        for &outer in containers.get(color).into_iter().flatten() {
+            if can_contain_my_bag.insert(outer) {
+                search_stack.push(outer);
+            }
+        }
+    }
+
+    // Part 2: count the bags inside each bag, deepest layer first.
+    let mut bags_inside = HashMap::<&str, u64>::new();
+    for layer in layers.iter().rev() {
+        for color in layer {
+            let count = contents[color.as_str()]
+                .iter()
+                .map(|(inner, number)| *number as u64 * (1 + bags_inside[inner]))
+                .sum();
+            bags_inside.insert(color.as_str(), count);
+        }
+    }
+
+    let mut rules = layers
+        .iter()
+        .flatten()
+        .map(|color| format_rule(color, &contents[color.as_str()]))
+        .collect::<Vec<String>>();
+    rules.shuffle(rng);
+    let text = rules.concat();
+    Ok(Generated::new(text, Some(can_contain_my_bag.len()), Some(bags_inside[MY_BAG])))
+}
+
+fn format_rule(color: &str, inside: &[(&str, u32)]) -> String {
+    if inside.is_empty() {
+        return format!("{} bags contain no other bags.\n", color);
+    }
+    let inside = inside
+        .iter()
+        .map(|&(inner, number)| format!("{} {} {}", number, inner, if number == 1 {"bag"} else {"bags"}))
+        .collect::<Vec<String>>();
+    format!("{} bags contain {}.\n", color, inside.join(", "))
+}
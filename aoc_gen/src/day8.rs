@@ -0,0 +1,100 @@
+//! Day 8: boot code programs with exactly one corrupt instruction, which can be repaired.
+//!
+//! The repaired program runs through its "live" instructions from start to end. Between them
+//! are dead blocks, which the live path jumps over, and which jump back into the live path.
+//! The corrupt instruction is a `jmp` back into the live path, which should be a `nop`.
+//!
+//! No other change of one instruction can repair the program: live `nop`s only have arguments
+//! of zero or less, and changing a live `jmp` to a `nop` runs a dead block, which jumps back to
+//! an instruction which already ran.
+
+use rand::{seq::SliceRandom, Rng as _};
+use crate::{parse_param, unknown_param, Generated, Rng};
+
+pub const SIZE_PARAM: &str = "instructions";
+
+#[derive(Clone, Debug)]
+pub struct Params {
+    /// The number of instructions in the program.
+    pub instructions: usize,
+    /// The chance that each live instruction is followed by a dead block.
+    pub dead_block_chance: f64,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params { instructions: 630, dead_block_chance: 0.1 }
+    }
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "instructions" => self.instructions = parse_param(name, value, 4..=10_000_000)?,
+            "dead_block_chance" => self.dead_block_chance = parse_param(name, value, 0.0..=1.0)?,
+            _ => return Err(unknown_param(name, &["instructions", "dead_block_chance"])),
+        }
+        Ok(())
+    }
+}
+
+fn random_acc_or_nop(rng: &mut Rng, max_nop_back: usize) -> (&'static str, i32) {
+    if rng.gen_bool(0.6) {
+        ("acc", rng.gen_range(-50..=50))
+    } else {
+        ("nop", -rng.gen_range(0..=max_nop_back.min(50) as i32))
+    }
+}
+
+pub fn generate(params: &Params, rng: &mut Rng) -> Result<Generated, String> {
+    let mut program = Vec::<(&str, i32)>::new();
+    let mut live = Vec::<usize>::new();
+    // The sum of the live `acc` arguments, which is the accumulator at the end of the path.
+    let mut accumulator: i64 = 0;
+    // The accumulator when the corrupt instruction runs, and the program starts its loop.
+    let mut accumulator_at_loop = None;
+    let corrupt_at = rng.gen_range(1..params.instructions - 2);
+
+    // Leave room for the last live instruction, which is never a `jmp`.
+    while program.len() < params.instructions - 1 {
+        let i = program.len();
+        live.push(i);
+        if accumulator_at_loop.is_none() && i >= corrupt_at {
+            let target = *live[..live.len() - 1].choose(rng).unwrap();
+            program.push(("jmp", target as i32 - i as i32));
+            accumulator_at_loop = Some(accumulator);
+            continue;
+        }
+
+        // The most instructions in a dead block here. Until the corrupt instruction is placed,
+        // there must be room for it after the dead block.
+        let room = params.instructions - 1 - i;
+        let max_length = if accumulator_at_loop.is_some() { room.saturating_sub(2) } else { room.saturating_sub(3) };
+        if max_length >= 1 && rng.gen_bool(params.dead_block_chance) {
+            // A `jmp` over the dead block, the dead block, and its `jmp` back into the live path.
+            let length = rng.gen_range(1..=max_length.min(4));
+            program.push(("jmp", length as i32 + 2));
+            for _ in 0..length {
+                let i = program.len();
+                program.push(random_acc_or_nop(rng, i));
+            }
+            let target = *live.choose(rng).unwrap();
+            program.push(("jmp", target as i32 - program.len() as i32));
+        } else {
+            let (operation, argument) = random_acc_or_nop(rng, i);
+            if operation == "acc" {
+                accumulator += argument as i64;
+            }
+            program.push((operation, argument));
+        }
+    }
+    let argument = rng.gen_range(-50..=50);
+    accumulator += argument;
+    program.push(("acc", argument as i32));
+
+    let text = program
+        .iter()
+        .map(|(operation, argument)| format!("{} {:+}\n", operation, argument))
+        .collect::<String>();
+    Ok(Generated::new(text, accumulator_at_loop, Some(accumulator)))
+}
@@ -0,0 +1,89 @@
+//! Day 9: XMAS numbers with one planted invalid number, which is the sum of exactly one
+//! contiguous set of the numbers before it.
+
+use rand::Rng as _;
+use crate::{parse_param, unknown_param, Generated, Rng};
+
+const PREAMBLE_LENGTH: usize = 25;
+
+/// The most valid numbers before the invalid number. Each valid number is the sum of two
+/// earlier ones, so the numbers grow exponentially, and must stay well within a `u64`.
+const MAX_VALID_NUMBERS: usize = 1000;
+
+pub const SIZE_PARAM: &str = "numbers";
+
+#[derive(Clone, Debug)]
+pub struct Params {
+    /// The number of numbers in the list, including the preamble.
+    pub numbers: usize,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params { numbers: 1000 }
+    }
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "numbers" => self.numbers = parse_param(name, value, 2 * PREAMBLE_LENGTH + 2..=10_000_000)?,
+            _ => return Err(unknown_param(name, &["numbers"])),
+        }
+        Ok(())
+    }
+}
+
+fn is_sum_of_pair(window: &[u64], value: u64) -> bool {
+    window.iter().any(|&x| window.iter().any(|&y| x != y && x + y == value))
+}
+
+/// Count the contiguous sets of `numbers`, all of which are positive, which add up to `value`.
+fn count_contiguous_sums(numbers: &[u64], value: u64) -> usize {
+    let mut count = 0;
+    let mut start = 0;
+    let mut sum = 0;
+    for end in 0..numbers.len() {
+        sum += numbers[end];
+        while sum > value {
+            sum -= numbers[start];
+            start += 1;
+        }
+        count += (sum == value && start <= end) as usize;
+    }
+    count
+}
+
+pub fn generate(params: &Params, rng: &mut Rng) -> Result<Generated, String> {
+    let valid_count = (params.numbers / 2).min(MAX_VALID_NUMBERS);
+    let mut numbers = (0..PREAMBLE_LENGTH).map(|_| rng.gen_range(1..=50)).collect::<Vec<u64>>();
+    while numbers.len() < valid_count {
+        let window = &numbers[numbers.len() - PREAMBLE_LENGTH..];
+        let x = window[rng.gen_range(0..PREAMBLE_LENGTH)];
+        let y = window[rng.gen_range(0..PREAMBLE_LENGTH)];
+        if x != y {
+            numbers.push(x + y);
+        }
+    }
+
+    let window = &numbers[valid_count - PREAMBLE_LENGTH..];
+    let (invalid, weakness) = loop {
+        let length = rng.gen_range(2..=17);
+        let start = rng.gen_range(0..=valid_count - length);
+        let set = &numbers[start..start + length];
+        let invalid = set.iter().sum();
+        if !is_sum_of_pair(window, invalid) && count_contiguous_sums(&numbers, invalid) == 1 {
+            break (invalid, set.iter().min().unwrap() + set.iter().max().unwrap());
+        }
+    };
+    numbers.push(invalid);
+
+    // The numbers after the invalid one do not change the answers.
+    let largest = *numbers.iter().max().unwrap();
+    while numbers.len() < params.numbers {
+        numbers.push(rng.gen_range(1..=largest));
+    }
+
+    let text = numbers.iter().map(|number| format!("{}\n", number)).collect::<String>();
+    Ok(Generated::new(text, Some(invalid), Some(weakness)))
+}
@@ -0,0 +1,209 @@
+//! Generators of random puzzle inputs for every day, for stress and scale testing.
+//!
+//! Inputs are seeded and reproducible: the same day, parameters and seed always give the same
+//! text. Each day's generator has a size parameter, e.g. the number of bag colors for day 7,
+//! which defaults to about the size of the official inputs. Other parameters, like the depth of
+//! day 7's bag graph, can be set by name.
+//!
+//! Wherever the generator can plant the answer, e.g. by choosing which instruction of day 8's
+//! program is corrupt, it returns the answer along with the input, so that generated inputs can
+//! be used as tests.
+
+use std::{fmt, ops::RangeInclusive, str::FromStr};
+use rand::SeedableRng;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+
+/// The random number generator for all generators. ChaCha gives the same numbers for the same
+/// seed on every platform and in every version of `rand_chacha`.
+pub type Rng = rand_chacha::ChaCha8Rng;
+
+/// A generated puzzle input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Generated {
+    pub text: String,
+    /// The answer to each part, formatted like the solvers' answers, or `None` if the generator
+    /// cannot know it without solving the puzzle.
+    pub answers: [Option<String>; 2],
+}
+
+impl Generated {
+    fn new(text: String, answer_1: Option<impl fmt::Display>, answer_2: Option<impl fmt::Display>) -> Generated {
+        Generated { text, answers: [answer_1.map(|a| a.to_string()), answer_2.map(|a| a.to_string())] }
+    }
+}
+
+/// Generate an input for `day` from `seed`, with the named parameters in `settings` changed
+/// from their defaults. The parameter named `size` is the day's size parameter.
+pub fn generate(day: u32, seed: u64, settings: &[(String, String)]) -> Result<Generated, String> {
+    let mut rng = Rng::seed_from_u64(seed);
+    macro_rules! generate_with {
+        ($day:ident) => {{
+            let mut params = $day::Params::default();
+            for (name, value) in settings {
+                let name = if name == "size" { $day::SIZE_PARAM } else { name.as_str() };
+                params.set(name, value)?;
+            }
+            $day::generate(&params, &mut rng)
+        }};
+    }
+    match day {
+        1 => generate_with!(day1),
+        2 => generate_with!(day2),
+        3 => generate_with!(day3),
+        4 => generate_with!(day4),
+        5 => generate_with!(day5),
+        6 => generate_with!(day6),
+        7 => generate_with!(day7),
+        8 => generate_with!(day8),
+        9 => generate_with!(day9),
+        10 => generate_with!(day10),
+        11 => generate_with!(day11),
+        12 => generate_with!(day12),
+        13 => generate_with!(day13),
+        14 => generate_with!(day14),
+        15 => generate_with!(day15),
+        16 => generate_with!(day16),
+        17 => generate_with!(day17),
+        18 => generate_with!(day18),
+        19 => generate_with!(day19),
+        _ => Err(format!("no generator for day {}", day)),
+    }
+}
+
+/// Parse the value of parameter `name`, which must be in `range`, for a `Params::set` method.
+fn parse_param<T: FromStr + PartialOrd + fmt::Display>(name: &str, value: &str, range: RangeInclusive<T>)
+        -> Result<T, String> {
+    match value.parse::<T>() {
+        Ok(value) if range.contains(&value) => Ok(value),
+        _ => Err(format!("expected {} from {} to {}, found `{}`", name, range.start(), range.end(), value)),
+    }
+}
+
+/// The error for an unknown parameter name, for a `Params::set` method.
+fn unknown_param(name: &str, known: &[&str]) -> String {
+    format!("unknown parameter `{}`, expected one of: {}", name, known.join(", "))
+}
+
+/// A random lowercase word of `length` letters, for names like day 7's bag colors.
+fn random_word(rng: &mut Rng, length: usize) -> String {
+    use rand::Rng as _;
+    (0..length).map(|_| rng.gen_range(b'a'..=b'z') as char).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|&(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+
+    /// Solve both parts of a generated input with the day's own solvers, which are the `dayN`
+    /// crates rather than this crate's `dayN` generator modules.
+    /// Parts without a solver, or whose answers the generator does not plant, are `None`.
+    fn solve(day: u32, text: &str) -> [Option<String>; 2] {
+        macro_rules! both {
+            ($day:ident) => {
+                [Some(::$day::solve_part_1(text).unwrap().to_string()), Some(::$day::solve_part_2(text).unwrap().to_string())]
+            };
+        }
+        match day {
+            1 => both!(day1),
+            2 => both!(day2),
+            3 => both!(day3),
+            4 => [None, Some(::day4::solve_part_2(text).unwrap().to_string())],
+            5 => both!(day5),
+            6 => both!(day6),
+            7 => both!(day7),
+            8 => both!(day8),
+            9 => both!(day9),
+            10 => both!(day10),
+            12 => both!(day12),
+            13 => both!(day13),
+            14 => both!(day14),
+            16 => both!(day16),
+            18 => both!(day18),
+            19 => [Some(::day19::solve_part_1(text).unwrap().to_string()), None],
+            _ => [None, None],
+        }
+    }
+
+    #[test]
+    fn test_planted_answers() {
+        let small_sizes = [
+            (1, "5"), (2, "10"), (3, "5"), (4, "10"), (5, "2"), (6, "3"), (7, "12"), (8, "4"), (9, "52"),
+            (10, "1"), (12, "3"), (13, "4"), (14, "3"), (16, "2"), (18, "3"), (19, "5"),
+        ];
+        for &(day, small_size) in small_sizes.iter() {
+            for seed in 0..5 {
+                for settings in [settings(&[]), settings(&[("size", small_size)])].iter() {
+                    let generated = generate(day, seed, settings).unwrap();
+                    let solved = solve(day, &generated.text);
+                    for (part, (planted, solved)) in generated.answers.iter().zip(solved.iter()).enumerate() {
+                        if solved.is_some() {
+                            assert_eq!(planted, solved, "day {} part {}, seed {}, settings {:?}", day, part + 1, seed, settings);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_unplanted() {
+        for seed in 0..5 {
+            let text = generate(11, seed, &[]).unwrap().text;
+            assert_eq!(::day11::parse_seating_area(&text).unwrap().height(), 90);
+            let text = generate(15, seed, &[]).unwrap().text;
+            assert_eq!(::day15::parse_starting_numbers(&text).unwrap().len(), 6);
+            let text = generate(17, seed, &settings(&[("size", "3")])).unwrap().text;
+            assert!(::day17::parse_initial_slice(&text).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_params() {
+        let generated = generate(7, 2, &settings(&[("size", "40"), ("depth", "12")])).unwrap();
+        assert_eq!(generated.text.lines().count(), 40);
+        assert_eq!(generated.answers, solve(7, &generated.text));
+        let generated = generate(16, 2, &settings(&[("fields", "30"), ("invalid_chance", "1")])).unwrap();
+        assert_eq!(generated.answers, solve(16, &generated.text));
+    }
+
+    #[test]
+    fn test_reproducible() {
+        for day in 1..=19 {
+            assert_eq!(generate(day, 7, &[]), generate(day, 7, &[]));
+            assert_ne!(generate(day, 7, &[]).unwrap().text, generate(day, 8, &[]).unwrap().text);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(generate(20, 0, &[]), Err("no generator for day 20".to_string()));
+        assert_eq!(generate(8, 0, &settings(&[("depth", "3")])),
+            Err("unknown parameter `depth`, expected one of: instructions, dead_block_chance".to_string()));
+        assert_eq!(generate(5, 0, &settings(&[("size", "1022")])),
+            Err("expected passes from 2 to 1021, found `1022`".to_string()));
+        assert!(generate(7, 0, &settings(&[("colors", "10"), ("depth", "9")])).is_err());
+    }
+}
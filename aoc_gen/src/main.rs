@@ -0,0 +1,57 @@
+//! `aoc_gen`: generate a random puzzle input for any day.
+//!
+//! ```text
+//! aoc_gen 7 --seed 3 --size 5000 --param depth=12 > bags.txt
+//! aoc_gen 8 --output program.txt
+//! ```
+//!
+//! The input is written to stdout, or to `--output`, and the planted answers to stderr.
+
+use std::fs;
+use aoc_common::UnwrapOrExit;
+use clap::Parser;
+
+#[derive(Parser)]
+#[command(name = "aoc_gen", about = "Generate random Advent of Code 2020 puzzle inputs with known answers")]
+struct Cli {
+    /// Day number (1-19).
+    #[arg(value_parser = clap::value_parser!(u32).range(1..=19))]
+    day: u32,
+    /// Seed for the random number generator. The same seed always gives the same input.
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    /// The day's size parameter, e.g. the number of bag colors for day 7.
+    #[arg(long)]
+    size: Option<String>,
+    /// Set another parameter of the day's generator, like `depth=12`. Can be repeated.
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_setting)]
+    params: Vec<(String, String)>,
+    /// Write the input to this file instead of stdout.
+    #[arg(long)]
+    output: Option<String>,
+}
+
+fn parse_setting(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((name, value)) => Ok((name.to_string(), value.to_string())),
+        None => Err(format!("expected a parameter like `name=value`, found `{}`", s)),
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let mut settings = cli.params;
+    if let Some(size) = cli.size {
+        settings.insert(0, ("size".to_string(), size));
+    }
+    let generated = aoc_gen::generate(cli.day, cli.seed, &settings).unwrap_or_exit();
+    match &cli.output {
+        Some(path) => fs::write(path, &generated.text)
+            .map_err(|error| format!("could not write {}: {}", path, error))
+            .unwrap_or_exit(),
+        None => print!("{}", generated.text),
+    }
+    for (part, answer) in generated.answers.iter().enumerate() {
+        eprintln!("part {}: {}", part + 1, answer.as_deref().unwrap_or("unknown"));
+    }
+}
@@ -0,0 +1,338 @@
+//! An HTTP server which answers puzzles for other tools, with JSON responses.
+//!
+//! - `GET /days` lists the days and which parts have solvers.
+//! - `POST /day/{n}/part/{p}`, with the puzzle input as the body, solves one part.
+//! - `POST /day/7/bags-inside?color=shiny%20gold`, with day 7's rules as the body, counts the bags
+//!   inside a bag of that color. `POST /day/7/bags-that-can-contain?color=...` lists the colors
+//!   of the bags which can contain it.
+//! - `POST /day/19/matches`, with rules and messages like day 19's input as the body, checks each
+//!   message against rule 0.
+//!
+//! Every error is a JSON object like `{"error": {"code": "parse_error", "message": "..."}}`, with
+//! `line` and `column` as well for malformed input. Requests are handled by `handle`, which needs
+//! no network, and `serve` answers them on a `tiny_http::Server`.
+
+use std::{io::Read, panic::{self, AssertUnwindSafe}, thread, time::Instant};
+use aoc_common::{ParseError, Solver};
+use aoc_days::{find_day, DAYS};
+use serde_json::{json, Value};
+
+/// The largest request body accepted, in bytes.
+pub const MAX_BODY_BYTES: u64 = 16 * 1024 * 1024;
+
+/// A response: an HTTP status code and a JSON body.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    fn ok(body: Value) -> Response {
+        Response { status: 200, body }
+    }
+}
+
+/// A request which could not be answered.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiError {
+    pub status: u16,
+    /// A short name for the kind of error, like `parse_error`, for programs to match on.
+    pub code: &'static str,
+    pub message: String,
+    /// Where malformed input went wrong, counting from 1.
+    pub position: Option<(usize, usize)>,
+}
+
+impl ApiError {
+    fn new(status: u16, code: &'static str, message: String) -> ApiError {
+        ApiError { status, code, message, position: None }
+    }
+
+    fn not_found(message: String) -> ApiError {
+        ApiError::new(404, "not_found", message)
+    }
+}
+
+impl From<ParseError> for ApiError {
+    fn from(error: ParseError) -> ApiError {
+        ApiError { status: 400, code: "parse_error", message: error.to_string(), position: Some((error.line, error.column)) }
+    }
+}
+
+impl From<ApiError> for Response {
+    fn from(error: ApiError) -> Response {
+        let mut details = json!({ "code": error.code, "message": error.message });
+        if let Some((line, column)) = error.position {
+            details["line"] = json!(line);
+            details["column"] = json!(column);
+        }
+        Response { status: error.status, body: json!({ "error": details }) }
+    }
+}
+
+/// The solver for part `part` of day `day`, from the registry which the `aoc` runner uses too.
+fn find_solver(day: u32, part: u32) -> Option<Solver> {
+    find_day(day)?.part(part as usize)
+}
+
+/// Answer one request. `url` is the path with its query string, and `body` the request body.
+/// A solver which panics gives a `solver_panicked` error, so one bad input can't stop the server.
+pub fn handle(method: &str, url: &str, body: &[u8]) -> Response {
+    match panic::catch_unwind(AssertUnwindSafe(|| route(method, url, body))) {
+        Ok(Ok(response)) => response,
+        Ok(Err(error)) => error.into(),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            ApiError::new(500, "solver_panicked", format!("solver panicked: {}", message)).into()
+        }
+    }
+}
+
+fn route(method: &str, url: &str, body: &[u8]) -> Result<Response, ApiError> {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments = path.split('/').filter(|segment| !segment.is_empty()).collect::<Vec<&str>>();
+    let expect_method = |expected: &str| {
+        if method == expected {
+            Ok(())
+        } else {
+            Err(ApiError::new(405, "method_not_allowed", format!("{} needs a {} request, not {}", path, expected, method)))
+        }
+    };
+    match segments[..] {
+        ["days"] => {
+            expect_method("GET")?;
+            Ok(list_days())
+        }
+        ["day", day, "part", part] => {
+            expect_method("POST")?;
+            let day = parse_path_number(day, "day")?;
+            let part = parse_path_number(part, "part")?;
+            solve(day, part, body_text(body)?)
+        }
+        ["day", "7", query_name @ ("bags-inside" | "bags-that-can-contain")] => {
+            expect_method("POST")?;
+            let color = query_parameter(query, "color")?;
+            bag_query(query_name, body_text(body)?, &color)
+        }
+        ["day", "19", "matches"] => {
+            expect_method("POST")?;
+            match_messages(body_text(body)?)
+        }
+        _ => Err(ApiError::not_found(format!("no endpoint at {}", path))),
+    }
+}
+
+fn parse_path_number(segment: &str, name: &str) -> Result<u32, ApiError> {
+    segment.parse().map_err(|_| ApiError::not_found(format!("expected a {} number, found `{}`", name, segment)))
+}
+
+fn body_text(body: &[u8]) -> Result<&str, ApiError> {
+    std::str::from_utf8(body).map_err(|error| ApiError::new(400, "invalid_utf8", format!("the body is not UTF-8: {}", error)))
+}
+
+/// Find the parameter `name` in a query string like `color=shiny%20gold&x=1`, and decode it.
+fn query_parameter(query: &str, name: &str) -> Result<String, ApiError> {
+    let missing = || ApiError::new(400, "missing_parameter", format!("expected a `{}` query parameter", name));
+    let value = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|&(key, _)| key == name)
+        .map(|(_, value)| value)
+        .ok_or_else(missing)?;
+    percent_decode(value).ok_or_else(|| ApiError::new(400, "invalid_parameter", format!("`{}` is not a valid query value", value)))
+}
+
+/// Decode a query string value, where `+` is a space and `%XX` is a byte in hex.
+fn percent_decode(value: &str) -> Option<String> {
+    let mut bytes = Vec::new();
+    let mut rest = value.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+                bytes.push(u8::from_str_radix(hex, 16).ok()?);
+                rest = &tail[2..];
+                continue;
+            }
+            _ => bytes.push(byte),
+        }
+        rest = tail;
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn list_days() -> Response {
+    let days = DAYS
+        .iter()
+        .map(|day| json!({ "day": day.number, "parts": (1..=day.parts.len()).filter(|&part| day.part(part).is_some()).collect::<Vec<usize>>() }))
+        .collect::<Vec<Value>>();
+    Response::ok(json!({ "days": days }))
+}
+
+/// Solve one part, and give the answer with its intermediate values. Like `aoc run --format json`,
+/// the values are strings, because some answers are too big for a JSON number.
+fn solve(day: u32, part: u32, text: &str) -> Result<Response, ApiError> {
+    let solver = find_solver(day, part)
+        .ok_or_else(|| ApiError::new(404, "no_solver", format!("there is no solver for day {} part {}", day, part)))?;
+    let start = Instant::now();
+    let solution = solver(text)?;
+    let intermediates = solution.intermediates.iter().map(|(name, value)| (name.to_string(), json!(value))).collect();
+    Ok(Response::ok(json!({
+        "day": day,
+        "part": part,
+        "answer": solution.answer,
+        "intermediates": Value::Object(intermediates),
+        "elapsed_seconds": start.elapsed().as_secs_f64(),
+    })))
+}
+
+fn bag_query(query_name: &str, text: &str, color: &str) -> Result<Response, ApiError> {
+    let graph = day7::parse_graph_from_text(text)?;
+    if !graph.contains_node(color) {
+        return Err(ApiError::new(404, "unknown_bag_color", format!("no rule mentions {} bags", color)));
+    }
+    if query_name == "bags-inside" {
+        return Ok(Response::ok(json!({ "color": color, "bags_inside": day7::compute_bags_inside(&graph, color) })));
+    }
+    let mut colors = day7::compute_bags_that_can_contain(&graph, color).into_iter().collect::<Vec<&str>>();
+    colors.sort();
+    Ok(Response::ok(json!({ "color": color, "count": colors.len(), "colors": colors })))
+}
+
+fn match_messages(text: &str) -> Result<Response, ApiError> {
+    let (ruleset, messages) = day19::parse_input(text)?;
+    let rule0 = &ruleset[&0];
+    let results = messages
+        .iter()
+        .map(|message| json!({ "message": message, "matches": rule0.matches(&ruleset, message) }))
+        .collect::<Vec<Value>>();
+    Ok(Response::ok(json!({ "count": day19::count_matching_messages(&ruleset, &messages), "messages": results })))
+}
+
+/// Answer requests on `server` until it fails, each on its own thread, so that a slow solver
+/// doesn't hold up the other requests.
+pub fn serve(server: tiny_http::Server) {
+    for mut request in server.incoming_requests() {
+        thread::spawn(move || {
+            let mut body = Vec::new();
+            let response = match request.as_reader().take(MAX_BODY_BYTES + 1).read_to_end(&mut body) {
+                Ok(_) if body.len() as u64 > MAX_BODY_BYTES => Response::from(ApiError::new(413, "body_too_large",
+                    format!("the body is larger than {} bytes", MAX_BODY_BYTES))),
+                Ok(_) => handle(request.method().as_str(), request.url(), &body),
+                Err(error) => ApiError::new(400, "invalid_body", format!("could not read the body: {}", error)).into(),
+            };
+            let mut json = serde_json::to_string_pretty(&response.body).unwrap();
+            json.push('\n');
+            let content_type = tiny_http::Header::from_bytes("Content-Type", "application/json").unwrap();
+            // The client may have gone away, and then there is nobody to tell.
+            let _ = request.respond(tiny_http::Response::from_string(json)
+                .with_status_code(response.status)
+                .with_header(content_type));
+        });
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, net::TcpStream};
+    use super::*;
+
+    const BAG_RULES: &str = "light red bags contain 1 bright white bag, 2 muted yellow bags.\n\
+        bright white bags contain 1 shiny gold bag.\n\
+        muted yellow bags contain 2 shiny gold bags.\n\
+        shiny gold bags contain 2 dark red bags.\n\
+        dark red bags contain no other bags.\n";
+
+    fn error_code(response: &Response) -> &str {
+        response.body["error"]["code"].as_str().unwrap()
+    }
+
+    #[test]
+    fn test_solve() {
+        let response = handle("POST", "/day/13/part/1", b"939\n7,13,x,x,59,x,31,19\n");
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body["answer"], "295");
+        assert_eq!(response.body["intermediates"], json!({ "bus_id": "59", "wait_time": "5" }));
+        let response = handle("POST", "/day/7/part/2", BAG_RULES.as_bytes());
+        assert_eq!((response.status, &response.body["answer"]), (200, &json!("2")));
+    }
+
+    #[test]
+    fn test_solve_errors() {
+        let response = handle("POST", "/day/15/part/1", b"0,3,x\n");
+        assert_eq!(response.status, 400);
+        assert_eq!(response.body, json!({ "error": {
+            "code": "parse_error", "message": "line 1, column 5: expected a number, found `x`", "line": 1, "column": 5 } }));
+        assert_eq!(error_code(&handle("POST", "/day/4/part/1", b"")), "no_solver");
+        assert_eq!(error_code(&handle("POST", "/day/seven/part/1", b"")), "not_found");
+        assert_eq!(error_code(&handle("GET", "/day/7/part/1", b"")), "method_not_allowed");
+        assert_eq!(handle("POST", "/day/7/part/1", b"\xff").status, 400);
+        let response = handle("POST", "/day/8/part/2", b"jmp +0\njmp -1\n");
+        assert_eq!((response.status, error_code(&response)), (500, "solver_panicked"));
+    }
+
+    #[test]
+    fn test_list_days() {
+        let response = handle("GET", "/days", b"");
+        assert_eq!(response.body["days"].as_array().unwrap().len(), 19);
+        assert_eq!(response.body["days"][3], json!({ "day": 4, "parts": [2] }));
+    }
+
+    #[test]
+    fn test_bag_queries() {
+        let response = handle("POST", "/day/7/bags-inside?color=shiny+gold", BAG_RULES.as_bytes());
+        assert_eq!(response.body, json!({ "color": "shiny gold", "bags_inside": 2 }));
+        let response = handle("POST", "/day/7/bags-that-can-contain?color=shiny%20gold", BAG_RULES.as_bytes());
+        assert_eq!(response.body, json!({ "color": "shiny gold", "count": 3,
+            "colors": ["bright white", "light red", "muted yellow"] }));
+        let response = handle("POST", "/day/7/bags-inside?color=faded+blue", BAG_RULES.as_bytes());
+        assert_eq!((response.status, error_code(&response)), (404, "unknown_bag_color"));
+        assert_eq!(error_code(&handle("POST", "/day/7/bags-inside", BAG_RULES.as_bytes())), "missing_parameter");
+        assert_eq!(error_code(&handle("POST", "/day/7/bags-inside?color=%zz", BAG_RULES.as_bytes())), "invalid_parameter");
+    }
+
+    #[test]
+    fn test_match_messages() {
+        let text = "0: 1 2\n1: \"a\"\n2: 1 3 | 3 1\n3: \"b\"\n\naab\naba\nabb\n";
+        let response = handle("POST", "/day/19/matches", text.as_bytes());
+        assert_eq!(response.body, json!({ "count": 2, "messages": [
+            { "message": "aab", "matches": true },
+            { "message": "aba", "matches": true },
+            { "message": "abb", "matches": false },
+        ] }));
+        assert_eq!(error_code(&handle("POST", "/day/19/matches", b"0: 1\n\na\n")), "parse_error");
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("shiny+gold"), Some("shiny gold".to_string()));
+        assert_eq!(percent_decode("caf%C3%A9%2B"), Some("café+".to_string()));
+        assert_eq!(percent_decode("%4"), None);
+        assert_eq!(percent_decode("%ff"), None);
+    }
+
+    #[test]
+    fn test_serve_on_localhost() {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let address = server.server_addr().to_ip().unwrap();
+        thread::spawn(move || serve(server));
+
+        let body = "1721\n979\n366\n299\n675\n1456\n";
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(stream, "POST /day/1/part/1 HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(), body).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.contains("Content-Type: application/json"), "{}", response);
+        let (_, json) = response.split_once("\r\n\r\n").unwrap();
+        assert_eq!(serde_json::from_str::<Value>(json).unwrap()["answer"], "514579");
+    }
+}
@@ -0,0 +1,30 @@
+//! `aoc_server`: answer puzzles over HTTP, for tools which would rather not run `aoc`.
+//!
+//! ```text
+//! aoc_server --port 3020
+//! curl --data-binary @day7/input.txt 'http://127.0.0.1:3020/day/7/bags-inside?color=shiny+gold'
+//! ```
+
+use aoc_common::UnwrapOrExit;
+use clap::Parser;
+
+#[derive(Parser)]
+#[command(name = "aoc_server", about = "Serve the Advent of Code 2020 solvers over HTTP, with JSON responses")]
+struct Cli {
+    /// Address to listen on. The default only accepts connections from this machine.
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+    /// Port to listen on, or 0 for any free port.
+    #[arg(long, default_value_t = 3020)]
+    port: u16,
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let address = format!("{}:{}", cli.host, cli.port);
+    let server = tiny_http::Server::http(&address)
+        .map_err(|error| format!("could not listen on {}: {}", address, error))
+        .unwrap_or_exit();
+    println!("Listening on http://{}", server.server_addr());
+    aoc_server::serve(server);
+}
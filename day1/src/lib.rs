@@ -0,0 +1,1127 @@
+//! Day 1: Report Repair.
+//!
+//! Find the entries in an expense report which sum to 2020, and multiply them together.
+//! `find_k_values_sum` finds any number of entries with any sum, and `k_sum_solutions` and
+//! `count_k_sum_solutions` list or count every such choice of entries. `find_subset_sum` finds the
+//! fewest entries, of any number, with a given sum. `EntryIndex` finds pairs and triplets among
+//! entries which arrive one at a time, such as the lines of a stream.
+//!
+//! `find_k_values_sum`, `checked_product` and `EntryIndex` work with any `Entry` type, from `u8` to
+//! `u128`, or with `BigInt` when the `bigint` feature is on. Their sums and products are checked, so
+//! entries which are too large for the type give an error instead of a wrong answer.
+
+use std::{any::type_name, collections::{BTreeMap, HashMap}, error, fmt, hash::Hash, io::{self, BufRead}, str::FromStr};
+use aoc_common::{combinators::{integer, lines, parse_all, Parser}, InputError, InputSource, ParseError, Solution};
+use num_traits::{CheckedAdd, CheckedMul, CheckedSub, FromPrimitive, One, Zero};
+use tracing::{debug, instrument};
+
+#[cfg(feature = "bigint")]
+pub use num_bigint::BigInt;
+
+/// The sum which the entries in each part must add up to.
+pub const TARGET_SUM: u32 = 2020;
+
+/// An integer type which expense entries can be, such as `u32`, `i64` or `u128`, or `BigInt` with
+/// the `bigint` feature. Entries are never negative, even if the type can be.
+pub trait Entry: Clone + Ord + Hash + fmt::Debug + fmt::Display + FromStr + Zero + One + CheckedAdd + CheckedSub
+    + CheckedMul + FromPrimitive {}
+
+impl<T> Entry for T where T: Clone + Ord + Hash + fmt::Debug + fmt::Display + FromStr + Zero + One + CheckedAdd
+    + CheckedSub + CheckedMul + FromPrimitive {}
+
+/// Why entries can't be added up or multiplied.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EntryError {
+    /// An entry, or the sum it should add up to, is negative. Holds the value.
+    Negative(String),
+    /// A result doesn't fit in the type of the entries. Holds a description of the result.
+    Overflow(String),
+    /// A target is too large to look for. Holds a description of the target.
+    TooLarge(String),
+}
+
+impl fmt::Display for EntryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntryError::Negative(value) => write!(f, "entries can't be negative, found {}", value),
+            EntryError::Overflow(what) => write!(f, "overflow: {}", what),
+            EntryError::TooLarge(what) => write!(f, "too large: {}", what),
+        }
+    }
+}
+
+impl error::Error for EntryError {}
+
+/// Parse the expense report, one entry per line, into a sorted list of entries.
+pub fn parse_expense_report(text: &str) -> Result<Vec<u32>, ParseError> {
+    parse_expense_report_as(text)
+}
+
+/// Parse the expense report into a sorted list of entries of type `T`. A negative entry is an error.
+pub fn parse_expense_report_as<T: Entry>(text: &str) -> Result<Vec<T>, ParseError> {
+    let mut numbers = parse_all(text, lines(entry::<T>()))?;
+    numbers.sort();
+    Ok(numbers)
+}
+
+/// Parse one entry of the expense report, which is on line `line_number` (counting from 1).
+/// This is for reading the report line by line, e.g. with `read_lines`.
+pub fn parse_entry(line: &str, line_number: usize) -> Result<u32, ParseError> {
+    parse_entry_as(line, line_number)
+}
+
+/// Parse one entry of the expense report, of type `T`, which is on line `line_number`.
+pub fn parse_entry_as<T: Entry>(line: &str, line_number: usize) -> Result<T, ParseError> {
+    parse_all(line.trim(), entry::<T>()).map_err(|error| error.on_line(line_number))
+}
+
+fn entry<'a, T: Entry>() -> impl Parser<'a, T> {
+    integer::<T>().verify(|value| value >= &T::zero(), "an entry of at least 0")
+}
+
+/// The largest number of entries which `find_k_values_sum` looks for by choosing one entry at a time.
+/// Above this, it splits the entries it looks for into two halves, and meets in the middle.
+pub const MAX_DIRECT_K: usize = 6;
+
+/// The names of the entries in each part's detailed solution.
+const ENTRY_NAMES: [&str; 3] = ["entry_1", "entry_2", "entry_3"];
+
+/// Solve part 1: the product of the two entries which sum to 2020.
+///
+/// # Panics
+/// Panics if no pair of entries sums to 2020.
+#[instrument(skip(text), ret)]
+pub fn solve_part_1(text: &str) -> Result<u32, ParseError> {
+    Ok(product(&find_entries(text, 2)?))
+}
+
+/// Solve part 2: the product of the three entries which sum to 2020.
+///
+/// # Panics
+/// Panics if no triplet of entries sums to 2020.
+#[instrument(skip(text), ret)]
+pub fn solve_part_2(text: &str) -> Result<u32, ParseError> {
+    Ok(product(&find_entries(text, 3)?))
+}
+
+/// Solve part 1, and also give the two entries which sum to 2020.
+///
+/// # Panics
+/// Panics if no pair of entries sums to 2020.
+#[instrument(skip(text), ret)]
+pub fn solve_part_1_detailed(text: &str) -> Result<Solution, ParseError> {
+    Ok(detailed_solution(&find_entries(text, 2)?))
+}
+
+/// Solve part 2, and also give the three entries which sum to 2020.
+///
+/// # Panics
+/// Panics if no triplet of entries sums to 2020.
+#[instrument(skip(text), ret)]
+pub fn solve_part_2_detailed(text: &str) -> Result<Solution, ParseError> {
+    Ok(detailed_solution(&find_entries(text, 3)?))
+}
+
+/// Find `k` entries of the expense report which sum to 2020.
+///
+/// # Panics
+/// Panics if there are no such entries.
+fn find_entries(text: &str, k: usize) -> Result<Vec<u32>, ParseError> {
+    let numbers = parse_expense_report(text)?;
+    Ok(find_k_values_sum(TARGET_SUM, k, &numbers)
+        .expect("u32 entries are never negative")
+        .unwrap_or_else(|| panic!("no {} numbers add to {}", k, TARGET_SUM)))
+}
+
+fn product(entries: &[u32]) -> u32 {
+    // At most three entries which add up to 2020 multiply to at most 673 * 673 * 674.
+    checked_product(entries).expect("the product of the entries fits in u32")
+}
+
+fn detailed_solution(entries: &[u32]) -> Solution {
+    entries
+        .iter()
+        .zip(ENTRY_NAMES.iter())
+        .fold(Solution::new(product(entries)), |solution, (&entry, &name)| solution.with(name, entry))
+}
+
+/// Find `k` values in `numbers`, at different positions, which add up to `target`.
+/// Returns the values in ascending order, or `None` if there are no such values.
+/// `numbers` doesn't need to be sorted, and can be of any `Entry` type. Sums which don't fit in
+/// the type are more than `target`, so they can't overflow, but negative values are an error.
+///
+/// Up to `MAX_DIRECT_K` values, this chooses the smallest value and looks for the other `k - 1`
+/// among the larger ones, down to a two-pointer search for the last two, so it runs in
+/// O(n^(k-1)) time at worst, and much faster when the sums rule most choices out.
+/// For larger `k`, it finds every sum of the first `k / 2` values in a hash map, and looks up what
+/// each choice of the other values needs, in O(n^(k - k/2)) time and O(n^(k/2)) space.
+pub fn find_k_values_sum<T: Entry>(target: T, k: usize, numbers: &[T]) -> Result<Option<Vec<T>>, EntryError> {
+    if let Some(negative) = std::iter::once(&target).chain(numbers).find(|&value| value < &T::zero()) {
+        return Err(EntryError::Negative(negative.to_string()));
+    }
+    let mut sorted = numbers.to_vec();
+    sorted.sort_unstable();
+    let values = if k <= MAX_DIRECT_K {
+        find_sorted(&target, k, &sorted)
+    } else {
+        meet_in_the_middle(&target, k, &sorted)
+    };
+    if let Some(values) = &values {
+        debug!(?values, %target, "found values with the target sum");
+    }
+    Ok(values)
+}
+
+/// Multiply `entries` together, or fail if the product doesn't fit in `T`.
+pub fn checked_product<T: Entry>(entries: &[T]) -> Result<T, EntryError> {
+    entries.iter().try_fold(T::one(), |product, entry| product.checked_mul(entry)).ok_or_else(|| {
+        let factors = entries.iter().map(|entry| entry.to_string()).collect::<Vec<String>>();
+        EntryError::Overflow(format!("the product {} doesn't fit in `{}`", factors.join(" * "), type_name::<T>()))
+    })
+}
+
+/// `value * k`, or `None` if that doesn't fit in `T`.
+fn times<T: Entry>(value: &T, k: usize) -> Option<T> {
+    if value.is_zero() {
+        return Some(T::zero());
+    }
+    T::from_usize(k)?.checked_mul(value)
+}
+
+/// The sum of `values`, or `None` if that doesn't fit in `T`.
+fn checked_sum<'a, T: Entry + 'a>(values: impl IntoIterator<Item = &'a T>) -> Option<T> {
+    values.into_iter().try_fold(T::zero(), |sum, value| sum.checked_add(value))
+}
+
+/// Whether `sum`, where `None` is too large for `T`, is more than `limit`.
+fn exceeds<T: Entry>(sum: &Option<T>, limit: &T) -> bool {
+    sum.as_ref().is_none_or(|sum| sum > limit)
+}
+
+/// Find `k` values in the ascending slice `sorted` of non-negative values which add up to
+/// `target`, one value at a time.
+fn find_sorted<T: Entry>(target: &T, k: usize, sorted: &[T]) -> Option<Vec<T>> {
+    match k {
+        0 => return if target.is_zero() { Some(Vec::new()) } else { None },
+        1 => return sorted.binary_search(target).ok().map(|i| vec![sorted[i].clone()]),
+        2 => return find_pair(target, sorted),
+        _ => {}
+    }
+    if sorted.len() < k {
+        return None;
+    }
+    // The most that the other k - 1 values can add up to.
+    let largest_rest = checked_sum(&sorted[sorted.len() - (k - 1)..]);
+    for (i, x) in sorted[..=sorted.len() - k].iter().enumerate() {
+        // Every later choice is at least as large as x, so none of them can work either.
+        if exceeds(&times(x, k), target) {
+            break;
+        }
+        // Choosing an equal value again would only repeat the same search.
+        let most = largest_rest.as_ref().and_then(|rest| rest.checked_add(x));
+        if most.as_ref().is_some_and(|most| most < target) || (i > 0 && &sorted[i - 1] == x) {
+            continue;
+        }
+        // x is at most target / k, so this doesn't underflow.
+        let rest_target = target.checked_sub(x)?;
+        if let Some(mut rest) = find_sorted(&rest_target, k - 1, &sorted[i + 1..]) {
+            rest.insert(0, x.clone());
+            return Some(rest);
+        }
+    }
+    None
+}
+
+/// Find two values in the ascending slice `sorted` which add up to `target`, by moving a pointer in
+/// from each end.
+fn find_pair<T: Entry>(target: &T, sorted: &[T]) -> Option<Vec<T>> {
+    let (mut low, mut high) = (0, sorted.len().checked_sub(1)?);
+    while low < high {
+        let sum = sorted[low].checked_add(&sorted[high]);
+        if sum.as_ref() == Some(target) {
+            return Some(vec![sorted[low].clone(), sorted[high].clone()]);
+        } else if exceeds(&sum, target) {
+            high -= 1;
+        } else {
+            low += 1;
+        }
+    }
+    None
+}
+
+/// Find `k` values in the ascending slice `sorted` which add up to `target`, by splitting them into
+/// the first `k / 2` values by position and the rest.
+fn meet_in_the_middle<T: Entry>(target: &T, k: usize, sorted: &[T]) -> Option<Vec<T>> {
+    let first_k = k / 2;
+    // For each sum of `first_k` values, the positions of the values which end earliest.
+    let mut first_halves = HashMap::<T, Vec<usize>>::new();
+    for_each_combination(sorted, first_k, 0, target, &mut Vec::new(), &mut |positions, sum| {
+        let earliest = first_halves.entry(sum.clone()).or_insert_with(|| positions.to_vec());
+        if positions.last() < earliest.last() {
+            *earliest = positions.to_vec();
+        }
+        false
+    });
+
+    let mut found = None;
+    for_each_combination(sorted, k - first_k, 0, target, &mut Vec::new(), &mut |positions, sum| {
+        // Both halves together are k values if the first half ends before the second half starts.
+        // The sum is at most the target, so this doesn't underflow.
+        match target.checked_sub(sum).and_then(|needed| first_halves.get(&needed)) {
+            Some(first_half) if first_half.last() < positions.first() => {
+                found = Some(first_half.iter().chain(positions).map(|&i| sorted[i].clone()).collect());
+                true
+            }
+            _ => false,
+        }
+    });
+    found
+}
+
+/// Call `f` with each choice of `k` positions from `start` onwards in the ascending slice `sorted`,
+/// whose values add up to at most `limit`, and with the sum of those values. `chosen` holds the
+/// positions chosen so far. Stops, and returns true, as soon as `f` returns true.
+fn for_each_combination<T: Entry>(sorted: &[T], k: usize, start: usize, limit: &T, chosen: &mut Vec<usize>,
+        f: &mut dyn FnMut(&[usize], &T) -> bool) -> bool {
+    // The sum of the chosen values is at most the limit, so it fits in T.
+    let chosen_sum = checked_sum(chosen.iter().map(|&i| &sorted[i])).expect("the chosen values add up to at most the limit");
+    if k == 0 {
+        return f(chosen, &chosen_sum);
+    }
+    for i in start..sorted.len().saturating_sub(k - 1) {
+        if exceeds(&times(&sorted[i], k).and_then(|least| least.checked_add(&chosen_sum)), limit) {
+            break;
+        }
+        chosen.push(i);
+        let stop = for_each_combination(sorted, k - 1, i + 1, limit, chosen, f);
+        chosen.pop();
+        if stop {
+            return true;
+        }
+    }
+    false
+}
+
+/// Iterate over every choice of `k` positions in `numbers` whose values add up to `target`.
+/// Each solution is the indices of its values in `numbers`, in ascending order, and each set of
+/// indices is given once, so equal values at different positions make different solutions.
+/// The solutions are found as they are needed, so there is no limit to how many there can be.
+pub fn k_sum_solutions(target: u32, k: usize, numbers: &[u32]) -> KSumSolutions<'_> {
+    let mut order = (0..numbers.len()).collect::<Vec<usize>>();
+    order.sort_by_key(|&i| numbers[i]);
+    let mut prefix = vec![0];
+    for &i in &order {
+        prefix.push(prefix.last().unwrap() + numbers[i] as u64);
+    }
+    KSumSolutions { numbers, order, prefix, target: target as u64, k, chosen: Vec::new(), sum: 0, next: Some(0) }
+}
+
+/// Iterator over the solutions of a k-sum problem. See `k_sum_solutions`.
+pub struct KSumSolutions<'a> {
+    numbers: &'a [u32],
+    /// The indices of `numbers`, in ascending order of their values.
+    order: Vec<usize>,
+    /// `prefix[i]` is the sum of the `i` smallest values.
+    prefix: Vec<u64>,
+    target: u64,
+    k: usize,
+    /// The positions in `order` of the values chosen so far, in ascending order.
+    chosen: Vec<usize>,
+    /// The sum of the values chosen so far, which is never more than `target`.
+    sum: u64,
+    /// The next position in `order` to try choosing, or `None` once every choice has been tried.
+    next: Option<usize>,
+}
+
+impl KSumSolutions<'_> {
+    fn value(&self, position: usize) -> u64 {
+        self.numbers[self.order[position]] as u64
+    }
+
+    /// Undo the last choice, and try the next position instead. There is nothing left to try if
+    /// nothing was chosen.
+    fn backtrack(&mut self) {
+        self.next = self.chosen.pop().map(|position| {
+            self.sum -= self.value(position);
+            position + 1
+        });
+    }
+}
+
+impl Iterator for KSumSolutions<'_> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        let n = self.order.len();
+        loop {
+            let mut position = self.next?;
+            if self.chosen.len() == self.k {
+                let solution = if self.sum == self.target {
+                    let mut indices = self.chosen.iter().map(|&position| self.order[position]).collect::<Vec<usize>>();
+                    indices.sort_unstable();
+                    Some(indices)
+                } else {
+                    None
+                };
+                self.backtrack();
+                match solution {
+                    Some(indices) => return Some(indices),
+                    None => continue,
+                }
+            }
+
+            let remaining = self.k - self.chosen.len();
+            let needed = self.target - self.sum;
+            if remaining == 1 {
+                // Skip straight to the first value which could be the last one needed.
+                position += self.order[position..].partition_point(|&i| (self.numbers[i] as u64) < needed);
+            }
+            // Choosing `remaining` more values from `position` on gives at least the smallest of them,
+            // and no choice at all gives more than the largest values.
+            if position + remaining > n
+                || self.prefix[position + remaining] - self.prefix[position] > needed
+                || self.prefix[n] - self.prefix[n - remaining] < needed {
+                self.backtrack();
+                continue;
+            }
+            self.chosen.push(position);
+            self.sum += self.value(position);
+            self.next = Some(position + 1);
+        }
+    }
+}
+
+/// Count the choices of `k` positions in `numbers` whose values add up to `target`, which are the
+/// solutions given by `k_sum_solutions`, without finding each one. Equal values are counted
+/// together, e.g. any 2 of 4 equal values are C(4, 2) = 6 solutions, so this is much faster
+/// when there are many of them. The count saturates at `u64::MAX`.
+pub fn count_k_sum_solutions(target: u32, k: usize, numbers: &[u32]) -> u64 {
+    let mut sorted = numbers.to_vec();
+    sorted.sort_unstable();
+    // Each distinct value, with the number of times it appears.
+    let mut groups = Vec::<(u64, u64)>::new();
+    for value in sorted {
+        match groups.last_mut() {
+            Some((last, count)) if *last == value as u64 => *count += 1,
+            _ => groups.push((value as u64, 1)),
+        }
+    }
+    count_in_groups(target as u64, k as u64, &groups)
+}
+
+/// Count the ways to choose `k` values which add up to `target` from the ascending `groups` of
+/// equal values, given as (value, number of times it appears).
+fn count_in_groups(target: u64, k: u64, groups: &[(u64, u64)]) -> u64 {
+    match k {
+        0 => return (target == 0) as u64,
+        1 => return groups.binary_search_by_key(&target, |&(value, _)| value).map_or(0, |i| groups[i].1),
+        2 => return count_pairs_in_groups(target, groups),
+        _ => {}
+    }
+    let mut total = 0u64;
+    for (j, &(value, count)) in groups.iter().enumerate() {
+        // Every later value is larger, so k of them add up to even more.
+        if value.saturating_mul(k) > target {
+            break;
+        }
+        // Choose `taken` copies of this value, and the rest from the larger values.
+        for taken in 1..=count.min(k) {
+            if value * taken > target {
+                break;
+            }
+            let rest = count_in_groups(target - value * taken, k - taken, &groups[j + 1..]);
+            total = total.saturating_add(binomial(count, taken).saturating_mul(rest));
+        }
+    }
+    total
+}
+
+/// Count the ways to choose two values which add up to `target` from the ascending `groups`, by
+/// moving a pointer in from each end.
+fn count_pairs_in_groups(target: u64, groups: &[(u64, u64)]) -> u64 {
+    let (mut low, mut high) = match groups.len() {
+        0 => return 0,
+        len => (0, len - 1),
+    };
+    let mut total = 0u64;
+    while low <= high {
+        let ((low_value, low_count), (high_value, high_count)) = (groups[low], groups[high]);
+        let sum = low_value + high_value;
+        if sum == target {
+            let pairs = if low == high { binomial(low_count, 2) } else { low_count * high_count };
+            total = total.saturating_add(pairs);
+        }
+        if sum <= target {
+            low += 1;
+        }
+        if sum >= target {
+            if high == 0 {
+                break;
+            }
+            high -= 1;
+        }
+    }
+    total
+}
+
+/// The number of ways to choose `r` of `n` things, saturating at `u64::MAX`.
+fn binomial(n: u64, r: u64) -> u64 {
+    if r > n {
+        return 0;
+    }
+    let r = r.min(n - r);
+    let mut result: u128 = 1;
+    for i in 0..r {
+        // Each partial result is itself a binomial coefficient, so the division is exact.
+        result = result * (n - i) as u128 / (i + 1) as u128;
+        if result > u64::MAX as u128 {
+            return u64::MAX;
+        }
+    }
+    result as u64
+}
+
+/// The smallest choice of entries which add up to a target, found by `find_subset_sum`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubsetSum {
+    /// One of the smallest subsets of the entries which add up to the target, in ascending order.
+    pub entries: Vec<u32>,
+    /// The product of `entries`.
+    pub product: u128,
+    /// The (size, product) of each subset which is not beaten by another which is no larger and has
+    /// at least as large a product, in ascending order of size. So each larger subset on the list
+    /// has a larger product, and the first is the size of `entries`, with the largest product of
+    /// any subset of that size.
+    pub pareto: Vec<(usize, u128)>,
+}
+
+/// The largest target which `subset_sum_exists` looks for, so that its bitset takes at most 128 MiB.
+pub const MAX_BITSET_TARGET: u32 = 1 << 30;
+
+/// Whether any subset of `numbers`, of any size, adds up to `target`. The empty subset adds up to 0.
+/// Takes O(n * target / 64) time, with a bitset of the reachable sums, which takes `target / 8`
+/// bytes. Fails if `target` is more than `MAX_BITSET_TARGET`, unless the entries add up to less.
+///
+/// Unlike `find_subset_sum`, which needs a product and a parent for each sum, this only needs to
+/// know which sums are reachable, and a bit for each is much smaller than a map entry whenever
+/// more than a few of the sums are reachable.
+pub fn subset_sum_exists(target: u32, numbers: &[u32]) -> Result<bool, EntryError> {
+    if numbers.iter().map(|&value| value as u64).sum::<u64>() < target as u64 {
+        return Ok(false);
+    }
+    if target > MAX_BITSET_TARGET {
+        return Err(EntryError::TooLarge(format!("the target {} is more than {}, the largest which a bitset is made for",
+            target, MAX_BITSET_TARGET)));
+    }
+    let mut reachable = Bitset::new(target as usize + 1);
+    reachable.insert(0);
+    for &value in numbers {
+        reachable.add_shifted(value as usize);
+    }
+    Ok(reachable.contains(target as usize))
+}
+
+/// Find the smallest subset of `numbers` which adds up to `target`, or `None` if no subset does.
+/// Also finds the Pareto set of subset sizes and products: how much larger a subset has to be to
+/// give a larger product. `numbers` doesn't need to be sorted.
+///
+/// This is a pseudo-polynomial dynamic program over the sums up to `target` which each number of
+/// entries can reach. Only the reachable sums are kept, in a map for each size, so it takes
+/// O(n * m * r) time and O(m * r) space, where `m` is the most entries which can add up to
+/// `target` and `r` is the number of sums up to `target` which any subset reaches. As `r` is at
+/// most `target + 1` and at most 2^n, a few large entries are as cheap as many small ones.
+///
+/// Fails if the product of `entries`, or a product in the Pareto set, doesn't fit in a `u128`.
+pub fn find_subset_sum(target: u32, numbers: &[u32]) -> Result<Option<SubsetSum>, EntryError> {
+    let mut sorted = numbers.to_vec();
+    sorted.sort_unstable();
+    let target = target as usize;
+    // No subset is larger than the smallest values which add up to at most the target.
+    let mut max_size = 0;
+    let mut smallest_sum = 0;
+    for &value in &sorted {
+        smallest_sum += value as usize;
+        if smallest_sum > target {
+            break;
+        }
+        max_size += 1;
+    }
+
+    let layers = reachable_by_size(target, max_size, &sorted);
+    let size = match layers.iter().position(|layer| layer.contains_key(&target)) {
+        Some(size) => size,
+        None => return Ok(None),
+    };
+    let entries = smallest_subset(&layers, target, size, &sorted);
+    let product = checked_product(&entries.iter().map(|&value| value as u128).collect::<Vec<u128>>())?;
+    let mut pareto = Vec::<(usize, u128)>::new();
+    for (size, layer) in layers.iter().enumerate().skip(size) {
+        if let Some(reach) = layer.get(&target) {
+            // A product which doesn't fit is larger than every product before it, so it is in the set.
+            let product = reach.product.ok_or_else(|| EntryError::Overflow(format!(
+                "the largest product of {} entries which add up to {} doesn't fit in `u128`", size, target)))?;
+            if pareto.last().is_none_or(|&(_, largest)| product > largest) {
+                pareto.push((size, product));
+            }
+        }
+    }
+    debug!(?entries, ?pareto, target, "found the smallest subset with the target sum");
+    Ok(Some(SubsetSum { entries, product, pareto }))
+}
+
+/// How some number of values from a sorted list add up to a sum.
+#[derive(Debug, Clone, Copy)]
+struct Reach {
+    /// The index of the last value of the first subset found with this size and sum. The rest of
+    /// that subset reaches the sum minus this value with one value fewer, using only earlier values.
+    last: usize,
+    /// The largest product of the values of any subset with this size and sum, or `None` if it
+    /// doesn't fit in a `u128`.
+    product: Option<u128>,
+}
+
+/// For each number of values from `sorted`, up to `max_size`, the sums up to `target` which that
+/// many values can add up to, and how.
+fn reachable_by_size(target: usize, max_size: usize, sorted: &[u32]) -> Vec<HashMap<usize, Reach>> {
+    let mut layers = vec![HashMap::new(); max_size + 1];
+    layers[0].insert(0, Reach { last: 0, product: Some(1) });
+    for (i, &value) in sorted.iter().enumerate() {
+        let value = value as usize;
+        if value > target {
+            break;
+        }
+        // Going down through the sizes uses each value at most once.
+        for c in (0..max_size.min(i + 1)).rev() {
+            let (smaller, larger) = layers.split_at_mut(c + 1);
+            for (&sum, reach) in &smaller[c] {
+                if value > target - sum {
+                    continue;
+                }
+                let product = match value {
+                    0 => Some(0),
+                    _ => reach.product.and_then(|product| product.checked_mul(value as u128)),
+                };
+                larger[0]
+                    .entry(sum + value)
+                    .and_modify(|best| {
+                        if best.product.is_some_and(|best| product.is_none_or(|product| product > best)) {
+                            best.product = product;
+                        }
+                    })
+                    .or_insert(Reach { last: i, product });
+            }
+        }
+    }
+    layers
+}
+
+/// Find `size` values in `sorted` which add up to `target`, when `layers` shows that some do,
+/// by following the last value of each subset back to the empty one.
+fn smallest_subset(layers: &[HashMap<usize, Reach>], target: usize, size: usize, sorted: &[u32]) -> Vec<u32> {
+    let mut sum = target;
+    let mut entries = Vec::with_capacity(size);
+    for layer in layers[1..=size].iter().rev() {
+        let value = sorted[layer[&sum].last];
+        entries.push(value);
+        sum -= value as usize;
+    }
+    entries.reverse();
+    entries
+}
+
+/// A fixed-size set of small numbers, one bit each.
+#[derive(Debug, Clone)]
+struct Bitset {
+    words: Vec<u64>,
+}
+
+impl Bitset {
+    /// An empty set, which can hold the numbers below `len`.
+    fn new(len: usize) -> Self {
+        Bitset { words: vec![0; len.div_ceil(64)] }
+    }
+
+    fn insert(&mut self, i: usize) {
+        self.words[i / 64] |= 1 << (i % 64);
+    }
+
+    fn contains(&self, i: usize) -> bool {
+        self.words.get(i / 64).is_some_and(|word| word & (1 << (i % 64)) != 0)
+    }
+
+    /// Add each number in the set plus `shift`, where it fits. Going down from the highest word
+    /// means every word which is read still holds the numbers from before the call.
+    fn add_shifted(&mut self, shift: usize) {
+        let (word_shift, bit_shift) = (shift / 64, shift % 64);
+        for i in (word_shift..self.words.len()).rev() {
+            let from = i - word_shift;
+            let mut word = self.words[from] << bit_shift;
+            if bit_shift > 0 && from > 0 {
+                word |= self.words[from - 1] >> (64 - bit_shift);
+            }
+            self.words[i] |= word;
+        }
+    }
+}
+
+/// Entries which arrive one at a time, which finds the pairs and triplets of them with a given sum.
+/// Entries can be removed again, and equal entries are kept as many times as they are inserted.
+///
+/// A pair or triplet which adds up to the target only appears when one of its entries is inserted,
+/// so checking each entry with `pair_with` and `triple_with` before inserting it, in O(log n) and
+/// O(n log n) time, finds a solution as soon as there is one.
+#[derive(Clone, Debug)]
+pub struct EntryIndex<T> {
+    /// How many times each entry has been inserted, for the entries which are in the index.
+    counts: BTreeMap<T, usize>,
+    len: usize,
+}
+
+impl<T: Entry> Default for EntryIndex<T> {
+    fn default() -> Self {
+        EntryIndex { counts: BTreeMap::new(), len: 0 }
+    }
+}
+
+impl<T: Entry> EntryIndex<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of entries, counting equal entries separately.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// How many times `value` is in the index.
+    pub fn count(&self, value: &T) -> usize {
+        self.counts.get(value).copied().unwrap_or(0)
+    }
+
+    /// The entries in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.counts.iter().flat_map(|(value, &count)| std::iter::repeat_n(value, count))
+    }
+
+    /// Add an entry. Fails if it is negative.
+    pub fn insert(&mut self, value: T) -> Result<(), EntryError> {
+        if value < T::zero() {
+            return Err(EntryError::Negative(value.to_string()));
+        }
+        *self.counts.entry(value).or_insert(0) += 1;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Remove one copy of an entry. Returns whether it was in the index.
+    pub fn remove(&mut self, value: &T) -> bool {
+        match self.counts.get_mut(value) {
+            Some(count) if *count > 1 => *count -= 1,
+            Some(_) => {
+                self.counts.remove(value);
+            }
+            None => return false,
+        }
+        self.len -= 1;
+        true
+    }
+
+    /// Find two entries which add up to `target`, in ascending order, in O(n log n) time.
+    pub fn query_pair(&self, target: &T) -> Option<Vec<T>> {
+        self.pair_from(target, &T::zero(), None)
+    }
+
+    /// Find three entries which add up to `target`, in ascending order, in O(n^2 log n) time.
+    pub fn query_triple(&self, target: &T) -> Option<Vec<T>> {
+        for a in self.counts.keys() {
+            if exceeds(&times(a, 3), target) {
+                break;
+            }
+            let rest = target.checked_sub(a)?;
+            if let Some(mut pair) = self.pair_from(&rest, a, Some(a)) {
+                pair.insert(0, a.clone());
+                return Some(pair);
+            }
+        }
+        None
+    }
+
+    /// Find an entry which adds up to `target` with `value`, which is not one of the entries, and
+    /// give them both in ascending order.
+    pub fn pair_with(&self, value: &T, target: &T) -> Option<Vec<T>> {
+        let other = target.checked_sub(value)?;
+        if self.count(&other) == 0 {
+            return None;
+        }
+        let mut pair = vec![value.clone(), other];
+        pair.sort();
+        Some(pair)
+    }
+
+    /// Find two entries which add up to `target` with `value`, which is not one of the entries, and
+    /// give all three in ascending order.
+    pub fn triple_with(&self, value: &T, target: &T) -> Option<Vec<T>> {
+        let rest = target.checked_sub(value)?;
+        let mut triple = self.pair_from(&rest, &T::zero(), None)?;
+        triple.push(value.clone());
+        triple.sort();
+        Some(triple)
+    }
+
+    /// Find two entries, each at least `least`, which add up to `target`, in ascending order. If
+    /// `taken` is an entry, one copy of it is already used, so it must be in the index too.
+    fn pair_from(&self, target: &T, least: &T, taken: Option<&T>) -> Option<Vec<T>> {
+        for b in self.counts.range(least.clone()..).map(|(b, _)| b) {
+            // b is the smaller of the two, so once it passes half of the target there are no more pairs.
+            let c = target.checked_sub(b)?;
+            if &c < b {
+                break;
+            }
+            let wanted = [Some(b), Some(&c), taken];
+            if self.holds(&wanted.iter().flatten().copied().collect::<Vec<&T>>()) {
+                return Some(vec![b.clone(), c]);
+            }
+        }
+        None
+    }
+
+    /// Whether the index has all of `values`, with as many copies of each as are in `values`.
+    fn holds(&self, values: &[&T]) -> bool {
+        values.iter().all(|value| values.iter().filter(|&other| other == value).count() <= self.count(value))
+    }
+}
+
+/// Open the input and return an iterator over its lines.
+/// The output is wrapped in a Result to allow matching on errors.
+pub fn read_lines(source: &InputSource) -> Result<io::Lines<Box<dyn BufRead>>, InputError> {
+    Ok(source.open()?.lines())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "1721\n979\n366\n299\n675\n1456\n";
+
+    /// Every choice of entries from `numbers`, of any number of them, which add up to `target`, as
+    /// ascending indices into `numbers`.
+    fn brute_force(target: u32, numbers: &[u32]) -> Vec<Vec<usize>> {
+        (0u32..(1 << numbers.len()))
+            .map(|mask| (0..numbers.len()).filter(|&i| mask & (1 << i) != 0).collect::<Vec<usize>>())
+            .filter(|indices| indices.iter().map(|&i| numbers[i] as u64).sum::<u64>() == target as u64)
+            .collect()
+    }
+
+    /// The entries of `numbers` at `indices`, in ascending order.
+    fn values_at(indices: &[usize], numbers: &[u32]) -> Vec<u32> {
+        let mut values = indices.iter().map(|&i| numbers[i]).collect::<Vec<u32>>();
+        values.sort();
+        values
+    }
+
+    #[test]
+    fn test_find_pair() {
+        let numbers = parse_expense_report(EXAMPLE).unwrap();
+        assert_eq!(find_k_values_sum(2020, 2, &numbers), Ok(Some(vec![299, 1721])));
+        assert_eq!(find_k_values_sum(1, 2, &numbers), Ok(None));
+        assert_eq!(solve_part_1(EXAMPLE), Ok(514579));
+        assert_eq!(solve_part_1_detailed(EXAMPLE), Ok(Solution::new(514579).with("entry_1", 299).with("entry_2", 1721)));
+    }
+
+    #[test]
+    fn test_find_triplet() {
+        let numbers = parse_expense_report(EXAMPLE).unwrap();
+        assert_eq!(find_k_values_sum(2020, 3, &numbers), Ok(Some(vec![366, 675, 979])));
+        assert_eq!(find_k_values_sum(1, 3, &numbers), Ok(None));
+        assert_eq!(solve_part_2(EXAMPLE), Ok(241861950));
+    }
+
+    #[test]
+    fn test_find_k_values_sum() {
+        let numbers = [1721, 979, 366, 299, 675, 1456];
+        assert_eq!(find_k_values_sum(0, 0, &numbers), Ok(Some(vec![])));
+        assert_eq!(find_k_values_sum(1, 0, &numbers), Ok(None));
+        assert_eq!(find_k_values_sum(979, 1, &numbers), Ok(Some(vec![979])));
+        assert_eq!(find_k_values_sum(5496, 6, &numbers), Ok(Some(vec![299, 366, 675, 979, 1456, 1721])));
+        assert_eq!(find_k_values_sum(5496, 7, &numbers), Ok(None));
+        // Each entry can only be used once.
+        assert_eq!(find_k_values_sum(2020, 2, &[1010]), Ok(None));
+        assert_eq!(find_k_values_sum(2020, 2, &[1010, 1010]), Ok(Some(vec![1010, 1010])));
+        assert_eq!(find_k_values_sum(u32::MAX, 2, &[u32::MAX, 0, 1]), Ok(Some(vec![0, u32::MAX])));
+    }
+
+    #[test]
+    fn test_meet_in_the_middle() {
+        let numbers = (1..=40).collect::<Vec<u32>>();
+        let values = find_k_values_sum(2 + 3 + 5 + 7 + 11 + 13 + 17 + 19 + 23, 9, &numbers).unwrap().unwrap();
+        assert_eq!(values.len(), 9);
+        assert_eq!(values.iter().sum::<u32>(), 100);
+        assert!(values.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(find_k_values_sum(44, 9, &numbers), Ok(None));
+        assert_eq!(find_k_values_sum(45, 9, &numbers), Ok(Some((1..=9).collect())));
+        assert_eq!(find_k_values_sum(8, 8, &[1; 8]), Ok(Some(vec![1; 8])));
+        assert_eq!(find_k_values_sum(8, 8, &[1; 7]), Ok(None));
+    }
+
+    #[test]
+    fn test_k_sum_solutions() {
+        let numbers = [1721, 979, 366, 299, 675, 1456];
+        assert_eq!(k_sum_solutions(2020, 2, &numbers).collect::<Vec<_>>(), vec![vec![0, 3]]);
+        assert_eq!(count_k_sum_solutions(2020, 3, &numbers), 1);
+        // A value can't be paired with itself, but it can be paired with an equal value elsewhere.
+        assert_eq!(k_sum_solutions(2020, 2, &[1010, 7]).count(), 0);
+        let numbers = [1010, 5, 1010, 2015, 1010];
+        assert_eq!(k_sum_solutions(2020, 2, &numbers).collect::<Vec<_>>(),
+            vec![vec![1, 3], vec![0, 2], vec![0, 4], vec![2, 4]]);
+        assert_eq!(count_k_sum_solutions(2020, 2, &numbers), 4);
+        assert_eq!(k_sum_solutions(0, 0, &numbers).collect::<Vec<_>>(), vec![Vec::<usize>::new()]);
+        assert_eq!(count_k_sum_solutions(0, 0, &numbers), 1);
+        assert_eq!(count_k_sum_solutions(1, 0, &numbers), 0);
+    }
+
+    #[test]
+    fn test_count_many_solutions() {
+        // Any 3 of 60 ones, and any 3 of 100 zeros with 3 ones: more solutions than are worth listing.
+        assert_eq!(count_k_sum_solutions(3, 3, &[1; 60]), 34220);
+        let numbers = [vec![0; 100], vec![1; 3]].concat();
+        assert_eq!(count_k_sum_solutions(3, 6, &numbers), 161700);
+        assert_eq!(k_sum_solutions(3, 6, &numbers).take(10).count(), 10);
+        assert_eq!(count_k_sum_solutions(0, 40, &[0; 200]), u64::MAX);
+        assert_eq!(binomial(1, 2), 0);
+        assert_eq!(binomial(5, 0), 1);
+        assert_eq!(binomial(200, 100), u64::MAX);
+    }
+
+    proptest! {
+        #[test]
+        fn k_values_sum_matches_brute_force(numbers in prop::collection::vec(0u32..50, 0..12), k in 0usize..10,
+                target in 0u32..200) {
+            let all = brute_force(target, &numbers)
+                .iter()
+                .filter(|indices| indices.len() == k)
+                .map(|indices| values_at(indices, &numbers))
+                .collect::<Vec<Vec<u32>>>();
+            match find_k_values_sum(target, k, &numbers).unwrap() {
+                Some(values) => prop_assert!(all.contains(&values), "{:?} is not a solution", values),
+                None => prop_assert!(all.is_empty(), "missed {:?}", all),
+            }
+        }
+
+        #[test]
+        fn k_sum_solutions_match_brute_force(numbers in prop::collection::vec(0u32..20, 0..12), k in 0usize..6,
+                target in 0u32..60) {
+            let solutions = k_sum_solutions(target, k, &numbers).collect::<Vec<Vec<usize>>>();
+            let mut expected = brute_force(target, &numbers)
+                .into_iter()
+                .filter(|indices| indices.len() == k)
+                .collect::<Vec<Vec<usize>>>();
+            let mut sorted = solutions.clone();
+            sorted.sort();
+            expected.sort();
+            prop_assert_eq!(sorted, expected);
+            prop_assert_eq!(count_k_sum_solutions(target, k, &numbers), solutions.len() as u64);
+        }
+    }
+
+    #[test]
+    fn test_find_subset_sum() {
+        let numbers = [1721, 979, 366, 299, 675, 1456];
+        let found = find_subset_sum(2020, &numbers).unwrap().unwrap();
+        assert_eq!(found.entries, vec![299, 1721]);
+        assert_eq!(found.product, 514579);
+        // Both the pair and the triplet of part 2 add up to 2020, and the triplet's product is larger.
+        assert_eq!(found.pareto, vec![(2, 514579), (3, 241861950)]);
+        assert_eq!(subset_sum_exists(2020, &numbers), Ok(true));
+        assert_eq!(subset_sum_exists(2, &numbers), Ok(false));
+        assert_eq!(find_subset_sum(2, &numbers), Ok(None));
+
+        // A smaller subset doesn't always have a smaller product.
+        let found = find_subset_sum(12, &[2, 10, 3, 3, 3, 3, 1]).unwrap().unwrap();
+        assert_eq!(found.entries, vec![2, 10]);
+        assert_eq!(found.pareto, vec![(2, 20), (4, 81)]);
+        let found = find_subset_sum(0, &[5, 0]).unwrap().unwrap();
+        assert_eq!((found.entries, found.product, found.pareto), (vec![], 1, vec![(0, 1)]));
+        // Sums past the end of a bitset word.
+        assert_eq!(find_subset_sum(200, &[70, 131, 1, 199]).unwrap().unwrap().entries, vec![1, 199]);
+        assert_eq!(subset_sum_exists(200, &[70, 131, 1, 199]), Ok(true));
+        assert_eq!(subset_sum_exists(130, &[64, 65, 1, 63]), Ok(true));
+        assert_eq!(subset_sum_exists(192, &[64, 64, 63, 2]), Ok(false));
+        // Only the sums which are reached take up memory, however large the target is.
+        let found = find_subset_sum(u32::MAX, &[u32::MAX, 1, u32::MAX - 1]).unwrap().unwrap();
+        assert_eq!(found.entries, vec![u32::MAX]);
+        assert_eq!(found.pareto, vec![(1, u32::MAX as u128)]);
+        // But a bitset takes memory for every sum, so it has a limit.
+        assert_eq!(subset_sum_exists(u32::MAX, &[1, 2]), Ok(false));
+        assert!(matches!(subset_sum_exists(u32::MAX, &[u32::MAX]), Err(EntryError::TooLarge(_))));
+        assert_eq!(subset_sum_exists(MAX_BITSET_TARGET, &[MAX_BITSET_TARGET, 1]), Ok(true));
+
+        // Products which don't fit in a u128: 10^40, of the only subset, and of the largest one.
+        assert!(matches!(find_subset_sum(400, &[10; 40]), Err(EntryError::Overflow(_))));
+        let numbers = [vec![10; 40], vec![200, 200]].concat();
+        assert_eq!(find_subset_sum(400, &numbers), Err(EntryError::Overflow(
+            "the largest product of 40 entries which add up to 400 doesn't fit in `u128`".to_string())));
+    }
+
+    proptest! {
+        #[test]
+        fn subset_sum_matches_brute_force(numbers in prop::collection::vec(0u32..100, 0..12), target in 0u32..300) {
+            // The largest product of each size of subset which adds up to the target.
+            let mut largest = vec![None::<u128>; numbers.len() + 1];
+            for indices in brute_force(target, &numbers) {
+                let product = indices.iter().map(|&i| numbers[i] as u128).product::<u128>();
+                let best = &mut largest[indices.len()];
+                *best = Some(best.map_or(product, |best| best.max(product)));
+            }
+            prop_assert_eq!(subset_sum_exists(target, &numbers), Ok(largest.iter().any(Option::is_some)));
+            match find_subset_sum(target, &numbers).unwrap() {
+                Some(found) => {
+                    prop_assert_eq!(found.entries.iter().sum::<u32>(), target);
+                    prop_assert_eq!(found.product, found.entries.iter().map(|&value| value as u128).product::<u128>());
+                    prop_assert_eq!(largest.iter().position(Option::is_some), Some(found.entries.len()));
+                    let mut pareto = Vec::new();
+                    for (size, product) in largest.iter().enumerate() {
+                        if let Some(product) = *product {
+                            if pareto.last().is_none_or(|&(_, best)| product > best) {
+                                pareto.push((size, product));
+                            }
+                        }
+                    }
+                    prop_assert_eq!(found.pareto, pareto);
+                }
+                None => prop_assert!(largest.iter().all(Option::is_none)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_entry_types() {
+        let numbers = parse_expense_report_as::<i64>(EXAMPLE).unwrap();
+        assert_eq!(find_k_values_sum(2020, 3, &numbers), Ok(Some(vec![366, 675, 979])));
+        assert_eq!(find_k_values_sum(-1, 2, &numbers), Err(EntryError::Negative("-1".to_string())));
+        assert_eq!(find_k_values_sum(2020, 2, &[1721, -5, 299]), Err(EntryError::Negative("-5".to_string())));
+        let error = parse_expense_report_as::<i64>("1721\n-979\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 1: expected an entry of at least 0, found `-979`");
+        assert_eq!(parse_entry_as::<i32>(" 42 ", 3), Ok(42));
+
+        // Entries bigger than the target, and sums which don't fit in the type.
+        assert_eq!(find_k_values_sum(200u8, 2, &[250, 100, 255, 100]), Ok(Some(vec![100, 100])));
+        assert_eq!(find_k_values_sum(255u8, 3, &[200, 50, 5, 250]), Ok(Some(vec![5, 50, 200])));
+        assert_eq!(find_k_values_sum(255u8, 9, &[200, 34, 3, 3, 3, 3, 3, 3, 3, 250]), Ok(Some(vec![3, 3, 3, 3, 3, 3, 3, 34, 200])));
+        let big = u128::MAX / 2;
+        assert_eq!(find_k_values_sum(u128::MAX, 2, &[big, 1, big + 1, u128::MAX]), Ok(Some(vec![big, big + 1])));
+
+        assert_eq!(checked_product(&[299u32, 1721]), Ok(514579));
+        assert_eq!(checked_product::<u64>(&[]), Ok(1));
+        assert_eq!(checked_product(&[70000u32, 70000]),
+            Err(EntryError::Overflow("the product 70000 * 70000 doesn't fit in `u32`".to_string())));
+        assert_eq!(checked_product(&[70000u64, 70000]), Ok(4_900_000_000));
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_bigint_entries() {
+        let text = "340282366920938463463374607431768211456\n5\n340282366920938463463374607431768211455\n";
+        let numbers = parse_expense_report_as::<BigInt>(text).unwrap();
+        let target = "680564733841876926926749214863536422911".parse::<BigInt>().unwrap();
+        let values = find_k_values_sum(target, 2, &numbers).unwrap().unwrap();
+        assert_eq!(values, vec![numbers[1].clone(), numbers[2].clone()]);
+        assert_eq!(checked_product(&values).unwrap().to_string(),
+            "115792089237316195423570985008687907852929702298719625575994209400481361428480");
+        assert!(parse_expense_report_as::<BigInt>("-1\n").is_err());
+    }
+
+    #[test]
+    fn test_entry_index() {
+        let mut index = EntryIndex::new();
+        let mut first_pair = None;
+        let mut first_triple = None;
+        for (i, &value) in [1721u32, 979, 366, 299, 675, 1456].iter().enumerate() {
+            if first_pair.is_none() && index.pair_with(&value, &2020).is_some() {
+                first_pair = Some(i);
+            }
+            if first_triple.is_none() && index.triple_with(&value, &2020).is_some() {
+                first_triple = Some(i);
+            }
+            index.insert(value).unwrap();
+        }
+        // The pair is complete once 299 arrives, and the triplet once 675 does.
+        assert_eq!((first_pair, first_triple), (Some(3), Some(4)));
+        assert_eq!(index.len(), 6);
+        assert_eq!(index.query_pair(&2020), Some(vec![299, 1721]));
+        assert_eq!(index.query_triple(&2020), Some(vec![366, 675, 979]));
+        assert_eq!(index.triple_with(&979, &2020), Some(vec![366, 675, 979]));
+
+        assert!(index.remove(&299));
+        assert!(!index.remove(&299));
+        assert_eq!(index.query_pair(&2020), None);
+        assert_eq!(index.query_triple(&2020), Some(vec![366, 675, 979]));
+        assert_eq!(index.iter().copied().collect::<Vec<u32>>(), vec![366, 675, 979, 1456, 1721]);
+
+        // Equal entries are only a pair once both are in the index.
+        let mut index = EntryIndex::new();
+        assert_eq!(index.pair_with(&1010, &2020), None);
+        index.insert(1010u64).unwrap();
+        assert_eq!(index.pair_with(&1010, &2020), Some(vec![1010, 1010]));
+        assert_eq!(index.query_pair(&2020), None);
+        index.insert(1010).unwrap();
+        assert_eq!((index.count(&1010), index.query_pair(&2020)), (2, Some(vec![1010, 1010])));
+        assert_eq!(index.query_triple(&3030), None);
+        index.insert(1010).unwrap();
+        assert_eq!(index.query_triple(&3030), Some(vec![1010, 1010, 1010]));
+
+        let mut index = EntryIndex::<i64>::new();
+        assert_eq!(index.insert(-4), Err(EntryError::Negative("-4".to_string())));
+        assert!(index.is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn entry_index_matches_find_k_values_sum(operations in prop::collection::vec((any::<bool>(), 0u32..30), 0..40),
+                target in 0u32..60) {
+            let mut index = EntryIndex::new();
+            let mut entries = Vec::new();
+            for (insert, value) in operations {
+                if insert {
+                    prop_assert_eq!(index.pair_with(&value, &target).is_some(),
+                        entries.contains(&(target.wrapping_sub(value))));
+                    index.insert(value).unwrap();
+                    entries.push(value);
+                } else {
+                    let position = entries.iter().position(|&entry| entry == value);
+                    prop_assert_eq!(index.remove(&value), position.is_some());
+                    if let Some(position) = position {
+                        entries.swap_remove(position);
+                    }
+                }
+                prop_assert_eq!(index.len(), entries.len());
+                for (k, found) in [(2, index.query_pair(&target)), (3, index.query_triple(&target))] {
+                    let expected = find_k_values_sum(target, k, &entries).unwrap();
+                    prop_assert_eq!(found.is_some(), expected.is_some());
+                    if let Some(values) = found {
+                        let mut left = entries.clone();
+                        let from_entries = values.iter().all(|value| match left.iter().position(|entry| entry == value) {
+                            Some(position) => left.swap_remove(position) == *value,
+                            None => false,
+                        });
+                        prop_assert!(values.len() == k && values.iter().sum::<u32>() == target && from_entries
+                            && values.windows(2).all(|pair| pair[0] <= pair[1]),
+                            "{:?} is not a solution", values);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_expense_report("1721\n97x9\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 1: invalid number `97x9`");
+        assert_eq!(parse_entry("-3", 4).unwrap_err().line, 4);
+    }
+}
@@ -0,0 +1,89 @@
+use aoc_common::{format_json, Args, InputSource, OutputFormat, PartResult, UnwrapOrExit};
+use day1::{checked_product, count_k_sum_solutions, EntryIndex, find_k_values_sum, find_subset_sum, parse_entry_as, read_lines, solve_part_1_detailed,
+    solve_part_2_detailed, TARGET_SUM};
+use num_traits::ToPrimitive;
+
+/// The type of the entries. Build with `--features bigint` for entries and products of any size.
+#[cfg(not(feature = "bigint"))]
+type Number = u64;
+#[cfg(feature = "bigint")]
+type Number = day1::BigInt;
+
+/// Run with `cargo run -p day1 -- input.txt`, or `-` to read from stdin.
+/// With no argument, reads `$AOC_INPUT` or this crate's `input.txt`.
+/// Add `--format json` to print the answers as JSON.
+fn main() {
+    let source = InputSource::from_env(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt"));
+    if Args::from_env().format == OutputFormat::Json {
+        let text = source.read_to_string().unwrap_or_exit();
+        print!("{}", format_json(&[
+            PartResult::run(1, 1, solve_part_1_detailed, &text),
+            PartResult::run(1, 2, solve_part_2_detailed, &text),
+        ]));
+        return;
+    }
+    println!("{}", source);
+
+    let target = Number::from(TARGET_SUM);
+    let mut index = EntryIndex::new();
+    let (mut pair, mut triple) = (None, None);
+
+    // The input might be a stream which never ends, so report the first pair and the first triplet
+    // which add up to the target as soon as the line which completes them is read.
+    for (i, line) in read_lines(&source).unwrap_or_exit().enumerate() {
+        let x_str = line.unwrap_or_exit();
+        if x_str.trim().is_empty() {
+            continue;
+        }
+        let x = parse_entry_as::<Number>(&x_str, i + 1).unwrap_or_exit();
+        if pair.is_none() {
+            pair = index.pair_with(&x, &target);
+            report_sum(i + 1, &pair);
+        }
+        if triple.is_none() {
+            triple = index.triple_with(&x, &target);
+            report_sum(i + 1, &triple);
+        }
+        index.insert(x).unwrap_or_exit();
+    }
+    let numbers = index.iter().cloned().collect::<Vec<Number>>();
+    println!("{:?}", numbers);
+
+    // Counting and finding subsets work on entries which fit in a u32.
+    let small_numbers = numbers.iter().map(|x| x.to_u32()).collect::<Option<Vec<u32>>>();
+
+    // Part 1 looks for a pair of entries, and part 2 for a triplet.
+    for (part, k) in [(1, 2), (2, 3)] {
+        println!("Part {}:", part);
+        match find_k_values_sum(target.to_owned(), k, &numbers).unwrap_or_exit() {
+            Some(values) => {
+                let factors = values.iter().map(|value| value.to_string()).collect::<Vec<String>>();
+                println!("{} = {}", factors.join(" * "), checked_product(&values).unwrap_or_exit());
+            }
+            None => println!("No {} numbers which add to {} were found :(", k, TARGET_SUM),
+        }
+        if let Some(small_numbers) = &small_numbers {
+            println!("{} choices of {} entries add to {}", count_k_sum_solutions(TARGET_SUM, k, small_numbers), k, TARGET_SUM);
+        }
+    }
+
+    // Any number of entries will do, as long as there are as few as possible.
+    match small_numbers.as_deref().map(|small_numbers| find_subset_sum(TARGET_SUM, small_numbers).unwrap_or_exit()) {
+        Some(Some(found)) => {
+            println!("Smallest subset: {:?}, with product {}", found.entries, found.product);
+            for (size, product) in found.pareto {
+                println!("Largest product of {} entries: {}", size, product);
+            }
+        }
+        Some(None) => println!("No entries add to {} :(", TARGET_SUM),
+        None => println!("Some entries are too large to look for subsets of"),
+    }
+}
+
+/// Print the entries which add up to the target, if some were found when line `line_number` was read.
+fn report_sum(line_number: usize, values: &Option<Vec<Number>>) {
+    if let Some(values) = values {
+        let terms = values.iter().map(|value| value.to_string()).collect::<Vec<String>>();
+        println!("Line {}: {} = {}", line_number, terms.join(" + "), TARGET_SUM);
+    }
+}
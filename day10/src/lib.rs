@@ -0,0 +1,180 @@
+//! Day 10: Adapter Array.
+//!
+//! Chain joltage adapters from the charging outlet (0 jolts) to the device's built-in adapter
+//! (3 jolts higher than the highest adapter). Each adapter can take an input 1, 2 or 3 jolts
+//! lower than its rating.
+
+use std::{convert::TryFrom, collections::HashMap};
+use aoc_common::{parse_number_lines, ParseError};
+use petgraph::{graphmap::DiGraphMap, Direction};
+use tracing::{instrument, warn};
+
+/// Parse the adapter joltage ratings, one per line.
+pub fn parse_adapters(text: &str) -> Result<Vec<u32>, ParseError> {
+    parse_number_lines(text)
+}
+
+/// Solve part 1: the number of 1-jolt differences multiplied by the number of 3-jolt
+/// differences, in the chain which uses every adapter.
+///
+/// # Panics
+/// Panics if the adapters can't all be chained together.
+#[instrument(skip(text), ret)]
+pub fn solve_part_1(text: &str) -> Result<u32, ParseError> {
+    let difference_counts = compute_jolt_differences(&parse_adapters(text)?)
+        .expect("the adapters can't all be chained together");
+    Ok(difference_counts[1] * difference_counts[3])
+}
+
+/// Solve part 2: the number of distinct ways to arrange the adapters.
+#[instrument(skip(text), ret)]
+pub fn solve_part_2(text: &str) -> Result<u64, ParseError> {
+    Ok(count_paths_outlet_to_device(&parse_adapters(text)?))
+}
+
+/// Count the joltage differences in the chain which uses every adapter.
+/// Element `i` of the result is the number of `i`-jolt differences.
+/// Returns `None` if the adapters can't all be chained together.
+pub fn compute_jolt_differences(adapters: &[u32]) -> Option<Box<[u32; 4]>> {
+    // If `difference_counts[i] == k`, there are `k` joltage differences of `i` jolts
+    // in the adapter chain.
+    let mut difference_counts = [0, 0, 0, 0];
+    let mut unused_adapters = adapters.to_vec();
+    // The jolt rating of the built-in joltage adapter.
+    let built_in_joltage = adapters.iter().max().unwrap() + 3;
+    unused_adapters.push(built_in_joltage);
+
+    unused_adapters.sort();
+    unused_adapters.reverse();
+
+    // "The charging outlet has an effective rating of 0 jolts", so start our adapter chain
+    // at 0 jolts.
+    let mut jolt_level = 0;
+    while let Some(lowest_adapter) = unused_adapters.pop() {
+        
+        let difference = lowest_adapter - jolt_level;
+        if difference < 1 {
+            warn!(lowest_adapter, jolt_level, "lowest adapter is too low for the jolt level");
+            return None;
+        }
+        if difference > 3 {
+            warn!(lowest_adapter, jolt_level, "lowest adapter is too high for the jolt level");
+            return None;
+        }
+        difference_counts[usize::try_from(difference).unwrap()] += 1;
+        jolt_level = lowest_adapter;
+    }
+
+    return Some(Box::new(difference_counts));
+}
+
+/// Builds a (directed acyclic) graph of which adapter joltage levels can connect to each other.
+/// The returned graph contains a node for each adapter joltage level, and a `0`
+/// node for the charging outlet, and a node for the device's built-in adapter.
+/// There is an edge from node `i` to node `j` IFF adapter `j` can connect after adapter
+/// `i`, i.e. `j - i` equals 1, 2 or 3. 
+fn build_graph(adapters: &[u32]) -> Box<DiGraphMap<u32, ()>> {
+    let mut joltage_levels = adapters.to_vec();
+    joltage_levels.sort();
+    joltage_levels.push(joltage_levels.last().unwrap() + 3);
+    joltage_levels.insert(0, 0);
+
+    let mut graph = DiGraphMap::<u32, ()>::new();
+    for x in joltage_levels.iter() {
+        graph.add_node(*x);
+    }
+    for x in joltage_levels.iter() {
+        for difference in &[1, 2, 3] {
+            if graph.contains_node(x + difference) {
+                graph.add_edge(*x, x + difference, ());
+            }
+        }
+    }
+
+    return Box::new(graph);
+}
+
+/// Count the paths by which the 0-joltage outlet can connect to the device.
+pub fn count_paths_outlet_to_device(adapters: &[u32]) -> u64 {
+    let graph = *build_graph(adapters);
+
+    let mut joltage_levels = graph.nodes().collect::<Vec<u32>>();
+    joltage_levels.sort();
+    joltage_levels.reverse(); // Put the highest joltage level first
+    assert_eq!(*joltage_levels.last().unwrap(), 0);
+
+    // Node at joltage level `i` has `paths_to_end[i]` paths to reach the end node.
+    // This is the memoization data structure for the dynamic programming solution.
+    let mut paths_to_end = HashMap::<u32, u64>::new();
+    paths_to_end.insert(joltage_levels[0], 1);
+
+    // Count the number of paths from the `0` node (i.e. `joltage_levels.last()`)
+    // to the "builtin joltage adapter" node (i.e. `joltage_levels[0]`)
+    // using dynamic programming.
+    for j in &joltage_levels[1..] {
+        let mut paths_to_end_this_node = 0;
+        // Each `connectable_joltage` is an (higher) adapter joltage level that can connect to
+        // joltage level `j`. 
+        for connectable_joltage in graph.neighbors_directed(*j, Direction::Outgoing) {
+            // Note that `paths_to_end` must already have a key for `connectable_joltage`.
+            // Only joltages higher than `j` are connectable, and we are walking through the
+            // joltage levels from highest to lowest, so the higher levels have already been
+            // processed. 
+            paths_to_end_this_node += paths_to_end[&connectable_joltage];
+        }
+        paths_to_end.insert(*j, paths_to_end_this_node);
+    }
+
+    return paths_to_end[&0];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jolt_differences_small_example() {
+        // Setup
+        let adapters = vec![16, 10, 15, 5, 1, 11, 7, 19, 6, 12, 4];
+        // Action
+        let difference_counts: [u32; 4] = *compute_jolt_differences(&adapters).unwrap();
+        // Verification
+        assert_eq!(difference_counts[1], 7);
+        assert_eq!(difference_counts[2], 0);
+        assert_eq!(difference_counts[3], 5);
+    }
+
+    #[test]
+    fn test_jolt_differences_larger_example() {
+        // Setup
+        let adapters = vec![28, 33, 18, 42, 31, 14, 46, 20, 48, 47, 24, 23, 49, 45,
+            19, 38, 39, 11, 1, 32, 25, 35, 8, 17, 7, 9, 4, 2, 34, 10, 3];
+        // Action
+        let difference_counts: [u32; 4] = *compute_jolt_differences(&adapters).unwrap();
+        // Verification
+        assert_eq!(difference_counts[1], 22);
+        assert_eq!(difference_counts[2], 0);
+        assert_eq!(difference_counts[3], 10);
+    }
+
+    #[test]
+    fn test_count_paths_small_example() {
+        // Setup
+        let adapters = vec![16, 10, 15, 5, 1, 11, 7, 19, 6, 12, 4];
+        // Action
+        let path_count = count_paths_outlet_to_device(&adapters);
+        // Verification
+        assert_eq!(path_count, 8);
+    }
+
+    #[test]
+    fn test_count_paths_larger_example() {
+        // Setup
+        let adapters = vec![28, 33, 18, 42, 31, 14, 46, 20, 48, 47, 24, 23, 49, 45,
+            19, 38, 39, 11, 1, 32, 25, 35, 8, 17, 7, 9, 4, 2, 34, 10, 3];
+        // Action
+        let path_count = count_paths_outlet_to_device(&adapters);
+        // Verification
+        assert_eq!(path_count, 19208);
+    }
+}
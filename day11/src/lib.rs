@@ -0,0 +1,202 @@
+//! Day 11: Seating System.
+//!
+//! Simulate people choosing seats in a waiting area, where each position is floor (`.`),
+//! an empty seat (`L`) or an occupied seat (`#`), until the seating reaches a steady state.
+
+use aoc_common::{Cell, Grid, ParseError, Position, ALL_DIRECTIONS};
+use tracing::{debug, instrument, trace};
+
+/// One position in the seating area.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Seat {
+    Floor,
+    Empty,
+    Occupied,
+}
+
+impl Cell for Seat {
+    const EXPECTED: &'static str = "`.`, `L` or `#`";
+
+    fn from_char(c: char) -> Option<Seat> {
+        match c {
+            '.' => Some(Seat::Floor),
+            'L' => Some(Seat::Empty),
+            '#' => Some(Seat::Occupied),
+            _ => None,
+        }
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            Seat::Floor => '.',
+            Seat::Empty => 'L',
+            Seat::Occupied => '#',
+        }
+    }
+}
+
+/// Parse the seating area, one row per line.
+/// Every row must be made of `.`, `L` and `#`, and be the same width.
+pub fn parse_seating_area(text: &str) -> Result<Grid<Seat>, ParseError> {
+    Grid::parse(text)
+}
+
+/// Solve part 1: the number of occupied seats in the steady state, using the adjacent seats rule.
+#[instrument(skip(text), ret)]
+pub fn solve_part_1(text: &str) -> Result<u32, ParseError> {
+    Ok(occupied_seats_steady_state(&parse_seating_area(text)?, 1))
+}
+
+/// Solve part 2: the number of occupied seats in the steady state, using the visible seats rule.
+#[instrument(skip(text), ret)]
+pub fn solve_part_2(text: &str) -> Result<u32, ParseError> {
+    Ok(occupied_seats_steady_state(&parse_seating_area(text)?, 2))
+}
+
+fn count_occupied_seats(seating_area: &Grid<Seat>) -> u32 {
+    seating_area.iter().filter(|&&seat| seat == Seat::Occupied).count() as u32
+}
+
+fn count_occupied_adjacent_seats(seating_area: &Grid<Seat>, position: Position) -> u32 {
+    seating_area
+        .neighbors_8(position)
+        .filter(|&neighbor| seating_area[neighbor] == Seat::Occupied)
+        .count() as u32
+}
+
+fn count_occupied_visible_seats(seating_area: &Grid<Seat>, position: Position) -> u32 {
+    let mut count = 0;
+    for &direction in ALL_DIRECTIONS.iter() {
+        // Look along each direction until we find either a seat or the edge of the seating area.
+        let first_seat = seating_area
+            .ray(position, direction)
+            .map(|cursor| seating_area[cursor])
+            .find(|&seat| seat != Seat::Floor);
+        if first_seat == Some(Seat::Occupied) {
+            count += 1;
+        }
+    }
+    return count;
+}
+
+/// Apply one round of seating rules to every position, writing the result into `seating_area_new`.
+/// An empty seat becomes occupied if `count_occupied` finds no occupied seats, and an occupied
+/// seat becomes empty if it finds at least `crowded` occupied seats. Returns true if any seat changed.
+fn sim_step(
+        seating_area_old: &Grid<Seat>, seating_area_new: &mut Grid<Seat>,
+        count_occupied: fn(&Grid<Seat>, Position) -> u32, crowded: u32) -> bool {
+    let mut changed = false;
+    for position in seating_area_old.positions() {
+        let this_seat_old = seating_area_old[position];
+        let this_seat_new = match this_seat_old {
+            Seat::Floor => Seat::Floor,
+            Seat::Empty => {
+                let count = count_occupied(seating_area_old, position);
+                if count == 0 {Seat::Occupied} else {Seat::Empty}
+            },
+            Seat::Occupied => {
+                let count = count_occupied(seating_area_old, position);
+                if count >= crowded {Seat::Empty} else {Seat::Occupied}
+            },
+        };
+        if this_seat_new != this_seat_old { changed = true; }
+        seating_area_new[position] = this_seat_new;
+    }
+    return changed
+}
+
+/// Apply one round of the part 1 rules, which consider the 8 adjacent seats, writing the result
+/// into `seating_area_new`. Returns true if any seat changed.
+pub fn sim_step_1(seating_area_old: &Grid<Seat>, seating_area_new: &mut Grid<Seat>) -> bool {
+    sim_step(seating_area_old, seating_area_new, count_occupied_adjacent_seats, 4)
+}
+
+/// Apply one round of the part 2 rules, which consider the first seat visible in each of the
+/// 8 directions, writing the result into `seating_area_new`. Returns true if any seat changed.
+pub fn sim_step_2(seating_area_old: &Grid<Seat>, seating_area_new: &mut Grid<Seat>) -> bool {
+    sim_step(seating_area_old, seating_area_new, count_occupied_visible_seats, 5)
+}
+
+/// Apply the rules of part `part` (1 or 2) until no seats change, and count the occupied seats.
+/// Each round is logged at debug level, with the whole seating area at trace level.
+pub fn occupied_seats_steady_state(seating_area: &Grid<Seat>, part: u32) -> u32 {
+    let mut seating_area_old = seating_area.clone();
+    let mut seating_area_new = seating_area.clone();
+    let mut changed = true;
+    let mut round = 0;
+    while changed {
+        round += 1;
+
+        changed = match part {
+            1 => sim_step_1(&seating_area_old, &mut seating_area_new),
+            2 => sim_step_2(&seating_area_old, &mut seating_area_new),
+            _ => panic!()
+        };
+
+        debug!(round, changed, "simulated a round");
+        trace!("seating area after round {}:\n{}", round, seating_area_new);
+        std::mem::swap(&mut seating_area_old, &mut seating_area_new);
+    }
+
+    return count_occupied_seats(&seating_area_new);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_sim_example_part_1() {
+        // Setup
+        let text = fs::read_to_string("./example_input.txt").unwrap();
+        let seating_area = parse_seating_area(&text).unwrap();
+        
+        // Action
+        let count = occupied_seats_steady_state(&seating_area, 1);
+
+        // Verification
+        assert_eq!(count, 37);
+    }
+
+    #[test]
+    fn test_sim_example_part_2() {
+        // Setup
+        let text = fs::read_to_string("./example_input.txt").unwrap();
+        let seating_area = parse_seating_area(&text).unwrap();
+        
+        // Action
+        let count = occupied_seats_steady_state(&seating_area, 2);
+
+        // Verification
+        assert_eq!(count, 26);
+    }
+
+    #[test]
+    fn test_parse_and_render() {
+        let text = fs::read_to_string("./example_input.txt").unwrap();
+        let seating_area = parse_seating_area(&text).unwrap();
+        assert_eq!(seating_area[(0, 0)], Seat::Empty);
+        assert_eq!(seating_area[(0, 1)], Seat::Floor);
+        assert_eq!(seating_area.to_string(), text.trim().replace("\r", "") + "\n");
+        let error = parse_seating_area("L.\n.x\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 2: expected `.`, `L` or `#`, found `x`");
+    }
+
+    #[rstest(filename, row, col, expected_count,
+        case("./visible_seats_example_1.txt", 4, 3, 8),
+        case("./visible_seats_example_2.txt", 1, 1, 0),
+        case("./visible_seats_example_3.txt", 3, 3, 0),
+    )]
+    fn test_visible_seats_examples(filename: &str, row: usize, col: usize, expected_count: u32) {
+        // Setup
+        let text = fs::read_to_string(filename).unwrap();
+        let seating_area = parse_seating_area(&text).unwrap();
+        // Action
+        let count = count_occupied_visible_seats(&seating_area, (row, col));
+        // Verification
+        assert_eq!(count, expected_count);
+    }
+}
@@ -1,97 +1,123 @@
-use pyo3::prelude::*;
-use pyo3::wrap_pyfunction;
+//! Day 12: Rain Risk.
+//!
+//! Follow the ferry's navigation instructions, and find the Manhattan distance from its
+//! starting position. The solvers are available from Python through the `aoc2020` package.
+
+use aoc_common::{combinators::{integer, lines, parse_all, satisfy, Parser}, ParseError};
+use tracing::{instrument, trace};
+
+/// Solve part 1: the Manhattan distance travelled when the instructions move the ship.
+#[instrument(skip(text), ret)]
+pub fn solve_part_1(text: &str) -> Result<i32, ParseError> {
+    nav_instructions_manhattan_distance_1(text)
+}
+
+/// Solve part 2: the Manhattan distance travelled when the instructions move the waypoint.
+#[instrument(skip(text), ret)]
+pub fn solve_part_2(text: &str) -> Result<i32, ParseError> {
+    nav_instructions_manhattan_distance_2(text)
+}
+
+/// Whether the ship can turn by `degrees`: only multiples of 90 degrees keep it on a grid direction.
+pub fn is_valid_turn(degrees: i32) -> bool {
+    degrees % 90 == 0
+}
 
-/// Formats the sum of two numbers as string.
-#[pyfunction]
-fn sum_as_string(a: usize, b: usize) -> PyResult<String> {
-    Ok((a + b).to_string())
+/// Parse the navigation instructions, one per line, into (action, value) pairs.
+/// The action is one of `NSEWLRF`, and turns (`L` and `R`) must be a multiple of 90 degrees.
+pub fn parse_nav_instructions(nav_instructions_text: &str) -> Result<Vec<(char, i32)>, ParseError> {
+    let turn = (satisfy("`L` or `R`", |c| "LR".contains(c)),
+        integer().verify(|&degrees: &i32| is_valid_turn(degrees), "a multiple of 90 degrees"));
+    let movement = (satisfy("one of `NSEWF`", |c| "NSEWF".contains(c)), integer());
+    let instruction = turn.or(movement).expect("an instruction like `F10`");
+    parse_all(nav_instructions_text, lines(instruction))
 }
 
 /// Solves according to rules for part 1.
-#[pyfunction(verbose=false)]
-#[text_signature = "(nav_instructions_text, verbose, /)"]
-fn nav_instructions_manhattan_distance_1(nav_instructions_text: &str, verbose: bool) -> PyResult<i32> {
-    let nav_instructions = nav_instructions_text
-        .trim()
-        .split("\n")
-        .collect::<Vec<&str>>();
+/// The ship's state before each instruction is logged at trace level.
+pub fn nav_instructions_manhattan_distance_1(nav_instructions_text: &str) -> Result<i32, ParseError> {
+    let nav_instructions = parse_nav_instructions(nav_instructions_text)?;
 
+    let mut ship: (i32, i32) = (0, 0);
     let mut heading: i32 = 0;
-    let mut x: i32 = 0;
-    let mut y: i32 = 0;
-    for instruction in nav_instructions.iter() {
-        if verbose {
-            println!("x: {}, y: {}, heading: {}, inst: {}", x, y, heading, instruction);
-        }
+    for &(action, arg) in nav_instructions.iter() {
+        trace!(?ship, heading, %action, arg, "following an instruction");
+        (ship, heading) = follow_instruction_1(ship, heading, action, arg);
+    }
 
-        let action: char = instruction.chars().nth(0).unwrap();
-        let arg = instruction[1..].parse::<i32>().unwrap();
-        match action {
-            'N' => y += arg,
-            'S' => y -= arg,
-            'E' => x += arg,
-            'W' => x -= arg,
-            'L' => {
-                heading += arg;
-                heading = wrap_heading(heading);
-            },
-            'R' => {
-                heading -= arg;
-                heading = wrap_heading(heading);
-            }
-            'F' => {
-                x += arg * int_cosine(heading);
-                y += arg * int_sine(heading);
-            }
-            _ => panic!()
+    Ok(ship.0.abs() + ship.1.abs())
+}
+
+/// Follow one instruction with the rules for part 1, which move the ship itself.
+/// The ship is at `ship`, (x, y) with north as +y, and faces `heading` degrees counterclockwise
+/// from east. Returns the new position and heading.
+pub fn follow_instruction_1(ship: (i32, i32), heading: i32, action: char, arg: i32) -> ((i32, i32), i32) {
+    let (mut x, mut y) = ship;
+    let mut heading = heading;
+    match action {
+        'N' => y += arg,
+        'S' => y -= arg,
+        'E' => x += arg,
+        'W' => x -= arg,
+        'L' => {
+            heading += arg;
+            heading = wrap_heading(heading);
+        },
+        'R' => {
+            heading -= arg;
+            heading = wrap_heading(heading);
+        }
+        'F' => {
+            x += arg * int_cosine(heading);
+            y += arg * int_sine(heading);
         }
+        _ => unreachable!("`parse_nav_instructions` only accepts these actions")
     }
-
-    Ok(x.abs() + y.abs())
+    ((x, y), heading)
 }
 
 /// Solves according to rules for part 2.
-#[pyfunction(verbose=false)]
-#[text_signature = "(nav_instructions_text, verbose, /)"]
-fn nav_instructions_manhattan_distance_2(nav_instructions_text: &str, verbose: bool) -> PyResult<i32> {
-    let nav_instructions = nav_instructions_text
-        .trim()
-        .split("\n")
-        .collect::<Vec<&str>>();
+/// The ship's and waypoint's state before each instruction is logged at trace level.
+pub fn nav_instructions_manhattan_distance_2(nav_instructions_text: &str) -> Result<i32, ParseError> {
+    let nav_instructions = parse_nav_instructions(nav_instructions_text)?;
 
     let mut ship: (i32, i32) = (0, 0);
     // Waypoint (x, y) position is relative to the ship.
     let mut waypoint: (i32, i32) = (10, 1);
-    for instruction in nav_instructions.iter() {
-        if verbose {
-            println!("ship: {}, {};    waypoint {}, {};    inst: {}",
-            ship.0, ship.1, waypoint.0, waypoint.1, instruction);
-        }
-
-        let action: char = instruction.chars().nth(0).unwrap();
-        let arg = instruction[1..].parse::<i32>().unwrap();
-        match action {
-            'N' => waypoint.1 += arg,
-            'S' => waypoint.1 -= arg,
-            'E' => waypoint.0 += arg,
-            'W' => waypoint.0 -= arg,
-            'L' => {
-                waypoint = int_rotate_point(waypoint, arg);
-            },
-            'R' => {
-                waypoint = int_rotate_point(waypoint, -1 * arg);
-            }
-            'F' => {
-                ship.0 += arg * waypoint.0;
-                ship.1 += arg * waypoint.1;
-            }
-            _ => panic!()
-        }
+    for &(action, arg) in nav_instructions.iter() {
+        trace!(?ship, ?waypoint, %action, arg, "following an instruction");
+        (ship, waypoint) = follow_instruction_2(ship, waypoint, action, arg);
     }
 
     Ok(ship.0.abs() + ship.1.abs())
 }
 
+/// Follow one instruction with the rules for part 2, which move the waypoint, and move the ship
+/// towards it. The waypoint's position is relative to the ship. Returns the new positions of the
+/// ship and the waypoint.
+pub fn follow_instruction_2(ship: (i32, i32), waypoint: (i32, i32), action: char, arg: i32) -> ((i32, i32), (i32, i32)) {
+    let mut ship = ship;
+    let mut waypoint = waypoint;
+    match action {
+        'N' => waypoint.1 += arg,
+        'S' => waypoint.1 -= arg,
+        'E' => waypoint.0 += arg,
+        'W' => waypoint.0 -= arg,
+        'L' => {
+            waypoint = int_rotate_point(waypoint, arg);
+        },
+        'R' => {
+            waypoint = int_rotate_point(waypoint, -arg);
+        }
+        'F' => {
+            ship.0 += arg * waypoint.0;
+            ship.1 += arg * waypoint.1;
+        }
+        _ => unreachable!("`parse_nav_instructions` only accepts these actions")
+    }
+    (ship, waypoint)
+}
+
 /// Rotate a point (x, y) about (0, 0) by `angle` degrees counterclockwise.
 fn int_rotate_point(point: (i32, i32), angle: i32) -> (i32, i32) {
     let wrapped_angle = wrap_heading(angle);
@@ -132,18 +158,6 @@ fn int_sine(angle: i32) -> i32 {
 }
 
 
-
-/// A Python module implemented in Rust.
-#[pymodule]
-fn day12(_py: Python, m: &PyModule) -> PyResult<()> {
-    m.add_function(wrap_pyfunction!(sum_as_string, m)?)?;
-    m.add_function(wrap_pyfunction!(nav_instructions_manhattan_distance_1, m)?)?;
-    m.add_function(wrap_pyfunction!(nav_instructions_manhattan_distance_2, m)?)?;
-
-    Ok(())
-}
-
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -151,16 +165,22 @@ mod tests {
     #[test]
     fn nav_instructions_manhattan_distance_1_example() {
         let nav_instructions_text = "F10\nN3\nF7\nR90\nF11";
-        let result = nav_instructions_manhattan_distance_1(&nav_instructions_text, true);
-        let dist = result.ok().unwrap();
-        assert_eq!(dist, 25);
+        let dist = nav_instructions_manhattan_distance_1(nav_instructions_text);
+        assert_eq!(dist, Ok(25));
     }
 
     #[test]
     fn nav_instructions_manhattan_distance_2_example() {
         let nav_instructions_text = "F10\nN3\nF7\nR90\nF11";
-        let result = nav_instructions_manhattan_distance_2(&nav_instructions_text, true);
-        let dist = result.ok().unwrap();
-        assert_eq!(dist, 286);
+        let dist = nav_instructions_manhattan_distance_2(nav_instructions_text);
+        assert_eq!(dist, Ok(286));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = parse_nav_instructions("F10\nX3\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 1: expected an instruction like `F10`, found `X3`");
+        let error = parse_nav_instructions("F10\nR45\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 2: expected a multiple of 90 degrees, found `45`");
     }
 }
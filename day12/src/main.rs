@@ -0,0 +1,16 @@
+use aoc_common::{format_json, Args, OutputFormat, PartResult, Solution, UnwrapOrExit};
+use day12::{solve_part_1, solve_part_2};
+
+fn main() {
+    let args = Args::from_env();
+    let text = aoc_common::load_input(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")).unwrap_or_exit();
+    if args.format == OutputFormat::Json {
+        print!("{}", format_json(&[
+            PartResult::run(12, 1, |text| solve_part_1(text).map(Solution::new), &text),
+            PartResult::run(12, 2, |text| solve_part_2(text).map(Solution::new), &text),
+        ]));
+        return;
+    }
+    println!("Part 1: Manhattan distance = {}", solve_part_1(&text).unwrap_or_exit());
+    println!("Part 2: Manhattan distance = {}", solve_part_2(&text).unwrap_or_exit());
+}
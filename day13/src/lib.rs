@@ -0,0 +1,189 @@
+//! Day 13: Shuttle Search.
+//!
+//! Find the earliest bus to the airport, and the earliest timestamp at which the buses depart
+//! at offsets matching their positions in the schedule.
+
+use aoc_common::{combinators::{integer, line, parse_all, section, split, tag, Parser}, ParseError, Solution};
+use num_integer::{Integer, ExtendedGcd};
+use tracing::{debug, instrument};
+
+/// The notes about the bus schedule, from the puzzle input.
+#[derive(Debug)]
+pub struct Notes {
+    /// The earliest time you could depart on a bus.
+    pub ready_time: u32,
+    /// The IDs of the buses in service, which are also their periods.
+    pub buses: Vec<u32>,
+    /// The position of each bus in the schedule, i.e. its offset from the timestamp in part 2.
+    pub offsets: Vec<u32>,
+}
+
+/// Parse the notes: the ready time on the first line, and a comma-separated list of bus IDs on
+/// the second line, where `x` marks buses which are out of service.
+pub fn parse_notes(text: &str) -> Result<Notes, ParseError> {
+    let bus = tag("x").map(|_| None)
+        .or(integer::<u32>().verify(|&bus| bus >= 1, "a bus ID of at least 1").map(Some))
+        .expect("`x` or a bus ID");
+    let schedule = split(",", bus).verify(|buses| buses.iter().any(Option::is_some), "at least one bus ID");
+    let (ready_time, schedule) = parse_all(text, (line(integer()), section("the list of bus IDs", line(schedule))))?;
+
+    let mut buses = Vec::<u32>::new();
+    let mut offsets = Vec::<u32>::new();
+    for (i, bus) in schedule.into_iter().enumerate() {
+        if let Some(bus) = bus {
+            buses.push(bus);
+            offsets.push(i as u32);
+        }
+    }
+    Ok(Notes { ready_time, buses, offsets })
+}
+
+/// Solve part 1: the ID of the earliest bus multiplied by the time you will wait for it.
+#[instrument(skip(text), ret)]
+pub fn solve_part_1(text: &str) -> Result<u32, ParseError> {
+    let notes = parse_notes(text)?;
+    let (earliest_bus, lowest_wait_time) = find_earliest_bus(notes.ready_time, &notes.buses);
+    Ok(earliest_bus * lowest_wait_time)
+}
+
+/// Solve part 1, and also give the ID of the earliest bus and the wait time for it.
+#[instrument(skip(text), ret)]
+pub fn solve_part_1_detailed(text: &str) -> Result<Solution, ParseError> {
+    let notes = parse_notes(text)?;
+    let (earliest_bus, lowest_wait_time) = find_earliest_bus(notes.ready_time, &notes.buses);
+    Ok(Solution::new(earliest_bus * lowest_wait_time)
+        .with("bus_id", earliest_bus)
+        .with("wait_time", lowest_wait_time))
+}
+
+/// Solve part 2: the earliest timestamp at which each bus departs at its offset after the timestamp.
+///
+/// # Panics
+/// Panics if there is no such timestamp.
+#[instrument(skip(text), ret)]
+pub fn solve_part_2(text: &str) -> Result<u128, ParseError> {
+    let notes = parse_notes(text)?;
+    Ok(find_earliest_part_2(&notes.buses, &notes.offsets))
+}
+
+/// Find the (bus ID, wait time) of the earliest bus after `ready_time`.
+pub fn find_earliest_bus(ready_time: u32, buses: &[u32]) -> (u32, u32) {
+    let mut lowest_wait_time = u32::MAX;
+    let mut earliest_bus: u32 = 0;
+
+    for bus in buses.iter() {
+        let wait_time_this_bus = bus - (ready_time % bus);
+        if wait_time_this_bus < lowest_wait_time {
+            lowest_wait_time = wait_time_this_bus;
+            earliest_bus = *bus;
+        }
+    }
+    assert_ne!(earliest_bus, 0);
+
+    (earliest_bus, lowest_wait_time)
+}
+
+/// Combine two periodic events, with the given periods and phases, into the single periodic
+/// event at which both happen. Returns (period_c, phase_c), or `None` if the two events never
+/// happen at the same step.
+pub fn periodic_combination(period_a: i128, period_b: i128,
+        phase_a: i128, phase_b: i128) -> Option<(i128, i128)> {
+    // Following https://math.stackexchange.com/a/38128593
+    let ExtendedGcd { gcd, x, .. } = period_a.extended_gcd(&period_b);
+    let s = x;
+
+    let phase_difference = phase_a - phase_b;
+    let (pd_mult, pd_remainder) = phase_difference.div_mod_floor(&gcd);
+    if pd_remainder != 0 {
+        // "A and B will never land on their reference points at the same step"
+        return None;
+    }
+    let period_c = period_a / gcd * period_b;
+    let phase_c = (phase_a - s * pd_mult * period_a).mod_floor(&period_c);
+
+    return Some((period_c, phase_c))
+}
+
+/// Find the earliest timestamp `t` at which each bus `buses[i]` departs at `t + offsets[i]`.
+pub fn find_earliest_part_2(buses: &[u32], offsets: &[u32]) -> u128 {
+    assert_eq!(buses.len(), offsets.len());
+
+    let mut period = buses[0] as i128;
+    let mut phase = offsets[0] as i128;
+
+    for (bus, offset) in buses[1..].iter().zip(offsets[1..].iter()) {
+        let result = periodic_combination(
+            period, *bus as i128, phase, *offset as i128).unwrap();
+        period = result.0; phase = result.1;
+        debug!(bus, offset, period, phase, "combined a bus");
+    }
+    return (period - phase) as u128;
+}
+
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use super::*;
+
+    #[test]
+    fn test_mod() {
+        let x = 5;
+        assert_eq!(-1, x.mod_floor(&-3));
+    }
+
+    #[test]
+    fn test_find_earliest_bus() {
+        let buses = vec![7, 13, 59, 31, 19];
+        let (earliest_bus, lowest_wait_time) = find_earliest_bus(939, &buses);
+        assert_eq!(earliest_bus, 59);
+        assert_eq!(lowest_wait_time, 5);
+    }
+
+    #[test]
+    fn test_part_1_detailed() {
+        let solution = solve_part_1_detailed("939\n7,13,x,x,59,x,31,19").unwrap();
+        assert_eq!(solution, Solution::new(295).with("bus_id", 59).with("wait_time", 5));
+    }
+
+    #[rstest(period_a, period_b, phase_a, phase_b, correct_period_c, correct_phase_c,
+        case(9, 15, 0, 3, 45, 18),
+        case(2, 3, -1, -2, 6, 1),
+        case(2, 3, 0, 1, 6, 6-2),
+        // case(3, 2, -2, -1, 6, 5),
+        case(17, 13, 0, 2, 221, 221-102),
+    )]
+    fn test_periodic_combination(period_a: i128, period_b: i128, phase_a: i128, phase_b: i128,
+            correct_period_c: i128, correct_phase_c: i128) {
+        let (period_c, phase_c) = periodic_combination(period_a, period_b, phase_a, phase_b).unwrap();
+        assert_eq!(period_c, correct_period_c);
+        assert_eq!(phase_c, correct_phase_c);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let buses = vec![7, 13, 59, 31, 19];
+        let offsets = vec![0, 1, 4, 6, 7];
+        let earliest = find_earliest_part_2(&buses, &offsets);
+        assert_eq!(earliest, 1068781);
+    }
+
+    #[test]
+    fn test_part_2_ex1() {
+        let buses = vec![17, 13, 19];
+        let offsets = vec![0, 2, 3];
+        let earliest = find_earliest_part_2(&buses, &offsets);
+        assert_eq!(earliest, 3417);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_notes("939\n7,13,x,y,59").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 8: expected `x` or a bus ID, found `y`");
+        let error = parse_notes("939\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 1: input ended before the list of bus IDs");
+        let error = parse_notes("939\nx,0").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 3: expected a bus ID of at least 1, found `0`");
+    }
+
+}
@@ -0,0 +1,166 @@
+//! Day 14: Docking Data.
+//!
+//! Run the ferry's docking program, which writes values to memory through a bitmask.
+//! Part 1 masks the values, and part 2 masks the memory addresses, with floating bits.
+
+use std::collections::HashMap;
+use aoc_common::{combinators::{integer, lines, parse_all, tag, take_while1, Parser}, ParseError};
+use tracing::{debug, instrument, trace};
+
+/// Solve part 1: the sum of the values left in memory by the part 1 program.
+#[instrument(skip(source), ret)]
+pub fn solve_part_1(source: &str) -> Result<u64, ParseError> {
+    run_program(source)
+}
+
+/// Solve part 2: the sum of the values left in memory by the part 2 program.
+#[instrument(skip(source), ret)]
+pub fn solve_part_2(source: &str) -> Result<u64, ParseError> {
+    run_program_2(source)
+}
+
+/// The number of bits in each mask and memory value.
+pub const WORD_BITS: usize = 36;
+
+/// One line of the initialization program.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Instruction<'a> {
+    /// Set the bitmask, e.g. `mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X`.
+    /// The mask has `WORD_BITS` characters, from the most significant bit to the least.
+    Mask(&'a str),
+    /// Write `value` to memory at `address`, e.g. `mem[8] = 11`.
+    Mem { address: u64, value: u64 },
+}
+
+/// Parse the initialization program, one instruction per line.
+pub fn parse_program(source: &str) -> Result<Vec<Instruction<'_>>, ParseError> {
+    let bits = take_while1("`0`, `1` or `X`", |c| matches!(c, '0' | '1' | 'X'))
+        .verify(|bits: &&str| bits.len() == WORD_BITS, "36 bits");
+    let mask = (tag("mask = "), bits).map(|(_, bits)| Instruction::Mask(bits));
+    let mem = (tag("mem["), integer(), tag("] = "), integer())
+        .map(|(_, address, _, value)| Instruction::Mem { address, value });
+    let instruction = mask.or(mem).expect("`mask = ` and 36 bits, or a write like `mem[8] = 11`");
+    parse_all(source, lines(instruction))
+}
+
+/// Runs the part 1 program and returns the sum of all values in memory.
+pub fn run_program(source: &str) -> Result<u64, ParseError> {
+    let instructions = parse_program(source)?;
+
+    let mut memory = HashMap::<u64, u64>::new();
+    let mut mask: u64 = !0;
+    let mut imprint: u64 = 0;
+
+    for inst in instructions.iter() {
+        match *inst {
+            Instruction::Mask(mask_str) => {
+                mask = !0;
+                imprint = 0;
+                for (index, element) in mask_str.chars().rev().enumerate() {
+                    match element {
+                        'X' => {}
+                        '0' => {
+                            mask &= !(1_u64 << index);
+                        }
+                        '1' => {
+                            mask &= !(1_u64 << index);
+                            imprint |= 1_u64 << index;
+                        }
+                        _ => unreachable!(),
+                    }
+                }
+            }
+            Instruction::Mem { address, value } => {
+                trace!(address, value, masked = (value & mask) | imprint, "wrote to memory");
+                memory.insert(address, (value & mask) | imprint);
+            }
+        }
+    }
+
+    debug!(addresses = memory.len(), "ran the part 1 program");
+    Ok(memory.values().sum())
+}
+
+
+/// Runs the part 2 program and returns the sum of all values in memory.
+pub fn run_program_2(source: &str) -> Result<u64, ParseError> {
+    let instructions = parse_program(source)?;
+
+    let mut memory = HashMap::<u64, u64>::new();
+    let mut imprint: u64 = 0;
+    let mut not_floating_mask: u64 = !0; // 0 at each floating bit.
+    let mut floating_bit_positions: Vec<u64> = Vec::new();
+
+    for inst in instructions.iter() {
+        match *inst {
+            Instruction::Mask(mask_str) => {
+                imprint = 0;
+                not_floating_mask = !0;
+                floating_bit_positions = Vec::new();
+                for (index, element) in mask_str.chars().rev().enumerate() {
+                    match element {
+                        'X' => {
+                            floating_bit_positions.push(index as u64);
+                            not_floating_mask &= !(1 << index);
+                        }
+                        '0' => {}
+                        '1' => {
+                            imprint |= 1_u64 << index;
+                        }
+                        _ => unreachable!(),
+                    }
+                }
+            }
+            Instruction::Mem { address: raw_address, value } => {
+                // From the `raw_address`, set all floating bits to 0 and all one bits to 1.
+                let base_address = (raw_address & not_floating_mask) | imprint;
+                trace!(raw_address, base_address, value, floating_bits = floating_bit_positions.len(),
+                    "wrote to floating memory");
+
+                for options in 0..(1 << floating_bit_positions.len()) {
+                    let mut floating_imprint: u64 = 0;
+                    for (index, position) in floating_bit_positions.iter().enumerate() {
+                        if options & (1 << index) > 0 {
+                            floating_imprint |= 1 << position;
+                        }
+                    }
+                    memory.insert(base_address | floating_imprint, value);
+                }
+            }
+        }
+    }
+
+    debug!(addresses = memory.len(), "ran the part 2 program");
+    Ok(memory.values().sum())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_run_program() {
+        let source = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X\nmem[8] = 11\nmem[7] = 101\nmem[8] = 0";
+        let sum = run_program(source);
+        assert_eq!(sum, Ok(165));
+    }
+
+    #[test]
+    fn test_run_program_2() {
+        let source = "mask = 000000000000000000000000000000X1001X\nmem[42] = 100\nmask = 00000000000000000000000000000000X0XX\nmem[26] = 1";
+        let sum = run_program_2(source);
+        assert_eq!(sum, Ok(208));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let source = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X\nmem[8] = 11\nmem[7] 101";
+        let error = parse_program(source).unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (3, 1, "mem[7] 101"));
+        let error = parse_program("mask = X1X").unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 8: expected 36 bits, found `X1X`");
+        let error = parse_program("mem[8] = 99999999999999999999").unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 10: invalid number `99999999999999999999`");
+    }
+
+}
\ No newline at end of file
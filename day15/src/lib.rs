@@ -0,0 +1,72 @@
+//! Day 15: Rambunctious Recitation.
+//!
+//! Play the Elves' memory game: after the starting numbers, each turn's number is 0 if the
+//! previous number was new, or else how many turns apart the previous number was last spoken.
+
+use std::collections::HashMap;
+use aoc_common::{combinators::{integer, line, parse_all, split}, ParseError};
+use tracing::{debug, instrument};
+
+/// Parse the comma-separated starting numbers.
+pub fn parse_starting_numbers(text: &str) -> Result<Vec<usize>, ParseError> {
+    parse_all(text, line(split(",", integer())))
+}
+
+/// Solve part 1: the 2020th number spoken.
+#[instrument(skip(text), ret)]
+pub fn solve_part_1(text: &str) -> Result<usize, ParseError> {
+    Ok(elf_game(&parse_starting_numbers(text)?, 2020))
+}
+
+/// Solve part 2: the 30000000th number spoken.
+#[instrument(skip(text), ret)]
+pub fn solve_part_2(text: &str) -> Result<usize, ParseError> {
+    Ok(elf_game(&parse_starting_numbers(text)?, 30000000))
+}
+
+/// Play the game with the given starting numbers, and return the number spoken on turn `end_turn`.
+pub fn elf_game(starting_numbers: &[usize], end_turn: usize) -> usize {
+    // If `turn_last_spoken[i] == k`, then `i` was last spoken on turn `k`.
+    // If `turn_last_spoken` does not contain `i`, then `i` has not been spoken before.
+    let mut turn_last_spoken = HashMap::<usize, usize>::new();
+    let mut turn_number = 1;
+    let mut number_spoken_last_turn = 0;
+    let mut number_spoken_this_turn = 0;
+
+    for &number in starting_numbers.iter() {
+        turn_last_spoken.insert(number, turn_number);
+        number_spoken_last_turn = number;
+        turn_number += 1;
+    }
+
+    while turn_number <= end_turn {
+        if !turn_last_spoken.contains_key(&number_spoken_last_turn) {
+            number_spoken_this_turn = 0;
+        } else {
+            number_spoken_this_turn = turn_number - 1 - turn_last_spoken.get(&number_spoken_last_turn).unwrap();
+        }
+        turn_last_spoken.insert(number_spoken_last_turn, turn_number - 1);
+        turn_number += 1;
+        number_spoken_last_turn = number_spoken_this_turn;
+    }
+    debug!(distinct_numbers = turn_last_spoken.len(), "played the game");
+    number_spoken_this_turn
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_elf_game_1() {
+        let last_number = elf_game(&[0, 3, 6], 2020);
+        assert_eq!(last_number, 436);
+    }
+
+    #[test]
+    fn test_parse_starting_numbers() {
+        assert_eq!(parse_starting_numbers("0,3,6\n"), Ok(vec![0, 3, 6]));
+        let error = parse_starting_numbers("0,3, 6\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 5: expected a number, found ` 6`");
+    }
+}
@@ -0,0 +1,263 @@
+//! Day 16: Ticket Translation.
+//!
+//! Work out which field is which on the train tickets, from the rules for each field's valid
+//! ranges and the values on nearby tickets.
+
+use std::collections::{ HashMap, HashSet };
+use aoc_common::{
+    combinators::{consumed, end, integer, line, lines, paragraph, parse_all, section, separated, split, tag, take_while1, Parser},
+    ParseError, ParseErrorKind,
+};
+use tracing::{debug, instrument, trace};
+
+
+fn is_in_any_range(value: u32, ranges: &[(u32, u32)]) -> bool {
+    for &range in ranges.iter() {
+        if range.0 <= value && value <= range.1 { return true; }
+    }
+    return false;
+}
+
+/// A ticket field and its valid ranges, e.g. `departure location: 49-258 or 268-954`.
+#[derive(Debug)]
+pub struct TicketField {
+    pub name: String,
+    /// Inclusive (low, high) ranges of valid values.
+    pub ranges: Vec<(u32, u32)>,
+}
+
+impl TicketField {
+    /// Check if `value` is in any of this field's ranges.
+    pub fn is_valid_value(&self, value: u32) -> bool {
+        is_in_any_range(value, &self.ranges)
+    }
+}
+
+/// The notes about the tickets, from the puzzle input.
+#[derive(Debug)]
+pub struct Notes {
+    pub fields: Vec<TicketField>,
+    /// The values on your ticket.
+    pub your_ticket: Vec<u32>,
+    /// The values on each nearby ticket.
+    pub nearby_tickets: Vec<Vec<u32>>,
+}
+
+/// Parse the notes: the ticket fields, then `your ticket:` and its values, then
+/// `nearby tickets:` and their values, with the sections separated by blank lines.
+/// Every ticket must have the same number of values.
+pub fn parse_notes(text: &str) -> Result<Notes, ParseError> {
+    let range = (integer(), tag("-"), integer()).map(|(low, _, high)| (low, high));
+    let field = (take_while1("a field name", |c| c.is_ascii_lowercase() || c == ' '), tag(": "),
+        separated(range, tag(" or ")))
+        .map(|(name, _, ranges)| TicketField { name: name.to_string(), ranges })
+        .expect("a field like `class: 1-3 or 5-7`");
+    let ticket = || split(",", integer::<u32>());
+    // Keep each nearby ticket's line, to point at it if it has the wrong number of values.
+    let (fields, your_ticket, nearby_tickets) = parse_all(text, (
+        paragraph(lines(field)),
+        section("your ticket:", paragraph((line(tag("your ticket:")), line(ticket())))),
+        section("nearby tickets:", paragraph((line(tag("nearby tickets:")),
+            lines(consumed(ticket())).or(end().map(|_| Vec::new()))))),
+    ))?;
+    let (_, your_ticket) = your_ticket;
+    let (_, nearby_tickets) = nearby_tickets;
+
+    for (line, ticket) in &nearby_tickets {
+        if ticket.len() != your_ticket.len() {
+            return Err(ParseError::new(text, line,
+                ParseErrorKind::Expected("a ticket with as many values as your ticket")));
+        }
+    }
+    let nearby_tickets = nearby_tickets.into_iter().map(|(_, ticket)| ticket).collect();
+    Ok(Notes { fields, your_ticket, nearby_tickets })
+}
+
+/// Solve part 1: the ticket scanning error rate, i.e. the sum of the values on nearby tickets
+/// which are not valid for any field.
+#[instrument(skip(text), ret)]
+pub fn solve_part_1(text: &str) -> Result<u32, ParseError> {
+    let notes = parse_notes(text)?;
+    let ranges = notes.fields
+        .iter()
+        .flat_map(|field| field.ranges.iter().cloned())
+        .collect::<Vec<(u32, u32)>>();
+
+    let mut error_rate = 0;
+    for ticket in notes.nearby_tickets.iter() {
+        for &value in ticket {
+            if !is_in_any_range(value, &ranges) {
+                trace!(value, "found a value which is not valid for any field");
+                error_rate += value;
+            }
+        }
+    }
+    Ok(error_rate)
+}
+
+fn purge_invalid_tickets(tickets: &[Vec<u32>], ranges: &[(u32, u32)]) -> Vec<Vec<u32>> {
+    let mut valid_tickets: Vec<Vec<u32>> = Vec::new();
+    for ticket in tickets.iter() {
+        if ticket.iter().all(|value| is_in_any_range(*value, ranges)) {
+            valid_tickets.push(ticket.to_vec());
+        }
+    }
+    valid_tickets
+}
+
+/// Solve part 2: the product of the values of the fields on your ticket whose names start with
+/// `departure`.
+#[instrument(skip(text), ret)]
+pub fn solve_part_2(text: &str) -> Result<u64, ParseError> {
+    Ok(find_fields_your_ticket(text)?
+        .iter()
+        .filter(|(field_name, _)| field_name.starts_with("departure"))
+        .map(|(_, &value)| value as u64)
+        .product())
+}
+
+/// Work out which field is which, and return a map from each field name to its value on your
+/// ticket.
+pub fn find_fields_your_ticket(text: &str) -> Result<HashMap<String, u32>, ParseError> {
+    let notes = parse_notes(text)?;
+    let your_ticket_values = notes.your_ticket;
+    let mut all_ranges = Vec::<(u32, u32)>::new();
+    for field in notes.fields.iter() {
+        all_ranges.extend(field.ranges.iter().cloned());
+    }
+    let fields_by_name = notes.fields
+        .into_iter()
+        .map(|field| (field.name.clone(), field))
+        .collect::<HashMap<String, TicketField>>();
+    let nearby_tickets_values = notes.nearby_tickets;
+
+    let valid_nearby_tickets_values = purge_invalid_tickets(&nearby_tickets_values, &all_ranges);
+    debug!(nearby = nearby_tickets_values.len(), valid = valid_nearby_tickets_values.len(), "purged invalid tickets");
+
+    // Narrow down which fields can possibly correspond to each ticket position.
+    // Initially, all field names are possible for each ticket position.
+    let mut possible_field_names_by_position = Vec::<HashSet<String>>::new();
+    for _ in 0..your_ticket_values.len() {
+        possible_field_names_by_position.push(
+            fields_by_name.keys().cloned().collect::<HashSet<String>>());
+    }
+    // If, for a given ticket, a position's value is not valid for a field, remove that field from
+    // being a possible match for that position.
+    for ticket in valid_nearby_tickets_values.iter() {
+        for (position, value) in ticket.iter().enumerate() {
+            for (field_name, field) in fields_by_name.iter() {
+                if !field.is_valid_value(*value) {
+                    possible_field_names_by_position[position].remove(field_name);
+                }
+            }
+        }
+    }
+
+    // If a position can only correspond to one field, remove that field from consideration
+    // for all other positions. Keep doing this until we match all the positions & fields.
+    assert!(possible_field_names_by_position.iter().any(|hs| hs.len() == 1));
+    let mut position_to_field_name_matches = HashMap::<usize, String>::new();
+    while position_to_field_name_matches.len() < your_ticket_values.len() {
+        for position in 0..possible_field_names_by_position.len() {
+            if possible_field_names_by_position[position].len() == 1 && !position_to_field_name_matches.contains_key(&position) {
+                // Only one field name can correspond to ticket position `position`.
+                let matched_position = position;
+                let matched_name = possible_field_names_by_position[position].iter().next().unwrap().clone();
+                debug!(position, field = %matched_name, "matched a field");
+                position_to_field_name_matches.insert(matched_position, matched_name.clone());
+                // Remove matched_name from all other sets in possible_field_names_by_position
+                for (position_other, possible_field_names) in possible_field_names_by_position.iter_mut().enumerate() {
+                    if position_other != matched_position {
+                        possible_field_names.remove(&matched_name);
+                    }
+                }
+            }
+        }
+    }
+
+    // Pack up the (field name -> value) map for your ticket, using the position-field matches found above.
+    let mut your_ticket = HashMap::<String, u32>::new();
+    for (position, value) in your_ticket_values.iter().enumerate() {
+        your_ticket.insert(
+            position_to_field_name_matches.get(&position).unwrap().to_string(),
+            *value);
+    }
+    Ok(your_ticket)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    use std::fs;
+
+    /// Notes with `fields` and tickets of `length` values, as (fields, your ticket, nearby tickets).
+    type NotesParts = (Vec<(String, Vec<(u32, u32)>)>, Vec<u32>, Vec<Vec<u32>>);
+
+    fn notes_parts() -> impl Strategy<Value = NotesParts> {
+        let field = ("[a-z]{1,8}( [a-z]{1,8})?", prop::collection::vec((any::<u32>(), any::<u32>()), 1..4));
+        let fields = prop::collection::vec(field, 1..5);
+        (fields, 1usize..5).prop_flat_map(|(fields, length)| {
+            let ticket = prop::collection::vec(any::<u32>(), length);
+            (Just(fields), ticket.clone(), prop::collection::vec(ticket, 0..4))
+        })
+    }
+
+    fn format_notes((fields, your_ticket, nearby_tickets): &NotesParts) -> String {
+        let format_ticket = |ticket: &Vec<u32>| ticket.iter().map(u32::to_string).collect::<Vec<String>>().join(",");
+        let mut text = String::new();
+        for (name, ranges) in fields {
+            let ranges = ranges.iter().map(|(low, high)| format!("{}-{}", low, high)).collect::<Vec<String>>();
+            text += &format!("{}: {}\n", name, ranges.join(" or "));
+        }
+        text += &format!("\nyour ticket:\n{}\n\nnearby tickets:\n", format_ticket(your_ticket));
+        for ticket in nearby_tickets {
+            text += &format!("{}\n", format_ticket(ticket));
+        }
+        text
+    }
+
+    #[test]
+    fn test_part_1_example() {
+        let text = fs::read_to_string("example_input.txt").unwrap();
+        let error_rate = solve_part_1(&text);
+        assert_eq!(error_rate, Ok(71));
+    }
+
+    #[test]
+    fn test_part_2_example() {
+        let text = fs::read_to_string("example_input_2.txt").unwrap();
+        let your_ticket = find_fields_your_ticket(&text).unwrap();
+        assert_eq!(*your_ticket.get("class").unwrap(), 12);
+        assert_eq!(*your_ticket.get("row").unwrap(), 11);
+        assert_eq!(*your_ticket.get("seat").unwrap(), 13);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let text = "class: 1-3 or 5-7\nrow 6-11 or 33-44\n\nyour ticket:\n7,1,14\n";
+        let error = parse_notes(text).unwrap_err();
+        assert_eq!(error.to_string(),
+            "line 2, column 1: expected a field like `class: 1-3 or 5-7`, found `row 6-11 or 33-44`");
+        let error = parse_notes("class: 1-3 or 5-7\n\nyour ticket:\n7,1,14\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 5, column 1: input ended before nearby tickets:");
+        let text = "class: 1-3\n\nyour ticket:\n7\n\nnearby tickets:\n1\n3,x\n";
+        let error = parse_notes(text).unwrap_err();
+        assert_eq!(error.to_string(), "line 8, column 3: expected a number, found `x`");
+        let text = "class: 1-3\n\nyour ticket:\n7\n\nnearby tickets:\n1\n3,2\n";
+        let error = parse_notes(text).unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (8, "3,2"));
+    }
+
+    proptest! {
+        #[test]
+        fn parse_notes_round_trip(parts in notes_parts()) {
+            let notes = parse_notes(&format_notes(&parts)).unwrap();
+            let (fields, your_ticket, nearby_tickets) = parts;
+            let parsed_fields = notes.fields.into_iter().map(|field| (field.name, field.ranges)).collect::<Vec<_>>();
+            prop_assert_eq!(parsed_fields, fields);
+            prop_assert_eq!(notes.your_ticket, your_ticket);
+            prop_assert_eq!(notes.nearby_tickets, nearby_tickets);
+        }
+    }
+}
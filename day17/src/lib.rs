@@ -0,0 +1,247 @@
+//! Day 17: Conway Cubes.
+//!
+//! Simulate a Conway's Game of Life style pocket dimension, starting from a 2D slice of active
+//! (`#`) and inactive (`.`) cubes.
+
+use std::collections::HashSet;
+use aoc_common::{Cell, Grid, ParseError};
+use tracing::{debug, instrument};
+
+
+/// One cube of the initial slice.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cube {
+    Inactive,
+    Active,
+}
+
+impl Cell for Cube {
+    const EXPECTED: &'static str = "`.` or `#`";
+
+    fn from_char(c: char) -> Option<Cube> {
+        match c {
+            '.' => Some(Cube::Inactive),
+            '#' => Some(Cube::Active),
+            _ => None,
+        }
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            Cube::Inactive => '.',
+            Cube::Active => '#',
+        }
+    }
+}
+
+
+/// Parse the initial 2D slice of the Conway space, and return the (x, y) of each active cube.
+pub fn parse_initial_slice(text: &str) -> Result<Vec<[i32; 2]>, ParseError> {
+    let grid = Grid::<Cube>::parse(text)?;
+    let active = grid
+        .positions()
+        .filter(|&position| grid[position] == Cube::Active)
+        .map(|(y, x)| [x as i32, y as i32])
+        .collect();
+    Ok(active)
+}
+
+/// Solve part 1: the number of active cubes after 6 cycles in 3 dimensions.
+#[instrument(skip(text), ret)]
+pub fn solve_part_1(text: &str) -> Result<usize, ParseError> {
+    simulate(text, 3, 6)
+}
+
+/// Simulate the Conway space in `dims` dimensions for `steps` cycles, starting from the initial
+/// slice in `text`, and return the number of active cubes.
+///
+/// # Panics
+/// Panics if `dims` is not 3 or 4.
+#[instrument(skip(text))]
+pub fn simulate(text: &str, dims: usize, steps: usize) -> Result<usize, ParseError> {
+    let initial_slice = parse_initial_slice(text)?;
+    match dims {
+        3 => {
+            let mut active_cubes = initial_slice
+                .into_iter()
+                .map(|[x, y]| [x, y, 0])
+                .collect::<HashSet<[i32; 3]>>();
+            for step in 0..steps {
+                active_cubes = sim_step(&active_cubes);
+                debug!(step = step + 1, active = active_cubes.len(), "simulated a cycle");
+            }
+            Ok(active_cubes.len())
+        }
+        4 => {
+            let mut active_cubes = initial_slice
+                .into_iter()
+                .map(|[x, y]| [x, y, 0, 0])
+                .collect::<HashSet<[i32; 4]>>();
+            for step in 0..steps {
+                active_cubes = sim_step_4d(&active_cubes);
+                debug!(step = step + 1, active = active_cubes.len(), "simulated a cycle");
+            }
+            Ok(active_cubes.len())
+        }
+        _ => panic!("the Conway space can only be simulated in 3 or 4 dimensions, not {}", dims),
+    }
+}
+
+/// Simulate one cycle in 3 dimensions, and return the (x, y, z) of each active cube afterwards.
+pub fn sim_step(active_cubes_old: &HashSet<[i32; 3]>) -> HashSet<[i32; 3]> {
+    let mut active_cubes_new = HashSet::<[i32; 3]>::new();
+    let bounds = get_bounds(active_cubes_old);
+
+    for x in (bounds[0].0 - 1)..(bounds[0].1 + 2) {
+        for y in (bounds[1].0 - 1)..(bounds[1].1 + 2) {
+            for z in (bounds[2].0 - 1)..(bounds[2].1 + 2) {
+                let point = [x, y, z];
+                let count = count_active_neighbors(&point, active_cubes_old);
+                // Active cubes stay active with 2 or 3 active neighbors,
+                // inactive cubes become active with exactly 3 active neighbors.
+                if count == 3 || (count == 2 && active_cubes_old.contains(&point)) {
+                    active_cubes_new.insert(point);
+                }
+            }
+        }
+    }
+
+    active_cubes_new
+}
+
+/// Get the (minimum, maximum) coordinates with active cubes for each dimension of the Conway space.
+fn get_bounds(active_cubes: &HashSet<[i32; 3]>) -> [(i32, i32); 3] {
+    let mut bounds = [(i32::MAX, i32::MIN); 3];
+    let dims: [usize; 3] = [0, 1, 2];
+    for &point in active_cubes.iter() {
+        for &dim in dims.iter() {
+            if point[dim] < bounds[dim].0 { bounds[dim].0 = point[dim]; }
+            if point[dim] > bounds[dim].1 { bounds[dim].1 = point[dim]; }
+        }
+    }
+    bounds
+}
+
+fn count_active_neighbors(point: &[i32; 3], active_cubes: &HashSet<[i32; 3]>) -> u32 {
+    let mut count: u32 = 0; 
+    for &dx in &[-1, 0, 1] {
+        for &dy in &[-1, 0, 1] {
+            for &dz in &[-1, -0, 1] {
+                if !(dx == 0 && dy == 0 && dz == 0)
+                    && (active_cubes.contains(&[point[0] + dx, point[1] + dy, point[2] + dz]))
+                {
+                    count += 1;
+                }
+            }
+        }
+    }
+    count
+}
+
+
+//     Part 2
+// --------------
+// To avoid duplicating code, I wanted to make my functions my code generic to number of dimensions
+// (3 for part 1, 4 for part 2), but it looks like I cannot.
+// See Rust RFC 2000 and https://stackoverflow.com/a/28137604/
+
+/// Solve part 2: the number of active cubes after 6 cycles in 4 dimensions.
+#[instrument(skip(text), ret)]
+pub fn solve_part_2(text: &str) -> Result<usize, ParseError> {
+    simulate(text, 4, 6)
+}
+
+/// Simulate one cycle in 4 dimensions, and return the (x, y, z, w) of each active cube afterwards.
+pub fn sim_step_4d(active_cubes_old: &HashSet<[i32; 4]>) -> HashSet<[i32; 4]> {
+    let mut active_cubes_new = HashSet::<[i32; 4]>::new();
+    let bounds = get_bounds_4d(active_cubes_old);
+
+    for x in (bounds[0].0 - 1)..(bounds[0].1 + 2) {
+        for y in (bounds[1].0 - 1)..(bounds[1].1 + 2) {
+            for z in (bounds[2].0 - 1)..(bounds[2].1 + 2) {
+                for w in (bounds[3].0 - 1)..(bounds[3].1 + 2) {
+                    let point = [x, y, z, w];
+                    let count = count_active_neighbors_4d(&point, active_cubes_old);
+                    // Active cubes stay active with 2 or 3 active neighbors,
+                    // inactive cubes become active with exactly 3 active neighbors.
+                    if count == 3 || (count == 2 && active_cubes_old.contains(&point)) {
+                        active_cubes_new.insert(point);
+                    }
+                }
+            }
+        }
+    }
+
+    active_cubes_new
+}
+
+/// Get the (minimum, maximum) coordinates with active cubes for each dimension of the Conway space.
+fn get_bounds_4d(active_cubes: &HashSet<[i32; 4]>) -> [(i32, i32); 4] {
+    let mut bounds = [(i32::MAX, i32::MIN); 4];
+    let dims: [usize; 4] = [0, 1, 2, 3];
+    for &point in active_cubes.iter() {
+        for &dim in dims.iter() {
+            if point[dim] < bounds[dim].0 { bounds[dim].0 = point[dim]; }
+            if point[dim] > bounds[dim].1 { bounds[dim].1 = point[dim]; }
+        }
+    }
+    bounds
+}
+
+fn count_active_neighbors_4d(point: &[i32; 4], active_cubes: &HashSet<[i32; 4]>) -> u32 {
+    let mut count: u32 = 0; 
+    for &dx in &[-1, 0, 1] {
+        for &dy in &[-1, 0, 1] {
+            for &dz in &[-1, -0, 1] {
+                for &dw in &[-1, -0, 1] {
+                    if !(dx == 0 && dy == 0 && dz == 0 && dw == 0)
+                        && (active_cubes.contains(
+                            &[point[0] + dx, point[1] + dy, point[2] + dz, point[3] + dw]))
+                    {
+                        count += 1;
+                    }
+                }
+            }
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part_1_example() {
+        let text = ".#.\n..#\n###";
+        let active_at_6 = solve_part_1(text);
+        assert_eq!(active_at_6, Ok(112));
+    }
+
+    #[test]
+    fn test_part_2_example() {
+        let text = ".#.\n..#\n###";
+        let active_at_6 = solve_part_2(text);
+        assert_eq!(active_at_6, Ok(848));
+    }
+
+    #[test]
+    fn test_simulate() {
+        let text = ".#.\n..#\n###";
+        assert_eq!(simulate(text, 3, 0), Ok(5));
+        assert_eq!(simulate(text, 3, 1), Ok(11));
+        assert_eq!(simulate(text, 4, 1), Ok(29));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_simulate_5d() {
+        simulate(".#.\n..#\n###", 5, 6).unwrap();
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_initial_slice(".#.\n..#\n#*#").unwrap_err();
+        assert_eq!(error.to_string(), "line 3, column 2: expected `.` or `#`, found `*`");
+    }
+}
@@ -0,0 +1,372 @@
+//! Day 18: Operation Order.
+//!
+//! Evaluate arithmetic expressions of `+`, `*` and parentheses under the alternative precedence
+//! rules of the math homework: no precedence in part 1, and `+` before `*` in part 2.
+
+use aoc_common::{
+    combinators::{cut, integer, lines, parse_all, recognize, separated, tag, Failure, PResult, Parser},
+    ParseError, ParseErrorKind,
+};
+use tracing::{instrument, trace};
+
+/// Part 2's operator precedence: addition is evaluated before multiplication.
+pub const PART_2_PRECEDENCE: [char; 2] = ['+', '*'];
+
+/// Solve part 1: the sum of the values of each line's expression, evaluated left to right.
+#[instrument(skip(text), ret)]
+pub fn solve_part_1(text: &str) -> Result<u64, ParseError> {
+    Ok(parse_expressions(text)?
+        .into_iter()
+        .map(|expr| {
+            let value = evaluate_expression(expr);
+            trace!(expression = expr, value, "evaluated an expression");
+            value
+        })
+        .sum())
+}
+
+/// Solve part 2: the sum of the values of each line's expression, using `PART_2_PRECEDENCE`.
+#[instrument(skip(text), ret)]
+pub fn solve_part_2(text: &str) -> Result<i64, ParseError> {
+    Ok(parse_expressions(text)?
+        .into_iter()
+        .map(|expr| evaluate_infix_expression(expr, &PART_2_PRECEDENCE))
+        .sum())
+}
+
+/// The most parentheses which may be open at once in an expression.
+pub const MAX_NESTING: usize = 100;
+
+/// Split the homework into expressions, one per line, and check that each is well formed.
+pub fn parse_expressions(text: &str) -> Result<Vec<&str>, ParseError> {
+    parse_all(text, lines(recognize(|s| expression(0, s))))
+}
+
+/// Check that `expression`, a slice of `input`, is written like the homework, e.g.
+/// `2 * 3 + (4 * 5)`: numbers and parenthesized expressions, separated by ` + ` or ` * `.
+/// Each number must fit in a `u32`, and at most `MAX_NESTING` parentheses may be open at once.
+/// The evaluators in this module assume their expressions have passed this check.
+pub fn check_expression(input: &str, expression: &str) -> Result<(), ParseError> {
+    match (|s| self::expression(0, s)).parse(expression) {
+        Ok((_, "")) => Ok(()),
+        Ok((_, rest)) => {
+            let offending = &rest[..rest.chars().next().map_or(0, char::len_utf8)];
+            Err(ParseError::new(input, offending, ParseErrorKind::Expected("` + ` or ` * `")))
+        }
+        Err(failure) => Err(failure.into_error(input)),
+    }
+}
+
+/// Parse an expression inside `depth` parentheses.
+fn expression(depth: usize, s: &str) -> PResult<'_, ()> {
+    if depth > MAX_NESTING {
+        return Err(Failure::fatal(&s[..0], ParseErrorKind::Expected("fewer nested parentheses")));
+    }
+    let number = integer::<u32>().map(|_| ());
+    let parenthesized = (tag("("), move |s| expression(depth + 1, s), cut(tag(")"))).map(|_| ());
+    let operand = number.or(parenthesized).expect("a number or `(`");
+    separated(operand, tag(" + ").or(tag(" * "))).map(|_| ()).parse(s)
+}
+
+/// Returns (expression before parens, expression in parens)
+fn get_subexpressions_before_and_in_parens(expression: &str) -> (&str, &str) {
+    assert_eq!(expression.chars().last().unwrap(), ')');
+
+    let mut matching_paren_position = 0;
+    let mut paren_count = 0;
+    for (i, c) in expression.char_indices().rev() {
+        match c {
+            ')' => paren_count += 1,
+            '(' => {
+                paren_count -= 1;
+                if paren_count == 0 {
+                    matching_paren_position = i;
+                    break
+                }
+            }
+            _ => {}
+        };
+    }
+
+    (expression.get(..matching_paren_position).unwrap(),
+     expression.get(matching_paren_position + 1..expression.len() - 1).unwrap())
+}
+
+/// Evaluate an expression with no operator precedence, i.e. strictly left to right
+/// except for parentheses. The result wraps around on overflow.
+pub fn evaluate_expression(expression: &str) -> u64 {
+    let last_char = expression.chars().last().unwrap();
+    if last_char == ')' {
+        let (before_expression, parens_expression) = get_subexpressions_before_and_in_parens(expression);
+        let parens_value = evaluate_expression(parens_expression);
+        if before_expression.is_empty() {
+            return parens_value;
+        }
+
+        let operator = before_expression.chars().nth(before_expression.len() - 2).unwrap();
+        let before_value = evaluate_expression(before_expression.get(..before_expression.len() - 3).unwrap());
+
+        return match operator {
+            '+' => before_value.wrapping_add(parens_value),
+            '*' => before_value.wrapping_mul(parens_value),
+            _ => panic!()
+        }
+    } else if ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'].contains(&last_char) {
+        let pieces: Vec<&str> = expression.rsplitn(3, ' ').collect();
+        let last_value = pieces[0].parse::<u64>().unwrap();
+        if pieces.len() == 1 {
+            return last_value;
+        }
+        assert_eq!(pieces.len(), 3);
+        assert_eq!(pieces[1].len(), 1);
+        let operator = pieces[1].chars().next().unwrap();
+
+        let rest_value = evaluate_expression(pieces[2]);
+
+        return match operator {
+            '+' => rest_value.wrapping_add(last_value),
+            '*' => rest_value.wrapping_mul(last_value),
+            _ => panic!()
+        }
+    } else {
+        panic!();
+    } 
+}
+
+//    Part 2
+// ------------
+
+fn find(v: &[char], c: char) -> usize {
+    v.iter().position(|&x| x == c).unwrap()
+}
+
+/// Convert an expression in infix notation to Reverse Polish Notation
+/// Operators earlier in `operator_precedence` have greater precedence.
+pub fn infix_expression_to_rpn(infix_expression: &str, operator_precedence: &[char]) -> String {
+    // Put spaces after parens so we can tokenize on spaces.
+    let infix_expression_spaced = infix_expression.replace("(", "( ").replace(")", " )");
+
+    let mut rpn_expression: String = "".to_string();
+    let mut operator_stack = Vec::<char>::new();
+    for token in infix_expression_spaced.trim().split_ascii_whitespace() {
+        if let Ok(_number) = token.parse::<u64>() {
+            // Token is a number.
+            rpn_expression.push_str(token);
+            rpn_expression.push(' ');
+        } else {
+            assert_eq!(token.len(), 1);
+            let token_char = token.chars().next().unwrap();
+            if token_char == '(' {
+                operator_stack.push(token_char);
+            } else if token_char == ')' {
+                while *operator_stack.last().unwrap() != '(' {  // empty operator_stack here means mismatched parentheses.
+                    rpn_expression.push(operator_stack.pop().unwrap());
+                    rpn_expression.push(' ');
+                }
+                if *operator_stack.last().unwrap() == '(' {
+                    operator_stack.pop();
+                }
+            } else if operator_precedence.contains(&token_char) {
+                // Token is an operator.
+                while !operator_stack.is_empty()
+                    && *operator_stack.last().unwrap() != '('
+                    && find(operator_precedence, *operator_stack.last().unwrap())
+                        <= find(operator_precedence, token_char)
+                {
+                    rpn_expression.push(operator_stack.pop().unwrap());
+                    rpn_expression.push(' ');
+                }
+                operator_stack.push(token_char);
+            }
+        }
+    }
+    while let Some(op) = operator_stack.pop() {
+        
+        if op == '(' || op == ')' { panic!(); } // Mismatched parentheses.
+        rpn_expression.push(op);
+        rpn_expression.push(' ');
+    }
+
+    rpn_expression.trim().to_string()
+}
+
+/// Evaluate a Reverse Polish Notation expression. The result wraps around on overflow.
+pub fn evaluate_rpn_expression(rpn_expression: &str) -> i64 {
+    let mut stack = Vec::<i64>::new();
+    for token in rpn_expression.trim().split_ascii_whitespace() {
+        if let Ok(number) = token.parse::<i64>() {
+            stack.push(number);
+        } else {
+            assert_eq!(token.len(), 1);
+            let token_char = token.chars().next().unwrap();
+            let number2 = stack.pop().unwrap();
+            let number1 = stack.pop().unwrap();
+            stack.push(
+                match token_char {
+                    '+' => number1.wrapping_add(number2),
+                    '-' => number1.wrapping_sub(number2),
+                    '*' => number1.wrapping_mul(number2),
+                    '/' => number1 / number2,
+                    _ => panic!()
+                }
+            );
+        }
+    }
+    stack.pop().unwrap()
+}
+
+/// Evaluate an expression in infix notation, using a provided operator precedence.
+pub fn evaluate_infix_expression(infix_expression: &str, operator_precedence: &[char]) -> i64 {
+    let rpn_expression = infix_expression_to_rpn(infix_expression, operator_precedence);
+    let value = evaluate_rpn_expression(&rpn_expression);
+    trace!(infix_expression, rpn_expression = %rpn_expression, value, "evaluated an expression");
+    value
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    use rstest::*;
+
+    /// Well formed expressions of single digits, `+`, `*` and parentheses, like the homework.
+    fn expression() -> impl Strategy<Value = String> {
+        (0u32..10).prop_map(|digit| digit.to_string()).prop_recursive(6, 32, 2, |inner| prop_oneof![
+            (inner.clone(), inner.clone()).prop_map(|(a, b)| format!("{} + {}", a, b)),
+            (inner.clone(), inner.clone()).prop_map(|(a, b)| format!("{} * {}", a, b)),
+            inner.prop_map(|a| format!("({})", a)),
+        ])
+    }
+
+    /// A reference evaluator, by precedence climbing, independent of the RPN conversion.
+    /// Operators with a higher `level` bind tighter, and operators on the same level
+    /// are evaluated left to right.
+    fn reference_evaluate(tokens: &[char], position: &mut usize, level: fn(char) -> u8, min_level: u8) -> i64 {
+        let mut value = if tokens[*position] == '(' {
+            *position += 1;
+            let value = reference_evaluate(tokens, position, level, 0);
+            *position += 1; // The closing `)`.
+            value
+        } else {
+            *position += 1;
+            tokens[*position - 1].to_digit(10).unwrap() as i64
+        };
+        while let Some(&operator) = tokens.get(*position) {
+            if operator == ')' || level(operator) < min_level {
+                break;
+            }
+            *position += 1;
+            let rhs = reference_evaluate(tokens, position, level, level(operator) + 1);
+            value = match operator {
+                '+' => value.wrapping_add(rhs),
+                '*' => value.wrapping_mul(rhs),
+                _ => unreachable!(),
+            };
+        }
+        value
+    }
+
+    fn reference_value(expression: &str, level: fn(char) -> u8) -> i64 {
+        let tokens = expression.chars().filter(|&c| c != ' ').collect::<Vec<char>>();
+        reference_evaluate(&tokens, &mut 0, level, 0)
+    }
+
+    #[rstest(infix, correct_rpn,
+        case("3 + 4", "3 4 +"),
+        case("3 + 4 * (2 - 1)", "3 4 2 1 - * +"),
+    )]
+    fn test_infix_expression_to_rpn(infix: &str, correct_rpn: &str) {
+        let operator_precedence = vec!['*', '/', '+', '-']; // Normal math.
+        assert_eq!(infix_expression_to_rpn(infix, &operator_precedence), correct_rpn);
+    }
+
+    #[rstest(rpn_expression, correct_value,
+        case("3 4 +", 7),
+        case("3 4 5 * -", -17),
+    )]
+    fn test_evaluate_rpn_expression(rpn_expression: &str, correct_value: i64) {
+        assert_eq!(evaluate_rpn_expression(rpn_expression), correct_value);
+    }
+
+    #[rstest(
+        expression, correct_value,
+        case("1 + 2 * 3 + 4 * 5 + 6", 71),
+        case("1 + (2 * 3) + (4 * (5 + 6))", 51),
+        case("2 * 3 + (4 * 5)", 26),
+        case("5 + (8 * 3 + 9 + 3 * 4 * 3)", 437),
+        case("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", 12240),
+        case("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2", 13632),
+    )]
+    fn test_examples_part1(expression: &str, correct_value: u64) {
+        assert_eq!(evaluate_expression(expression), correct_value);
+    }
+    
+    #[rstest(
+        expression, correct_value,
+        case("1 + 2 * 3 + 4 * 5 + 6", 231),
+        case("1 + (2 * 3) + (4 * (5 + 6))", 51),
+        case("2 * 3 + (4 * 5)", 46),
+        case("5 + (8 * 3 + 9 + 3 * 4 * 3)", 1445),
+        case("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", 669060),
+        case("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2", 23340),
+    )]
+    fn test_examples_part2(expression: &str, correct_value: i64) {
+        let operator_precedence = vec!['+', '*']; // Part 2's operator precedence.
+        assert_eq!(evaluate_infix_expression(expression, &operator_precedence), correct_value);
+    }
+
+    #[test]
+    fn test_check_expression_errors() {
+        let text = "1 + (2 * 3)\n2 * (3 + 4\n";
+        let error = parse_expressions(text).unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 11: expected `)`, found the end of the line");
+
+        for (expression, column) in [("1 + 2)", 6), ("1 - 2", 2), ("1 + * 2", 5), ("1 +", 2), ("()", 2)] {
+            let error = check_expression(expression, expression).unwrap_err();
+            assert_eq!(error.column, column, "{}", expression);
+        }
+        let expression = "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2";
+        assert!(check_expression(expression, expression).is_ok());
+    }
+
+    #[test]
+    fn test_number_too_large() {
+        let error = parse_expressions("1 + 2\n3 * 44444444444444444444444444\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 5: invalid number `44444444444444444444444444`");
+    }
+
+    #[test]
+    fn test_nesting_too_deep() {
+        let expression = format!("{}1{}", "(".repeat(MAX_NESTING + 1), ")".repeat(MAX_NESTING + 1));
+        let error = check_expression(&expression, &expression).unwrap_err();
+        assert_eq!(error.column, MAX_NESTING + 2);
+        let expression = format!("{}1{}", "(".repeat(MAX_NESTING), ")".repeat(MAX_NESTING));
+        assert!(check_expression(&expression, &expression).is_ok());
+    }
+
+    proptest! {
+        #[test]
+        fn generated_expressions_are_well_formed(expression in expression()) {
+            prop_assert!(check_expression(&expression, &expression).is_ok());
+        }
+
+        #[test]
+        fn standard_precedence_agrees_with_reference(expression in expression()) {
+            let standard = |operator| if operator == '*' {1} else {0};
+            prop_assert_eq!(evaluate_infix_expression(&expression, &['*', '+']), reference_value(&expression, standard));
+        }
+
+        #[test]
+        fn part_2_precedence_agrees_with_reference(expression in expression()) {
+            let addition_first = |operator| if operator == '+' {1} else {0};
+            prop_assert_eq!(evaluate_infix_expression(&expression, &PART_2_PRECEDENCE),
+                reference_value(&expression, addition_first));
+        }
+
+        #[test]
+        fn no_precedence_agrees_with_reference(expression in expression()) {
+            prop_assert_eq!(evaluate_expression(&expression) as i64, reference_value(&expression, |_| 0));
+        }
+    }
+}
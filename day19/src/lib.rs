@@ -0,0 +1,252 @@
+//! Day 19: Monster Messages.
+//!
+//! Count the messages which completely match rule 0 of a set of grammar-like message rules.
+
+use std::collections::{HashMap, HashSet};
+use aoc_common::{
+    combinators::{consumed, integer, lines, paragraph, parse_all, rest, satisfy, section, split, tag, Parser},
+    ParseError, ParseErrorKind,
+};
+use tracing::{debug, instrument, trace};
+
+
+/// One message rule, like `1: 2 3 | 3 2` or `4: "a"`.
+#[derive(Debug)]
+pub struct Rule {
+    pub id: usize,
+    /// Alternative sequences of rules, any one of which can match.
+    pub subrule_id_lists: Vec<Vec<usize>>,
+    /// The single character matched by a literal rule. Literal rules have no subrules.
+    pub literal_match: Option<char>,
+}
+
+impl Rule {
+    fn matches_start(&self, ruleset: &HashMap<usize, Rule>, s: &str) -> Option<usize> {
+        if let Some(literal_match) = self.literal_match {
+            if s.starts_with(literal_match) {
+                return Some(literal_match.len_utf8());
+            } else {
+                return None;
+            }
+        } else {
+            for subrule_id_list in &self.subrule_id_lists {
+                let mut position = 0;
+                let mut no_match = false;
+                for rule_id in subrule_id_list {
+                    let rule = ruleset.get(rule_id).unwrap();
+                    match rule.matches_start(ruleset, &s[position..]) {
+                        None => {
+                            no_match = true;
+                            break;
+                        },
+                        Some(match_length) => {
+                            position += match_length;
+                        },
+                    }
+                }
+                if !no_match {
+                    return Some(position);
+                }
+            }
+            return None;
+        }
+    }
+
+    /// Check if the whole of `s` matches this rule.
+    pub fn matches(&self, ruleset: &HashMap<usize, Rule>, s: &str) -> bool {
+        match self.matches_start(ruleset, s) {
+            None => false,
+            Some(match_length) => {
+                return match_length == s.len();
+            }
+        }
+    }
+}
+
+/// Split the input into the rules, and the messages which follow them after a blank line.
+pub fn parse_input(text: &str) -> Result<(HashMap<usize, Rule>, Vec<&str>), ParseError> {
+    let (rules, messages) = parse_all(text, (
+        paragraph(lines(rule())),
+        section("the messages", paragraph(lines(rest()))),
+    ))?;
+    let ruleset = build_ruleset(text, rules)?;
+    debug!(rules = ruleset.len(), messages = messages.len(), "parsed the input");
+    if !ruleset.contains_key(&0) {
+        return Err(ParseError::missing_section(text, "rule 0"));
+    }
+    Ok((ruleset, messages))
+}
+
+/// Solve part 1: the number of messages which completely match rule 0.
+#[instrument(skip(text), ret)]
+pub fn solve_part_1(text: &str) -> Result<usize, ParseError> {
+    let (ruleset, messages) = parse_input(text)?;
+    Ok(count_matching_messages(&ruleset, &messages))
+}
+
+/// Count the messages which completely match rule 0.
+pub fn count_matching_messages(ruleset: &HashMap<usize, Rule>, messages: &[&str]) -> usize {
+    let rule0 = ruleset.get(&0).unwrap();
+    messages
+        .iter()
+        .filter(|message| {
+            let matches = rule0.matches(ruleset, message);
+            trace!(message, matches, "checked a message");
+            matches
+        })
+        .count()
+}
+
+/// Parse the rules, one per line, into a map from rule ID to rule.
+/// Errors give the index of the rule string, counting from 1, as the line number.
+/// Every rule which is referred to must be defined, and no rule may refer back to itself,
+/// directly or through other rules.
+pub fn parse_rules(rule_strings: Vec<&str>) -> Result<HashMap<usize, Rule>, ParseError> {
+    let text = rule_strings.join("\n");
+    build_ruleset(&text, parse_all(&text, lines(rule()))?)
+}
+
+/// Parser for one rule, like `1: 2 3 | 3 2` or `4: "a"`.
+/// Gives the rule, and the text of each subrule reference in it.
+fn rule<'a>() -> impl Parser<'a, (Rule, Vec<&'a str>)> {
+    let literal = (tag("\""), (satisfy("a character", |_| true), tag("\"")).expect("one quoted character like `\"a\"`"))
+        .map(|(_, (c, _))| (Some(c), Vec::new()));
+    let subrule_id_lists = split(" | ", split(" ", consumed(integer::<usize>())))
+        .map(|lists: Vec<Vec<(&str, usize)>>| (None, lists));
+    let id = (integer::<usize>(), tag(": ")).map(|(id, _)| id).expect("a rule like `1: 2 3 | 3 2`");
+    (id, literal.or(subrule_id_lists)).map(|(id, (literal_match, lists))| {
+        let references = lists.iter().flatten().map(|&(text, _)| text).collect();
+        let subrule_id_lists = lists.into_iter().map(|list| list.into_iter().map(|(_, id)| id).collect()).collect();
+        (Rule { id, subrule_id_lists, literal_match }, references)
+    })
+}
+
+/// Collect parsed rules into a map from rule ID to rule, and check their subrule references,
+/// which are slices of `text`.
+fn build_ruleset(text: &str, rules: Vec<(Rule, Vec<&str>)>) -> Result<HashMap<usize, Rule>, ParseError> {
+    let mut ruleset = HashMap::<usize, Rule>::new();
+    // Each subrule reference, with the ID of the rule it is in.
+    let mut references = Vec::<(usize, &str)>::new();
+    for (rule, subrule_strs) in rules {
+        references.extend(subrule_strs.into_iter().map(|subrule_str| (rule.id, subrule_str)));
+        ruleset.insert(rule.id, rule);
+    }
+
+    for &(_, subrule_str) in &references {
+        if !ruleset.contains_key(&subrule_str.parse::<usize>().unwrap()) {
+            return Err(ParseError::new(text, subrule_str, ParseErrorKind::Undefined("rule")));
+        }
+    }
+    // Matching a rule which refers back to itself would recurse forever.
+    for (rule_id, subrule_str) in references {
+        if leads_to(&ruleset, subrule_str.parse().unwrap(), rule_id) {
+            return Err(ParseError::new(text, subrule_str,
+                ParseErrorKind::Expected("a rule which does not lead back to this rule")));
+        }
+    }
+
+    return Ok(ruleset);
+}
+
+/// Check if rule `from` is rule `to`, or refers to it through any chain of subrules.
+fn leads_to(ruleset: &HashMap<usize, Rule>, from: usize, to: usize) -> bool {
+    let mut search_stack = vec![from];
+    let mut visited = HashSet::<usize>::new();
+    while let Some(rule_id) = search_stack.pop() {
+        if rule_id == to {
+            return true;
+        }
+        if visited.insert(rule_id) {
+            search_stack.extend(ruleset[&rule_id].subrule_id_lists.iter().flatten());
+        }
+    }
+    return false;
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part_1_first_example() {
+        let mut ruleset = HashMap::<usize, Rule>::new();
+        ruleset.insert(
+            0, Rule {
+                id: 0,
+                subrule_id_lists: vec![vec![1, 2]],
+                literal_match: None,
+            }
+        );
+        ruleset.insert(
+            1, Rule {
+                id: 1,
+                subrule_id_lists: vec![],
+                literal_match: Some('a'),
+            }
+        );
+        ruleset.insert(
+            2, Rule {
+                id: 2,
+                subrule_id_lists: vec![vec![1, 3], vec![3, 1]],
+                literal_match: None,
+            }
+        );
+        ruleset.insert(
+            3, Rule {
+                id: 3,
+                subrule_id_lists: vec![],
+                literal_match: Some('b'),
+            }
+        );
+
+        assert!(ruleset.get(&1).unwrap().matches(&ruleset, "a"));
+        assert!(ruleset.get(&3).unwrap().matches(&ruleset, "b"));
+        assert!(ruleset.get(&2).unwrap().matches(&ruleset, "ab"));
+        assert!(ruleset.get(&2).unwrap().matches(&ruleset, "ba"));
+        assert!(ruleset.get(&0).unwrap().matches(&ruleset, "aab"));
+        assert!(ruleset.get(&0).unwrap().matches(&ruleset, "aba"));
+    }
+
+    #[test]
+    fn test_part_1_parse_rules() {
+        let rule_strings = vec![
+            "0: 1 2",
+            "1: \"a\"",
+            "2: 1 3 | 3 1",
+            "3: \"b\"",
+        ];
+
+        let ruleset = parse_rules(rule_strings).unwrap();
+
+        assert_eq!(ruleset.len(), 4);
+        assert!(ruleset.get(&1).unwrap().matches(&ruleset, "a"));
+        assert!(ruleset.get(&3).unwrap().matches(&ruleset, "b"));
+        assert!(ruleset.get(&2).unwrap().matches(&ruleset, "ab"));
+        assert!(ruleset.get(&2).unwrap().matches(&ruleset, "ba"));
+        assert!(ruleset.get(&0).unwrap().matches(&ruleset, "aab"));
+        assert!(ruleset.get(&0).unwrap().matches(&ruleset, "aba"));
+        
+    }
+
+    #[test]
+    fn test_multi_byte_literal() {
+        let ruleset = parse_rules(vec!["0: 1 2", "1: \"é\"", "2: \"a\""]).unwrap();
+        assert!(ruleset.get(&0).unwrap().matches(&ruleset, "éa"));
+        assert!(!ruleset.get(&0).unwrap().matches(&ruleset, "éé"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_rules(vec!["0: 1 2", "1: \"a\"", "2: 1 x"]).unwrap_err();
+        assert_eq!(error.to_string(), "line 3, column 6: expected a number, found `x`");
+        let error = parse_rules(vec!["0: 1 2", "1: \"a\""]).unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 6: undefined rule `2`");
+        let error = parse_rules(vec!["0: 1 | ", "1: \"a\""]).unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 8: expected a number, found the end of the line");
+        let error = parse_rules(vec!["0: 1 2", "1: \"a\"", "2: 1 | 2 0"]).unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 6: expected a rule which does not lead back to this rule, found `2`");
+        let error = parse_input("0: 1\n1: \"a\"\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 3, column 1: input ended before the messages");
+    }
+}
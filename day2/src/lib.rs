@@ -0,0 +1,174 @@
+//! Day 2: Password Philosophy.
+//!
+//! Count the passwords in a database which comply with their password policy.
+//! The two parts interpret the same policy text in different ways.
+
+use aoc_common::{combinators::{integer, lines, parse_all, satisfy, tag, take_while1, Parser}, ParseError};
+use tracing::{instrument, trace};
+
+/// The Part 1 policy: `ch` must appear between `min` and `max` times (inclusive) in the password.
+#[derive(Debug)]
+pub struct Policy1 {
+    pub ch: char,
+    pub min: u32,
+    pub max: u32,
+}
+
+/// The Part 2 policy: `ch` must appear at exactly one of `pos1` and `pos2` in the password.
+#[derive(Debug)]
+pub struct Policy2 {
+    pub ch: char,
+    // Note: these positions index from 0 in the password string.
+    pub pos1: usize,
+    pub pos2: usize,
+}
+
+
+/// Parse the password database into (part 1 policy, part 2 policy, password) entries.
+/// Each line of the database holds one policy and password, like `1-3 a: abcde`.
+pub fn parse_password_database(text: &str) -> Result<Vec<(Policy1, Policy2, &str)>, ParseError> {
+    // Positions count from 1 in the policy, so 0 is not a valid position for part 2.
+    let number = || integer::<u32>().verify(|&value| value >= 1, "a number of at least 1");
+    let lowercase = |c: char| c.is_ascii_lowercase();
+    let entry = (number(), tag("-"), number(), tag(" "), satisfy("a letter", lowercase), tag(": "),
+        take_while1("a password", lowercase))
+        .map(|(first, _, second, _, ch, _, password)| {
+            let policy1 = Policy1 { ch, min: first, max: second };
+            let policy2 = Policy2 { ch, pos1: first as usize - 1, pos2: second as usize - 1 };
+            (policy1, policy2, password)
+        })
+        .expect("a policy and password like `1-3 a: abcde`");
+    parse_all(text, lines(entry))
+}
+
+/// Solve part 1: the number of passwords which are valid under the Part 1 policy.
+#[instrument(skip(text), ret)]
+pub fn solve_part_1(text: &str) -> Result<usize, ParseError> {
+    Ok(parse_password_database(text)?
+        .iter()
+        .filter(|(policy1, _, password)| check_password_policy_1(policy1, password))
+        .count())
+}
+
+/// Solve part 2: the number of passwords which are valid under the Part 2 policy.
+#[instrument(skip(text), ret)]
+pub fn solve_part_2(text: &str) -> Result<usize, ParseError> {
+    Ok(parse_password_database(text)?
+        .iter()
+        .filter(|(_, policy2, password)| check_password_policy_2(policy2, password))
+        .count())
+}
+
+/// Check if a password is valid according to the Part 1 policy interpretation.
+pub fn check_password_policy_1(policy: &Policy1, password: &str) -> bool {
+    let mut count = 0;
+    for ch in password.chars() {
+        if ch == policy.ch {
+            count += 1;
+        }
+    }
+    let valid = policy.min <= count && count <= policy.max;
+    trace!(?policy, password, count, valid, "checked a password");
+    valid
+}
+
+
+/// Check if a password is valid according to the Part 2 policy interpretation.
+pub fn check_password_policy_2(policy: &Policy2, password: &str) -> bool {
+    if policy.pos1 >= password.len() || policy.pos2 >= password.len() {
+        return false;
+    }
+
+    let c1 = password.as_bytes()[policy.pos1] as char;
+    let c2 = password.as_bytes()[policy.pos2] as char;
+    let valid = (c1 == policy.ch) != (c2 == policy.ch);
+    trace!(?policy, password, valid, "checked a password");
+    valid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pw1_ok() {
+        let policy = Policy1 {
+            ch: 'a',
+            min: 2,
+            max: 4,
+        };
+        let password = "skdjaaakla";
+        let complies_with_policy = check_password_policy_1(&policy, password);
+        assert!(complies_with_policy);
+    }
+
+    #[test]
+    fn pw1_bad() {
+        let policy = Policy1 {
+            ch: 'a',
+            min: 2,
+            max: 4,
+        };
+        let password = "skdjkla";
+        let complies_with_policy = check_password_policy_1(&policy, password);
+        assert!(!complies_with_policy);
+    }
+
+    #[test]
+    fn pw2_example1() {
+        let policy = Policy2 {
+            ch: 'a',
+            pos1: 1 - 1,
+            pos2: 3 - 1,
+        };
+        let password = "abcde";
+        let complies_with_policy = check_password_policy_2(&policy, password);
+        assert!(complies_with_policy);
+    }
+
+    #[test]
+    fn pw2_example2() {
+        let policy = Policy2 {
+            ch: 'b',
+            pos1: 1 - 1,
+            pos2: 3 - 1,
+        };
+        let password = "cdefg";
+        let complies_with_policy = check_password_policy_2(&policy, password);
+        assert!(!complies_with_policy);
+    }
+
+    #[test]
+    fn pw2_example3() {
+        let policy = Policy2 {
+            ch: 'c',
+            pos1: 2 - 1,
+            pos2: 9 - 1,
+        };
+        let password = "ccccccccc";
+        let complies_with_policy = check_password_policy_2(&policy, password);
+        assert!(!complies_with_policy);
+    }
+
+    #[test]
+    fn pw2_position_past_end() {
+        let policy = Policy2 {
+            ch: 'a',
+            pos1: 1 - 1,
+            pos2: 9 - 1,
+        };
+        assert!(!check_password_policy_2(&policy, "abc"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let text = "1-3 a: abcde\n1-3 b cdefg\n";
+        let error = parse_password_database(text).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.to_string(),
+            "line 2, column 1: expected a policy and password like `1-3 a: abcde`, found `1-3 b cdefg`");
+
+        let error = parse_password_database("0-3 a: abcde").unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 1: expected a number of at least 1, found `0`");
+    }
+}
@@ -0,0 +1,128 @@
+//! Day 3: Toboggan Trajectory.
+//!
+//! Count the trees a toboggan hits on a straight path down a slope of open squares (`.`)
+//! and trees (`#`), where the terrain pattern repeats infinitely to the right.
+
+use aoc_common::{Cell, Grid, ParseError, Wrap};
+use tracing::{instrument, trace};
+
+/// The (right, down) slopes whose tree counts are multiplied together in part 2.
+pub const PART_2_SLOPES: [(i32, usize); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+/// One square of the terrain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Terrain {
+    Open,
+    Tree,
+}
+
+impl Cell for Terrain {
+    const EXPECTED: &'static str = "`.` or `#`";
+
+    fn from_char(c: char) -> Option<Terrain> {
+        match c {
+            '.' => Some(Terrain::Open),
+            '#' => Some(Terrain::Tree),
+            _ => None,
+        }
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            Terrain::Open => '.',
+            Terrain::Tree => '#',
+        }
+    }
+}
+
+/// Parse the content of the input file.
+/// Every row must be made of `.` and `#`, and be the same width.
+/// The terrain wraps horizontally, because the pattern repeats infinitely to the right.
+pub fn load_terrain(text: &str) -> Result<Grid<Terrain>, ParseError> {
+    Ok(Grid::parse(text)?.with_wrap(Wrap { horizontal: true, vertical: false }))
+}
+
+/// Solve part 1: the number of trees on the path which goes right 3, down 1.
+#[instrument(skip(text), ret)]
+pub fn solve_part_1(text: &str) -> Result<u32, ParseError> {
+    Ok(count_trees(0, 3, 1, &load_terrain(text)?))
+}
+
+/// Solve part 2: the product of the tree counts on each of `PART_2_SLOPES`.
+#[instrument(skip(text), ret)]
+pub fn solve_part_2(text: &str) -> Result<u64, ParseError> {
+    let terrain = load_terrain(text)?;
+    Ok(PART_2_SLOPES
+        .iter()
+        .map(|&(right, down)| count_trees(0, right, down, &terrain) as u64)
+        .product())
+}
+
+/// Count the number of trees encountered on the toboggan's path through the terrain.
+/// `starting_col` is the column of the first row which the toboggan starts at.
+/// `right_step` is how many spaces the toboggan moves right on each time-step.
+///     Negative values make the toboggan move left.
+/// `down_step` is how many spaces the toboggan moves down on each time-step.
+#[instrument(level = "debug", skip(terrain), ret)]
+pub fn count_trees(
+        starting_col: i32, right_step: i32, down_step: usize,
+        terrain: &Grid<Terrain>) -> u32 {
+    let mut tree_count = 0;
+    // The position of the toboggan in the terrain pattern.
+    let mut position = terrain.step((0, 0), (0, starting_col as isize));
+    while let Some(current) = position {
+        if terrain[current] == Terrain::Tree {
+            trace!(row = current.0, column = current.1, "hit a tree");
+            tree_count += 1;
+        }
+        // The toboggan's path ends when it moves past the bottom row.
+        position = terrain.step(current, (down_step as isize, right_step as isize));
+    }
+    tree_count
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_example_input() {
+        let contents = fs::read_to_string("./test_input.txt").unwrap();
+        let terrain = load_terrain(&contents).unwrap();
+        let tree_count = count_trees(0, 3, 1, &terrain);
+        assert_eq!(tree_count, 7);
+    }
+    #[test]
+    fn test_example_input_11() {
+        let contents = fs::read_to_string("./test_input.txt").unwrap();
+        let terrain = load_terrain(&contents).unwrap();
+        let tree_count = count_trees(0, 1, 1, &terrain);
+        assert_eq!(tree_count, 2);
+    }
+
+    #[test]
+    fn test_example_input_51() {
+        let contents = fs::read_to_string("./test_input.txt").unwrap();
+        let terrain = load_terrain(&contents).unwrap();
+        let tree_count = count_trees(0, 5, 1, &terrain);
+        assert_eq!(tree_count, 3);
+    }
+
+    #[test]
+    fn test_example_input_71() {
+        let contents = fs::read_to_string("./test_input.txt").unwrap();
+        let terrain = load_terrain(&contents).unwrap();
+        let tree_count = count_trees(0, 7, 1, &terrain);
+        assert_eq!(tree_count, 4);
+    }
+
+    #[test]
+    fn test_example_input_12() {
+        let contents = fs::read_to_string("./test_input.txt").unwrap();
+        let terrain = load_terrain(&contents).unwrap();
+        let tree_count = count_trees(0, 1, 2, &terrain);
+        assert_eq!(tree_count, 2);
+    }
+}
@@ -0,0 +1,112 @@
+//! Day 4: Passport Processing.
+//!
+//! Count the passports in a batch file which have all the required fields, with valid values.
+
+use aoc_common::{combinators::{key_value, paragraphs, parse_all, split_whitespace, tag, take_while1, Parser}, ParseError};
+use tracing::{debug, instrument, trace};
+
+
+/// Solve part 2: the number of valid passports.
+#[instrument(skip(text), ret)]
+pub fn solve_part_2(text: &str) -> Result<u32, ParseError> {
+    count_valid_passports(text)
+}
+
+/// A passport's `key:value` fields, in the order they are in the batch file.
+pub type Passport<'a> = Vec<(&'a str, &'a str)>;
+
+/// The fields which every valid passport has. The `cid` field is optional.
+const REQUIRED_FIELDS: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+
+/// Parse the batch file into passports, which are separated by blank lines.
+/// Each passport is a list of `key:value` fields separated by spaces or newlines.
+/// The field values are not checked.
+pub fn parse_passports(text: &str) -> Result<Vec<Passport<'_>>, ParseError> {
+    parse_all(text, paragraphs(split_whitespace(key_value().expect("a field like `byr:1937`"))))
+}
+
+/// Count the passports which have all the required fields, where each field's value is valid.
+/// Passports are separated by blank lines. The `cid` field is optional.
+pub fn count_valid_passports(text: &str) -> Result<u32, ParseError> {
+    let passports = parse_passports(text)?;
+
+    let mut valid_passport_count: u32 = 0;
+    for (index, passport) in passports.iter().enumerate() {
+        let valid = REQUIRED_FIELDS.iter().all(|&field| {
+            match passport.iter().find(|&&(key, _)| key == field) {
+                None => {
+                    trace!(index, field, "field missing");
+                    false
+                }
+                Some(&(_, value)) => {
+                    let valid = is_valid_value(field, value);
+                    trace!(index, field, value, valid, "checked field");
+                    valid
+                }
+            }
+        });
+        debug!(index, valid, "checked passport");
+        if valid {
+            valid_passport_count += 1;
+        }
+    }
+    Ok(valid_passport_count)
+}
+
+/// Check the value of one of the required fields.
+fn is_valid_value(field: &str, value: &str) -> bool {
+    let digits = || take_while1("a digit", |c| c.is_ascii_digit());
+    let year = |min: u32, max: u32| digits().verify(move |year: &&str| {
+        year.len() == 4 && year.parse().is_ok_and(|year: u32| (min..=max).contains(&year))
+    }, "a year");
+    match field {
+        "byr" => is_match(value, year(1920, 2002)),
+        "iyr" => is_match(value, year(2010, 2020)),
+        "eyr" => is_match(value, year(2020, 2030)),
+        "hgt" => {
+            // A height too large for a `u32` is out of range, so the passport is invalid.
+            let height = digits().map(|height: &str| height.parse::<u32>().unwrap_or(u32::MAX));
+            is_match(value, (height, tag("cm").or(tag("in"))).verify(|&(height, units)| match units {
+                "cm" => (150..=193).contains(&height),
+                _ => (59..=76).contains(&height),
+            }, "a height"))
+        }
+        "hcl" => is_match(value, (tag("#"), take_while1("a hex digit", |c| matches!(c, '0'..='9' | 'a'..='f'))
+            .verify(|color: &&str| color.len() == 6, "6 hex digits"))),
+        "ecl" => ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&value),
+        "pid" => is_match(value, digits().verify(|id: &&str| id.len() == 9, "9 digits")),
+        _ => true,
+    }
+}
+
+/// Check if all of `value` matches `parser`.
+fn is_match<'a, T>(value: &'a str, parser: impl Parser<'a, T>) -> bool {
+    matches!(parser.parse(value), Ok((_, "")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_example_input() {
+        let text = fs::read_to_string("./example_input.txt").unwrap();
+        let valid_passport_count = count_valid_passports(&text).unwrap();
+        assert_eq!(valid_passport_count, 2);
+    }
+
+    #[test]
+    fn test_example_input_invalid() {
+        let text = fs::read_to_string("./example_input_invalid.txt").unwrap();
+        let valid_passport_count = count_valid_passports(&text).unwrap();
+        assert_eq!(valid_passport_count, 0);
+    }
+
+    #[test]
+    fn test_malformed_field() {
+        let text = "ecl:gry pid:860033327\n\nhcl:#cfa07d byr 1929\n";
+        let error = count_valid_passports(text).unwrap_err();
+        assert_eq!(error.to_string(), "line 3, column 13: expected a field like `byr:1937`, found `byr`");
+    }
+}
@@ -0,0 +1,162 @@
+//! Day 5: Binary Boarding.
+//!
+//! Decode binary space partitioning seat codes like `FBFBBFFRLR` into seats on a plane.
+
+use aoc_common::{combinators::{lines, parse_all, rest, Parser}, ParseError};
+use tracing::{debug, instrument, trace};
+
+/// Parse one seat code per line, and compute each seat's ID.
+pub fn parse_seat_ids(text: &str) -> Result<Vec<u32>, ParseError> {
+    parse_all(text, lines(rest().try_map(compute_seat_id, "a seat code like `FBFBBFFRLR`")))
+}
+
+/// Solve part 1: the highest seat ID of any boarding pass.
+#[instrument(skip(text), ret)]
+pub fn solve_part_1(text: &str) -> Result<u32, ParseError> {
+    Ok(parse_seat_ids(text)?.into_iter().max().unwrap())
+}
+
+/// Solve part 2: the ID of your seat, which is the only one missing from the list.
+///
+/// # Panics
+/// Panics if no seat ID is missing.
+#[instrument(skip(text), ret)]
+pub fn solve_part_2(text: &str) -> Result<u32, ParseError> {
+    Ok(find_missing_seat_id(&parse_seat_ids(text)?).expect("no seat ID is missing"))
+}
+
+/// Find the missing seat ID: the gap in an otherwise contiguous range of seat IDs.
+/// Returns `None` if there is no gap.
+pub fn find_missing_seat_id(seat_ids: &[u32]) -> Option<u32> {
+    let mut seat_ids = seat_ids.to_vec();
+    seat_ids.sort();
+    for i in 0..(seat_ids.len().saturating_sub(1)) {
+        if seat_ids[i] + 1 != seat_ids[i + 1] {
+            debug!(before = seat_ids[i], after = seat_ids[i + 1], "found the gap");
+            return Some(seat_ids[i] + 1);
+        }
+    }
+    return None;
+}
+
+/// Compute the seat ID, `row * 8 + column`, of a seat code.
+/// Returns `None` if the seat code is invalid.
+pub fn compute_seat_id(partition_code: &str) -> Option<u32> {
+    let seat = decode_seat(partition_code);
+    trace!(partition_code, ?seat, "decoded seat");
+    seat.map(|(row, column)| row * 8 + column)
+}
+
+/// Decode a seat code into its (row, column).
+/// The first 7 characters (`F` or `B`) give the row, and the last 3 (`L` or `R`) give the column.
+/// Returns `None` if the seat code is invalid.
+pub fn decode_seat(partition_code: &str) -> Option<(u32, u32)> {
+    let mut row: u32 = 0;
+    let mut column: u32 = 0;
+
+    let row_bits = 7;
+    let column_bits = 3;
+
+    if partition_code.len() != row_bits + column_bits {
+        return None;
+    }
+
+    // `get` instead of indexing, because a multi-byte character could straddle `row_bits`.
+    let (row_code, column_code) = (partition_code.get(..row_bits)?, partition_code.get(row_bits..)?);
+
+    // Decode the row
+    for (i, ch) in row_code.chars().enumerate() {
+        let bit = match ch {
+            'F' => false, // Front of the partition
+            'B' => true, // Back of the partition
+            _ => return None, // `partition_code` is invalid.
+        };
+        if bit { row |= 1 << (row_bits - i - 1); }
+    }
+
+    // Decode the column
+    for (i, ch) in column_code.chars().enumerate() {
+        let bit = match ch {
+            'L' => false, // Left of the partition
+            'R' => true, // Right of the partition
+            _ => return None, // `partition_code` is invalid.
+        };
+        if bit { column |= 1 << (column_bits - i - 1); }
+    }
+    return Some((row, column));
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Encode a (row, column) as a seat code: the inverse of `decode_seat`.
+    fn encode_seat(row: u32, column: u32) -> String {
+        let row_code = (0..7).rev().map(|bit| if row >> bit & 1 == 1 {'B'} else {'F'});
+        let column_code = (0..3).rev().map(|bit| if column >> bit & 1 == 1 {'R'} else {'L'});
+        row_code.chain(column_code).collect()
+    }
+
+    #[test]
+    fn test_part1_example1() {
+        let (row, column) = decode_seat("FBFBBFFRLR").unwrap();
+        assert_eq!(row, 44);
+        assert_eq!(column, 5);
+    }
+
+    #[test]
+    fn test_part1_example2() {
+        let (row, column) = decode_seat("BFFFBBFRRR").unwrap();
+        assert_eq!(row, 70);
+        assert_eq!(column, 7);
+    }
+
+    #[test]
+    fn test_part1_example3() {
+        let (row, column) = decode_seat("FFFBBBFRRR").unwrap();
+        assert_eq!(row, 14);
+        assert_eq!(column, 7);
+    }
+
+    #[test]
+    fn test_part1_example4() {
+        let (row, column) = decode_seat("BBFFBBFRLL").unwrap();
+        assert_eq!(row, 102);
+        assert_eq!(column, 4);
+    }
+
+    #[test]
+    fn test_find_missing_seat_id() {
+        assert_eq!(find_missing_seat_id(&[8, 5, 4, 7]), Some(6));
+        assert_eq!(find_missing_seat_id(&[4, 5, 6]), None);
+    }
+
+    #[test]
+    fn test_invalid_seat_code() {
+        let error = parse_seat_ids("FBFBBFFRLR\nFBFBXFFRLR\n").unwrap_err();
+        assert_eq!(error.to_string(),
+            "line 2, column 1: expected a seat code like `FBFBBFFRLR`, found `FBFBXFFRLR`");
+    }
+
+    #[test]
+    fn test_multi_byte_seat_code() {
+        // 10 bytes long, with a 3 byte character across the end of the row code.
+        assert_eq!(decode_seat("FBFBB\u{fe38}LR"), None);
+    }
+
+    proptest! {
+        #[test]
+        fn decode_inverts_encode(row in 0u32..128, column in 0u32..8) {
+            prop_assert_eq!(decode_seat(&encode_seat(row, column)), Some((row, column)));
+        }
+
+        #[test]
+        fn decoded_codes_encode_back(code in "[FBLR]{10}|\\PC{0,12}") {
+            if let Some((row, column)) = decode_seat(&code) {
+                prop_assert_eq!(encode_seat(row, column), code);
+            }
+        }
+    }
+}
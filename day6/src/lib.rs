@@ -0,0 +1,148 @@
+//! Day 6: Custom Customs.
+//!
+//! Count the customs declaration questions which groups of passengers answered yes to.
+
+use aoc_common::{combinators::{lines, paragraphs, parse_all, recognize, repeat, satisfy}, ParseError};
+use tracing::{instrument, trace};
+
+/// Parse groups of answers. Groups are separated by blank lines,
+/// and each line in a group holds the questions (`a` to `z`) one person answered yes to.
+pub fn parse_groups(text: &str) -> Result<Vec<Vec<&str>>, ParseError> {
+    let question = satisfy("a question from `a` to `z`", |c| c.is_ascii_lowercase());
+    parse_all(text, paragraphs(lines(recognize(repeat(question)))))
+}
+
+/// Solve part 1: the sum over groups of the questions which anyone in the group answered yes to.
+#[instrument(skip(text), ret)]
+pub fn solve_part_1(text: &str) -> Result<u32, ParseError> {
+    Ok(parse_groups(text)?
+        .iter()
+        .map(|group| count_group_questions_with_a_yes(group))
+        .sum())
+}
+
+/// Solve part 2: the sum over groups of the questions which everyone in the group answered yes to.
+#[instrument(skip(text), ret)]
+pub fn solve_part_2(text: &str) -> Result<u32, ParseError> {
+    Ok(parse_groups(text)?
+        .iter()
+        .map(|group| count_group_questions_all_yes(group))
+        .sum())
+}
+
+fn letter_to_index(c: char) -> usize {
+    (c as usize) - ('a' as usize)
+}
+
+/// Count the number of questions for which at least one person in the group answered yes.
+pub fn count_group_questions_with_a_yes(group_answers: &[&str]) -> u32 {
+    // For example, `question_has_a_yes[letter_to_index('b')]` will be set to true
+    // IFF at least one personin the group answered yes to question 'b', i.e. if
+    // `'b'` appears in the list of strings.
+    let mut question_has_a_yes: [bool; 26] = [false; 26];
+    for person_answers in group_answers {
+        for c in person_answers.chars() {
+            question_has_a_yes[letter_to_index(c)] = true;
+        }
+    }
+    let mut yes_count = 0;
+    for b in &question_has_a_yes {
+        if *b {
+            yes_count += 1;
+        }
+    }
+    trace!(?group_answers, yes_count, "counted questions with a yes");
+    yes_count
+}
+
+/// Count the number of questions for which every person in the group answered yes.
+pub fn count_group_questions_all_yes(group_answers: &[&str]) -> u32 {
+    let number_of_people = group_answers.len() as u32;
+    // For example, `question_yes_counts[letter_to_index('b')]` will be number of
+    // people in the group who answered yes to question 'b'.
+    let mut question_yes_counts: [u32; 26] = [0; 26];
+    for person_answers in group_answers {
+        for c in person_answers.chars() {
+            question_yes_counts[letter_to_index(c)] += 1;
+        }
+    }
+
+    let mut all_yes_count = 0;  // number of questions for which everyone answered yes.
+    for count in &question_yes_counts {
+        if *count == number_of_people {
+            all_yes_count += 1;
+        }
+    }
+    trace!(?group_answers, all_yes_count, "counted questions which everyone answered yes");
+    all_yes_count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Tests for `count_group_questions_with_a_yes`
+    #[test]
+    fn test_count_group_with_yes_example_1() {
+        let group_answers: Vec<&str> = vec!["abc"];
+        let yes_count = count_group_questions_with_a_yes(&group_answers);
+        assert_eq!(yes_count, 3);
+    }
+
+    #[test]
+    fn test_count_group_with_yes_example_2() {
+        let group_answers: Vec<&str> = vec!["a", "b", "c"];
+        let yes_count = count_group_questions_with_a_yes(&group_answers);
+        assert_eq!(yes_count, 3);
+    }
+
+    #[test]
+    fn test_count_group_with_yes_example_3() {
+        let group_answers: Vec<&str> = vec!["ab" , "ac"];
+        let yes_count = count_group_questions_with_a_yes(&group_answers);
+        assert_eq!(yes_count, 3);
+    }
+
+    #[test]
+    fn test_count_group_with_yes_example_4() {
+        let group_answers: Vec<&str> = vec!["a", "a", "a", "a"];
+        let yes_count = count_group_questions_with_a_yes(&group_answers);
+        assert_eq!(yes_count, 1);
+    }
+
+    // Tests for `count_group_questions_all_yes`
+    #[test]
+    fn test_count_group_all_yes_example_1() {
+        let group_answers: Vec<&str> = vec!["abc"];
+        let yes_count = count_group_questions_all_yes(&group_answers);
+        assert_eq!(yes_count, 3);
+    }
+
+    #[test]
+    fn test_count_group_all_yes_example_2() {
+        let group_answers: Vec<&str> = vec!["a", "b", "c"];
+        let yes_count = count_group_questions_all_yes(&group_answers);
+        assert_eq!(yes_count, 0);
+    }
+
+    #[test]
+    fn test_count_group_all_yes_example_3() {
+        let group_answers: Vec<&str> = vec!["ab" , "ac"];
+        let yes_count = count_group_questions_all_yes(&group_answers);
+        assert_eq!(yes_count, 1);
+    }
+
+    #[test]
+    fn test_count_group_all_yes_example_4() {
+        let group_answers: Vec<&str> = vec!["a", "a", "a", "a"];
+        let yes_count = count_group_questions_all_yes(&group_answers);
+        assert_eq!(yes_count, 1);
+    }
+
+    #[test]
+    fn test_parse_groups() {
+        assert_eq!(parse_groups("abc\n\na\nb\n").unwrap(), vec![vec!["abc"], vec!["a", "b"]]);
+        let error = parse_groups("abc\n\na\nbC\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 4, column 2: expected a question from `a` to `z`, found `C`");
+    }
+}
\ No newline at end of file
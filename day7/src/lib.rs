@@ -0,0 +1,180 @@
+//! Day 7: Handy Haversacks.
+//!
+//! Bag rules like `light red bags contain 1 bright white bag, 2 muted yellow bags.` form a
+//! directed graph, with an edge from each bag color to the colors it contains, weighted by
+//! the number of bags contained.
+
+use std::collections::HashSet;
+use aoc_common::{combinators::{ending_with, integer, lines, opt, parse_all, recognize, split, tag, word, Parser}, ParseError};
+use petgraph::{graphmap::DiGraphMap, Direction};
+use tracing::{debug, instrument, trace};
+
+/// The color of the bag which both parts of the puzzle ask about.
+pub const MY_BAG: &str = "shiny gold";
+
+/// Solve part 1: the number of bag colors which can eventually contain a `MY_BAG` bag.
+#[instrument(skip(text), ret)]
+pub fn solve_part_1(text: &str) -> Result<usize, ParseError> {
+    let graph = parse_graph_with_my_bag(text)?;
+    Ok(compute_bags_that_can_contain(&graph, MY_BAG).len())
+}
+
+/// Solve part 2: the number of bags required inside a `MY_BAG` bag.
+#[instrument(skip(text), ret)]
+pub fn solve_part_2(text: &str) -> Result<u32, ParseError> {
+    let graph = parse_graph_with_my_bag(text)?;
+    Ok(compute_bags_inside(&graph, MY_BAG))
+}
+
+/// Parse the graph, and check that the rules mention `MY_BAG`.
+fn parse_graph_with_my_bag(text: &str) -> Result<DiGraphMap<&str, u32>, ParseError> {
+    let graph = parse_graph_from_text(text)?;
+    if !graph.contains_node(MY_BAG) {
+        return Err(ParseError::missing_section(text, "a rule which mentions shiny gold bags"));
+    }
+    Ok(graph)
+}
+
+/// Compute the number of bags inside a bag of a given color
+pub fn compute_bags_inside(graph: &DiGraphMap<&str, u32>, bag_color: &str) -> u32 {
+    assert!(graph.contains_node(bag_color));
+
+    let mut count = 0;
+    for v in graph.neighbors_directed(bag_color, Direction::Outgoing) {
+        count += graph.edge_weight(bag_color, v).unwrap() * (1 + compute_bags_inside(graph, v));
+    }
+    trace!(bag_color, count, "counted bags inside");
+    count
+}
+
+/// Compute all the bag colors which can eventually contain a bag of color `start_bag`.
+pub fn compute_bags_that_can_contain<'a>(graph: &DiGraphMap<&'a str, u32>, start_bag: &'a str) -> HashSet<&'a str> {
+    assert!(graph.contains_node(start_bag));
+
+    // Use breadth-first search.
+    // Queue `Q` in CLRS page 595.
+    let mut search_queue = Vec::<&str>::new();
+    // Nodes which are colored black in CLRS page 595.
+    let mut upstream_bags = HashSet::<&str>::new();
+    search_queue.push(start_bag);
+    while let Some(u) = search_queue.pop() {
+        
+        for v in graph.neighbors_directed(u, Direction::Incoming) {
+            if !upstream_bags.contains(v) {
+                trace!(outer = v, inner = u, "found a bag which can contain");
+                search_queue.push(v);
+            }
+        }
+        upstream_bags.insert(u);
+    }
+
+    upstream_bags.remove(start_bag);
+    upstream_bags
+}
+
+/// Parse the bag rules, one per line, into a graph of which bags contain which.
+pub fn parse_graph_from_text(text: &str) -> Result<DiGraphMap<&str, u32>, ParseError> {
+    let mut graph = DiGraphMap::<&str, u32>::new();
+    for rule in parse_all(text, lines(bag_rule()))? {
+        graph = add_rule_to_graph(graph, rule);
+    }
+    debug!(colors = graph.node_count(), rules = graph.edge_count(), "built the bag graph");
+    Ok(graph)
+}
+
+fn add_rule_to_graph<'a>(mut graph: DiGraphMap<&'a str, u32>, rule: BagRule<'a>) -> DiGraphMap::<&'a str, u32> {
+    let (subject_bag_color, contained) = rule;
+
+    if !graph.contains_node(subject_bag_color) {
+        graph.add_node(subject_bag_color);
+    }
+
+    for (other_bag_color, number_in_subject_bag) in contained {
+        if !graph.contains_node(other_bag_color) {
+            graph.add_node(other_bag_color);
+        }
+        graph.add_edge(subject_bag_color, other_bag_color, number_in_subject_bag);
+    }
+    graph
+}
+
+/// One bag rule: the subject bag color, and the (color, number) of each bag which it must contain.
+pub type BagRule<'a> = (&'a str, Vec<(&'a str, u32)>);
+
+/// Parse one bag rule into the subject bag color,
+/// and the (color, number) of each bag which it must contain.
+/// Errors are reported as if `rule_str` is the first line of the input.
+pub fn parse_rule(rule_str: &str) -> Result<BagRule<'_>, ParseError> {
+    parse_all(rule_str, bag_rule())
+}
+
+/// Parser for one bag rule, like `light red bags contain 1 bright white bag, 2 muted yellow bags.`
+fn bag_rule<'a>() -> impl Parser<'a, BagRule<'a>> {
+    let color = || recognize((word(), tag(" "), word()));
+    let bags = (integer::<u32>(), tag(" "), color(), tag(" bag"), opt(tag("s")))
+        .map(|(number, _, color, _, _)| (color, number))
+        .expect("a number of bags like `2 muted yellow bags`");
+    let contents = tag("no other bags").map(|_| Vec::new()).or(split(", ", bags));
+    (color(), tag(" bags contain "), ending_with(".", contents))
+        .map(|(color, _, contents)| (color, contents))
+        .expect("a rule like `light red bags contain 1 bright white bag.`")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_parse_rule_1 () {
+        let rule_str = "light red bags contain 1 bright white bag, 2 muted yellow bags.";
+        let (subject_bag_color, contains) = parse_rule(rule_str).unwrap();
+        assert_eq!("light red", subject_bag_color);
+        assert_eq!(2, contains.len());
+        assert_eq!("bright white", contains[0].0);
+        assert_eq!(1, contains[0].1);
+    }
+
+    #[test]
+    fn test_parse_rule_many_bags () {
+        let rule_str = "faded blue bags contain 12 dotted black bags.";
+        let (_, contains) = parse_rule(rule_str).unwrap();
+        assert_eq!(vec![("dotted black", 12)], contains);
+        assert!(parse_rule("dotted black bags contain no other bags.").unwrap().1.is_empty());
+    }
+
+    #[test]
+    fn test_parse_errors () {
+        let text = "light red bags contain 1 bright white bag.\nbright white bags contain some shiny gold bags.\n";
+        let error = parse_graph_from_text(text).unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 27: expected a number of bags like `2 muted yellow bags`, \
+            found `some shiny gold bags`");
+        let error = parse_graph_from_text("light red bags hold 1 bright white bag.").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+    }
+
+    #[test]
+    fn test_can_contain_example () {
+        let text = fs::read_to_string("./example_input.txt").unwrap();
+        let graph = parse_graph_from_text(&text).unwrap();
+        let containing_bags = compute_bags_that_can_contain(&graph, "shiny gold");
+        assert_eq!(4, containing_bags.len());
+        assert!(containing_bags.contains("bright white"));
+        assert!(containing_bags.contains("muted yellow"));
+        assert!(containing_bags.contains("dark orange"));
+        assert!(containing_bags.contains("light red"));
+    }
+    
+    #[test]
+    fn compute_bags_inside_example1 () {
+        let text = fs::read_to_string("./example_input.txt").unwrap();
+        let graph = parse_graph_from_text(&text).unwrap();
+
+        assert_eq!(0, compute_bags_inside(&graph, "faded blue"));
+        assert_eq!(0, compute_bags_inside(&graph, "dotted black"));
+        assert_eq!(11, compute_bags_inside(&graph, "vibrant plum"));
+        assert_eq!(7, compute_bags_inside(&graph, "dark olive"));
+        assert_eq!(32, compute_bags_inside(&graph, "shiny gold"));
+
+    }
+}
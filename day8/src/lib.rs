@@ -0,0 +1,251 @@
+//! Day 8: Handheld Halting.
+//!
+//! Run the handheld game console's boot code, a program of `acc`, `jmp` and `nop` instructions,
+//! and repair the single corrupt instruction which makes it loop forever.
+
+use std::collections::HashSet;
+use aoc_common::{combinators::{integer, lines, parse_all, peek, satisfy, tag, Parser}, ParseError, Solution};
+use tracing::{debug, instrument, trace};
+
+
+/// Why `fix_corrupt_instruction` can fail.
+const NO_REPAIR: &str = "could not find a NOP->JMP or JMP->NOP change which makes the program reach its end";
+
+/// The operation of one boot code instruction.
+#[derive(Clone, PartialEq, Debug)]
+#[allow(clippy::upper_case_acronyms)] // Named after the opcodes in the puzzle's source code.
+pub enum Operation {
+    ACC, // Increases or decreases the accumulator value.
+    JMP, // Jumps to a new instruction relative to itself.
+    NOP, // No operation.
+}
+
+/// One boot code instruction, like `jmp -4`.
+#[derive(Clone, Debug)]
+pub struct Instruction {
+    pub operation: Operation,
+    pub argument: i32,
+}
+
+/// Solve part 1: the accumulator value immediately before any instruction runs a second time.
+#[instrument(skip(source), ret)]
+pub fn solve_part_1(source: &str) -> Result<i32, ParseError> {
+    let (accumulator, _) = run_program(&parse_program(source)?);
+    Ok(accumulator)
+}
+
+/// Solve part 2: the accumulator value after the repaired program reaches its end.
+///
+/// # Panics
+/// Panics if no single instruction can be repaired to make the program reach its end.
+#[instrument(skip(source), ret)]
+pub fn solve_part_2(source: &str) -> Result<i32, ParseError> {
+    let (accumulator, _) = fix_corrupt_instruction(&parse_program(source)?).expect(NO_REPAIR);
+    Ok(accumulator)
+}
+
+/// Solve part 2, and also give the index (counting from 0) of the corrupt instruction.
+///
+/// # Panics
+/// Panics if no single instruction can be repaired to make the program reach its end.
+#[instrument(skip(source), ret)]
+pub fn solve_part_2_detailed(source: &str) -> Result<Solution, ParseError> {
+    let (accumulator, i_corrupt) = fix_corrupt_instruction(&parse_program(source)?).expect(NO_REPAIR);
+    Ok(Solution::new(accumulator).with("corrupt_instruction_index", i_corrupt))
+}
+
+/// Parse the boot code, one instruction per line.
+pub fn parse_program(source: &str) -> Result<Vec<Instruction>, ParseError> {
+    let operation = tag("acc").map(|_| Operation::ACC)
+        .or(tag("jmp").map(|_| Operation::JMP))
+        .or(tag("nop").map(|_| Operation::NOP));
+    // The argument always has a sign.
+    let argument = (peek(satisfy("`+` or `-`", |c| c == '+' || c == '-')), integer::<i32>())
+        .map(|(_, argument)| argument);
+    let instruction = (operation, tag(" "), argument)
+        .map(|(operation, _, argument)| Instruction { operation, argument })
+        .expect("an instruction like `acc +1`");
+    parse_all(source, lines(instruction))
+}
+
+/// Why `run_program` stopped.
+#[derive(PartialEq, Debug)]
+pub enum TerminationCondition {
+    RepeatedInstruction,
+    ReachedEnd,
+    /// The program jumped to before the first instruction, or past the end of the program.
+    JumpedOutOfBounds,
+}
+
+/// Run the program until any instruction is called a second time, or the program terminates by
+/// attempting to execute an instruction immediately after the last instruction in the file.
+/// Return the value of the accumulator one of the termination conditions happens.
+/// The accumulator wraps around on overflow.
+/// This always terminates, because there are only so many instructions which can run once.
+pub fn run_program(program: &[Instruction]) -> (i32, TerminationCondition) {
+    let mut accumulator: i32 = 0;
+    let mut location: usize = 0;
+    let mut executed_locations = HashSet::<usize>::new();
+
+    loop {
+        // Keep track of which instructions we've executed before.
+        if executed_locations.contains(&location) {
+            trace!(location, accumulator, "repeated an instruction");
+            return (accumulator, TerminationCondition::RepeatedInstruction);
+        }
+        else {
+            executed_locations.insert(location);
+        }
+        // Check if we've reached the end of the program.
+        if location == program.len() {
+            return (accumulator, TerminationCondition::ReachedEnd);
+        }
+
+        // Execute this instruction.
+        let (target, new_accumulator) = execute(&program[location], location, accumulator);
+        accumulator = new_accumulator;
+        if target < 0 || target > program.len() as i64 {
+            trace!(location, target, accumulator, "jumped out of bounds");
+            return (accumulator, TerminationCondition::JumpedOutOfBounds);
+        }
+        location = target as usize;
+    }
+}
+
+/// Execute one instruction, found at `location`, with the accumulator at `accumulator`.
+/// Returns the location of the next instruction, which may be outside the program, and the new
+/// accumulator value. The accumulator wraps around on overflow.
+pub fn execute(instruction: &Instruction, location: usize, accumulator: i32) -> (i64, i32) {
+    match instruction.operation {
+        Operation::ACC => (location as i64 + 1, accumulator.wrapping_add(instruction.argument)),
+        Operation::JMP => (location as i64 + instruction.argument as i64, accumulator),
+        Operation::NOP => (location as i64 + 1, accumulator),
+    }
+}
+
+/// Copy the program, with instruction `i` changed from JMP to NOP or from NOP to JMP.
+/// ACC instructions are left as they are.
+pub fn repair_instruction(program: &[Instruction], i: usize) -> Vec<Instruction> {
+    let mut new_program = program.to_vec();
+    new_program[i].operation = match program[i].operation {
+        Operation::JMP => Operation::NOP,
+        Operation::NOP => Operation::JMP,
+        Operation::ACC => Operation::ACC,
+    };
+    new_program
+}
+
+/// Find the single JMP or NOP instruction which, when swapped for the other, makes the program
+/// reach its end.
+/// Returns (accumulator value at termination for the fixed program, index of corrupt instruction),
+/// or `None` if no such instruction exists.
+/// Computational effort: This should run on O(n^2) time, where n is the number of instructions in the program.
+///     - Need to check O(n) JMP/NOP substitutions.
+///     - Checking each substitution requires iterating through O(n) instructions.
+pub fn fix_corrupt_instruction(original_program: &[Instruction]) -> Option<(i32, usize)> {
+    for i in 0..original_program.len() {
+        if original_program[i].operation == Operation::ACC {
+            continue;
+        }
+
+        // Change instruction `i` from JMP -> NOP or visa versa.
+        let new_program = repair_instruction(original_program, i);
+
+        // See if the modified program reaches its end.
+        let (accumulator, term_cond) = run_program(&new_program);
+        debug!(index = i, operation = ?new_program[i].operation, ?term_cond, "tried a repair");
+        if term_cond == TerminationCondition::ReachedEnd {
+            return Some((accumulator, i));
+        }
+    }
+    return None;
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::fs;
+
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        let operation = prop_oneof![Just(Operation::ACC), Just(Operation::JMP), Just(Operation::NOP)];
+        (operation, -20i32..20).prop_map(|(operation, argument)| Instruction { operation, argument })
+    }
+
+    #[test]
+    fn test_acc_repeat_example () {
+        let source = fs::read_to_string("./example_input.txt").unwrap();
+        let program = parse_program(&source).unwrap();
+        let (accumulator, term_cond) = run_program(&program);
+        assert_eq!(TerminationCondition::RepeatedInstruction, term_cond);
+        assert_eq!(5, accumulator);
+    }
+
+    #[test]
+    fn test_reach_end_example () {
+        let source = fs::read_to_string("./example_input.txt").unwrap();
+        let mut program = parse_program(&source).unwrap();
+        program[7].operation = Operation::NOP;
+        let (accumulator, term_cond) = run_program(&program);
+        assert_eq!(TerminationCondition::ReachedEnd, term_cond);
+        assert_eq!(8, accumulator);
+    }
+
+    #[test]
+    fn test_fix_example () {
+        let source = fs::read_to_string("./example_input.txt").unwrap();
+        let program = parse_program(&source).unwrap();
+        let (accumulator, i_corrupt) = fix_corrupt_instruction(&program).unwrap();
+        assert_eq!(7, i_corrupt);
+        assert_eq!(8, accumulator);
+        let solution = solve_part_2_detailed(&source).unwrap();
+        assert_eq!(solution, Solution::new(8).with("corrupt_instruction_index", 7));
+    }
+
+    #[test]
+    fn test_parse_errors () {
+        let error = parse_program("nop +0\nacc 1\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 1: expected an instruction like `acc +1`, found `acc 1`");
+        let error = parse_program("nop +0\njmp +99999999999\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 5: invalid number `+99999999999`");
+    }
+
+    #[test]
+    fn test_jump_out_of_bounds () {
+        let program = parse_program("nop +0\njmp -2\n").unwrap();
+        assert_eq!(run_program(&program), (0, TerminationCondition::JumpedOutOfBounds));
+        let program = parse_program("acc +2147483647\nacc +1\njmp +3\n").unwrap();
+        assert_eq!(run_program(&program), (i32::MIN, TerminationCondition::JumpedOutOfBounds));
+    }
+
+    #[test]
+    fn test_no_fix () {
+        let program = parse_program("nop +0\nacc +1\njmp -1\njmp -1\n").unwrap();
+        assert_eq!(fix_corrupt_instruction(&program), None);
+    }
+
+    proptest! {
+        #[test]
+        fn run_program_terminates(program in prop::collection::vec(instruction(), 0..50)) {
+            // Returning at all is the property: every program stops one way or another.
+            let (_, term_cond) = run_program(&program);
+            if program.is_empty() {
+                prop_assert_eq!(term_cond, TerminationCondition::ReachedEnd);
+            }
+        }
+
+        #[test]
+        fn fixed_program_reaches_end(program in prop::collection::vec(instruction(), 0..50)) {
+            if let Some((accumulator, i_corrupt)) = fix_corrupt_instruction(&program) {
+                let mut fixed_program = program.clone();
+                fixed_program[i_corrupt].operation = match program[i_corrupt].operation {
+                    Operation::JMP => Operation::NOP,
+                    Operation::NOP => Operation::JMP,
+                    Operation::ACC => panic!("an ACC instruction cannot be corrupt"),
+                };
+                prop_assert_eq!(run_program(&fixed_program), (accumulator, TerminationCondition::ReachedEnd));
+            }
+        }
+    }
+}
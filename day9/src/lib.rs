@@ -0,0 +1,165 @@
+//! Day 9: Encoding Error.
+//!
+//! Find the weakness in the "XMAS" cypher, where every number after the preamble should be
+//! the sum of two different numbers among the previous `preamble_length` numbers.
+
+use aoc_common::{parse_number_lines, ParseError, Solution};
+use tracing::{debug, instrument};
+
+/// The preamble length used by the puzzle input.
+pub const PREAMBLE_LENGTH: usize = 25;
+
+/// Parse the list of numbers, one per line.
+/// There must be more numbers than the `PREAMBLE_LENGTH`.
+pub fn parse_numbers(text: &str) -> Result<Vec<u64>, ParseError> {
+    let numbers = parse_number_lines(text)?;
+    if numbers.len() <= PREAMBLE_LENGTH {
+        return Err(ParseError::missing_section(text, "the numbers after the preamble"));
+    }
+    Ok(numbers)
+}
+
+/// Solve part 1: the first number which is not the sum of two of the previous 25 numbers.
+///
+/// # Panics
+/// Panics if every number is valid.
+#[instrument(skip(text), ret)]
+pub fn solve_part_1(text: &str) -> Result<u64, ParseError> {
+    Ok(xmas_find_first_invalid_number(&parse_numbers(text)?, PREAMBLE_LENGTH).expect("every number is valid"))
+}
+
+/// Solve part 2: the encryption weakness.
+///
+/// # Panics
+/// Panics if every number is valid, or no contiguous set adds up to the invalid number.
+#[instrument(skip(text), ret)]
+pub fn solve_part_2(text: &str) -> Result<u64, ParseError> {
+    let numbers = parse_numbers(text)?;
+    let first_invalid = xmas_find_first_invalid_number(&numbers, PREAMBLE_LENGTH).expect("every number is valid");
+    Ok(encryption_weakness(&numbers, first_invalid).expect("no contiguous set adds up to the invalid number"))
+}
+
+/// Solve part 2, and also give the invalid number which the contiguous set adds up to.
+///
+/// # Panics
+/// Panics if every number is valid, or no contiguous set adds up to the invalid number.
+#[instrument(skip(text), ret)]
+pub fn solve_part_2_detailed(text: &str) -> Result<Solution, ParseError> {
+    let numbers = parse_numbers(text)?;
+    let first_invalid = xmas_find_first_invalid_number(&numbers, PREAMBLE_LENGTH).expect("every number is valid");
+    let weakness = encryption_weakness(&numbers, first_invalid).expect("no contiguous set adds up to the invalid number");
+    Ok(Solution::new(weakness).with("invalid_number", first_invalid))
+}
+
+/// Compute the encryption weakness: the sum of the smallest and largest numbers in the
+/// contiguous set of numbers which adds up to `invalid_number`.
+pub fn encryption_weakness(numbers: &[u64], invalid_number: u64) -> Option<u64> {
+    let contig_set = find_contiguous_set_sum(numbers, invalid_number)?;
+    debug!(length = contig_set.len(), "found the contiguous set");
+    let smallest = contig_set.iter().min().unwrap();
+    let largest = contig_set.iter().max().unwrap();
+    Some(smallest + largest)
+}
+
+/// Find the first number in a list which is invalid according to the rules of the
+/// "XMAS" cypher.
+pub fn xmas_find_first_invalid_number(numbers: &[u64], preamble_length: usize) -> Option<u64> {
+    assert!(numbers.len() > preamble_length);
+    for i in preamble_length..numbers.len() {
+        if !matches_sum_of_any_pair(&numbers[i-preamble_length..i], numbers[i]) {
+            debug!(index = i, number = numbers[i], "found the first invalid number");
+            return Some(numbers[i]);
+        }
+    }
+    return None;
+}
+
+/// Find a contiguous set of at least one number in the list which adds up to `target_value`.
+pub fn find_contiguous_set_sum(numbers: &[u64], target_value: u64) -> Option<&[u64]> {
+    // `running_sums[j]` is the sum of last `j` numbers.
+    let mut running_sums = Vec::<u64>::new();
+    running_sums.push(numbers[0]);
+    if running_sums[0] == target_value {
+        return Some(&numbers[0..1]);
+    }
+
+    for i in 1..numbers.len() {
+        for j in 0..i {
+            running_sums[j] += numbers[i];
+            running_sums[j] -= numbers[i - j - 1];
+            if running_sums[j] == target_value {
+                return Some(&numbers[i - j .. i + 1]);
+            }
+        }
+        running_sums.push(running_sums[i - 1] + numbers[0]);
+        if running_sums[i] == target_value {
+            return Some(&numbers[0 .. i + 1]);
+        }
+    }
+    return None;
+}
+
+/// Check if the sum of any pair of numbers in a given list matches a target value.
+fn matches_sum_of_any_pair(numbers: &[u64], target_value: u64) -> bool {
+    for (i1, x1) in numbers.iter().enumerate() {
+        for x2 in numbers[i1..].iter() {
+            if x1 == x2 { continue; }
+            if x1 + x2 == target_value { return true; }
+        }
+    }
+    return false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_1_to_25_examples() {
+        let numbers: Vec<u64> = (1..26).collect();
+        assert!(matches_sum_of_any_pair(&numbers, 26));
+        assert!(matches_sum_of_any_pair(&numbers, 49));
+        assert!(!matches_sum_of_any_pair(&numbers, 100));
+        assert!(!matches_sum_of_any_pair(&numbers, 50));
+    }
+
+    #[test]
+    fn test_find_first_invalid_preable_5_example() {
+        let numbers = vec![35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182,
+            127, 219, 299, 277, 309, 576];
+        let result = xmas_find_first_invalid_number(&numbers, 5).unwrap();
+        assert_eq!(result, 127);
+    }
+
+    #[test]
+    fn test_find_contiguous_set_sum_example() {
+        // Setup
+        let numbers = vec![35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182,
+            127, 219, 299, 277, 309, 576];
+        // Action
+        let contig_set = find_contiguous_set_sum(&numbers, 127).unwrap();
+        // Verification
+        assert_eq!(contig_set.len(), 4);
+        assert_eq!(contig_set[0], 15);
+        assert_eq!(contig_set[3], 40);
+    }
+
+    #[test]
+    fn test_find_contiguous_set_sum_first() {
+        // Setup
+        let numbers = vec![35, 20, 15];
+        // Action
+        let contig_set = find_contiguous_set_sum(&numbers, 35).unwrap();
+        // Verification
+        assert_eq!(contig_set.len(), 1);
+        assert_eq!(contig_set[0], 35);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_numbers("35\n20\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 3, column 1: input ended before the numbers after the preamble");
+        let error = parse_numbers("35\n2O\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 1: invalid number `2O`");
+    }
+}
@@ -0,0 +1,143 @@
+//! Fuzz targets for each day's input parser.
+//!
+//! Each target takes arbitrary bytes, and must not panic, loop forever or overflow the stack on
+//! any of them. Invalid input should give a `ParseError`. Where a parser promises that its output
+//! is safe to solve, the target also runs the solver on it.
+//!
+//! The targets are functions here, rather than only in `fuzz_targets/`, so that
+//! `tests/regressions.rs` can replay the corpus of inputs which used to crash them.
+
+use std::str;
+
+/// Run a fuzz target's body on `data`, if it is valid UTF-8. The parsers only accept text.
+fn with_text(data: &[u8], body: impl FnOnce(&str)) {
+    if let Ok(text) = str::from_utf8(data) {
+        body(text);
+    }
+}
+
+pub fn day1_parse_expense_report(data: &[u8]) {
+    with_text(data, |text| { let _ = day1::parse_expense_report(text); });
+}
+
+pub fn day2_parse_password_database(data: &[u8]) {
+    with_text(data, |text| { let _ = day2::parse_password_database(text); });
+}
+
+pub fn day3_load_terrain(data: &[u8]) {
+    with_text(data, |text| {
+        if let Ok(terrain) = day3::load_terrain(text) {
+            day3::count_trees(0, 3, 1, &terrain);
+        }
+    });
+}
+
+pub fn day4_parse_passports(data: &[u8]) {
+    with_text(data, |text| { let _ = day4::count_valid_passports(text); });
+}
+
+pub fn day5_parse_seat_ids(data: &[u8]) {
+    with_text(data, |text| {
+        if let Ok(seat_ids) = day5::parse_seat_ids(text) {
+            day5::find_missing_seat_id(&seat_ids);
+        }
+    });
+}
+
+pub fn day6_parse_groups(data: &[u8]) {
+    with_text(data, |text| { let _ = day6::parse_groups(text); });
+}
+
+pub fn day7_parse_graph(data: &[u8]) {
+    with_text(data, |text| { let _ = day7::parse_graph_from_text(text); });
+}
+
+pub fn day8_parse_program(data: &[u8]) {
+    with_text(data, |text| {
+        if let Ok(program) = day8::parse_program(text) {
+            day8::run_program(&program);
+            day8::fix_corrupt_instruction(&program);
+        }
+    });
+}
+
+pub fn day9_parse_numbers(data: &[u8]) {
+    with_text(data, |text| { let _ = day9::parse_numbers(text); });
+}
+
+pub fn day10_parse_adapters(data: &[u8]) {
+    with_text(data, |text| { let _ = day10::parse_adapters(text); });
+}
+
+pub fn day11_parse_seating_area(data: &[u8]) {
+    with_text(data, |text| { let _ = day11::parse_seating_area(text); });
+}
+
+pub fn day12_parse_nav_instructions(data: &[u8]) {
+    with_text(data, |text| { let _ = day12::parse_nav_instructions(text); });
+}
+
+pub fn day13_parse_notes(data: &[u8]) {
+    with_text(data, |text| { let _ = day13::parse_notes(text); });
+}
+
+pub fn day14_parse_program(data: &[u8]) {
+    with_text(data, |text| { let _ = day14::parse_program(text); });
+}
+
+pub fn day15_parse_starting_numbers(data: &[u8]) {
+    with_text(data, |text| { let _ = day15::parse_starting_numbers(text); });
+}
+
+pub fn day16_parse_notes(data: &[u8]) {
+    with_text(data, |text| { let _ = day16::parse_notes(text); });
+}
+
+pub fn day17_parse_initial_slice(data: &[u8]) {
+    with_text(data, |text| { let _ = day17::parse_initial_slice(text); });
+}
+
+pub fn day18_parse_expressions(data: &[u8]) {
+    with_text(data, |text| {
+        if let Ok(expressions) = day18::parse_expressions(text) {
+            for expression in expressions {
+                day18::evaluate_expression(expression);
+                day18::evaluate_infix_expression(expression, &day18::PART_2_PRECEDENCE);
+            }
+        }
+    });
+}
+
+pub fn day19_parse_input(data: &[u8]) {
+    with_text(data, |text| {
+        if let Ok((ruleset, messages)) = day19::parse_input(text) {
+            day19::count_matching_messages(&ruleset, &messages);
+        }
+    });
+}
+
+/// A fuzz target's body, which is given the fuzzer's input bytes.
+pub type Target = fn(&[u8]);
+
+/// Every target, by name, for replaying the regression corpus.
+pub const TARGETS: [(&str, Target); 19] = [
+    ("day1_parse_expense_report", day1_parse_expense_report),
+    ("day2_parse_password_database", day2_parse_password_database),
+    ("day3_load_terrain", day3_load_terrain),
+    ("day4_parse_passports", day4_parse_passports),
+    ("day5_parse_seat_ids", day5_parse_seat_ids),
+    ("day6_parse_groups", day6_parse_groups),
+    ("day7_parse_graph", day7_parse_graph),
+    ("day8_parse_program", day8_parse_program),
+    ("day9_parse_numbers", day9_parse_numbers),
+    ("day10_parse_adapters", day10_parse_adapters),
+    ("day11_parse_seating_area", day11_parse_seating_area),
+    ("day12_parse_nav_instructions", day12_parse_nav_instructions),
+    ("day13_parse_notes", day13_parse_notes),
+    ("day14_parse_program", day14_parse_program),
+    ("day15_parse_starting_numbers", day15_parse_starting_numbers),
+    ("day16_parse_notes", day16_parse_notes),
+    ("day17_parse_initial_slice", day17_parse_initial_slice),
+    ("day18_parse_expressions", day18_parse_expressions),
+    ("day19_parse_input", day19_parse_input),
+];
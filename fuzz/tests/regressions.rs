@@ -0,0 +1,23 @@
+//! Replay the inputs in `regressions/<target>/`, which each crashed a fuzz target before it was fixed.
+
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn test_regression_corpus() {
+    let regressions_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("regressions");
+    let mut replayed = 0;
+    for &(name, target) in aoc_fuzz::TARGETS.iter() {
+        let target_dir = regressions_dir.join(name);
+        if !target_dir.exists() {
+            continue;
+        }
+        for entry in fs::read_dir(&target_dir).unwrap() {
+            let path = entry.unwrap().path();
+            println!("replaying {}", path.display());
+            target(&fs::read(&path).unwrap());
+            replayed += 1;
+        }
+    }
+    assert!(replayed > 0, "no regression inputs found in {}", regressions_dir.display());
+}